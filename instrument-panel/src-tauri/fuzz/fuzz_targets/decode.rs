@@ -0,0 +1,15 @@
+//! Arbitrary bytes as a HWiNFO shared-memory region: the decoder and both
+//! debug dumps must reject or decode them without panicking, reading out of
+//! bounds or allocating more than the region holds.
+
+#![no_main]
+
+use instrument_panel_lib::hwinfo::decode;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: &[u8]| {
+    let _ = decode::decode(bytes);
+    let _ = decode::dump_sensors(bytes);
+    let _ = decode::dump_readings(bytes, None);
+    let _ = decode::dump_readings(bytes, Some("temp"));
+});
@@ -0,0 +1,16 @@
+//! Arbitrary snapshot files: parsing and replaying every frame must fail
+//! cleanly on anything malformed.
+
+#![no_main]
+
+use instrument_panel_lib::hwinfo::snapshot;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: &[u8]| {
+    let Ok(json) = std::str::from_utf8(bytes) else { return };
+    let Ok(snapshot) = snapshot::parse(json) else { return };
+    for index in 0..snapshot.frames.len() {
+        let _ = snapshot.replay(index);
+    }
+    let _ = snapshot.frame_at(u64::MAX, true);
+});
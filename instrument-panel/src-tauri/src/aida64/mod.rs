@@ -0,0 +1,197 @@
+//! AIDA64's `AIDA64_SensorValues` shared memory (`Preferences > Hardware
+//! Monitoring > External Applications`).
+//!
+//! The region holds a NUL-terminated XML fragment with no root element, one
+//! element per reading named after its category:
+//! `<sys><id>SCPUCLK</id><label>CPU Clock</label><value>4450</value></sys><temp>...</temp>`.
+//! Values are plain numbers in the unit implied by the category (°C, RPM, %, V,
+//! A, W; `sys` readings are MHz, MB or % depending on the id).
+
+#[cfg(windows)]
+pub mod shared_memory;
+
+#[cfg(not(windows))]
+#[path = "shared_memory_unsupported.rs"]
+pub mod shared_memory;
+
+use crate::hwinfo::types::*;
+
+/// One reading from the fragment
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reading {
+    /// Element name: "sys", "temp", "fan", "duty", "volt", "curr" or "pwr"
+    pub category: String,
+    /// e.g. "TCPU", "SCC-1-3"
+    pub id: String,
+    pub label: String,
+    /// The value as published, e.g. "4450" or, for SUPTIME, "1d 02:03:04"
+    pub value: String,
+}
+
+impl Reading {
+    pub fn number(&self) -> Option<f64> {
+        self.value.trim().parse().ok().filter(|v: &f64| v.is_finite())
+    }
+}
+
+/// Parse the whole fragment. Unknown categories are kept; malformed trailing
+/// text (the region is rewritten in place while AIDA64 runs) is ignored.
+pub fn parse(text: &str) -> Vec<Reading> {
+    let mut readings = Vec::new();
+    let mut rest = text;
+
+    while let Some((category, body, after)) = next_element(rest) {
+        rest = after;
+        let Some(id) = child_text(body, "id") else { continue };
+        readings.push(Reading {
+            category: category.to_string(),
+            id,
+            label: child_text(body, "label").unwrap_or_default(),
+            value: child_text(body, "value").unwrap_or_default(),
+        });
+    }
+
+    readings
+}
+
+/// Next `<name>body</name>` in `text`, with the text after it
+fn next_element(text: &str) -> Option<(&str, &str, &str)> {
+    let start = text.find('<')?;
+    let after_open = &text[start + 1..];
+    let name_end = after_open.find('>')?;
+    let name = &after_open[..name_end];
+    let body_and_rest = &after_open[name_end + 1..];
+    let close = format!("</{}>", name);
+    let body_end = body_and_rest.find(&close)?;
+    Some((name, &body_and_rest[..body_end], &body_and_rest[body_end + close.len()..]))
+}
+
+fn child_text(body: &str, name: &str) -> Option<String> {
+    let open = format!("<{}>", name);
+    let close = format!("</{}>", name);
+    let start = body.find(&open)? + open.len();
+    let end = start + body[start..].find(&close)?;
+    Some(unescape(body[start..end].trim()))
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// "1d 02:03:04" or "02:03:04" as published in SUPTIME
+pub fn parse_uptime(text: &str) -> Option<u64> {
+    let text = text.trim();
+    let (days, clock) = match text.split_once(' ') {
+        Some((days, clock)) => (days.trim_end_matches('d').parse::<u64>().ok()?, clock),
+        None => (0, text),
+    };
+    let parts: Vec<u64> = clock.split(':').map(|p| p.parse().ok()).collect::<Option<_>>()?;
+    let [h, m, s] = parts[..] else { return None };
+    Some(days * 86_400 + h * 3_600 + m * 60 + s)
+}
+
+/// Readings by id, for the fixed AIDA64 ids
+struct Lookup<'a>(&'a [Reading]);
+
+impl<'a> Lookup<'a> {
+    fn get(&self, id: &str) -> Option<&'a Reading> {
+        self.0.iter().find(|r| r.id == id)
+    }
+
+    fn number(&self, id: &str) -> Option<f64> {
+        self.get(id).and_then(Reading::number)
+    }
+
+    /// Values of ids like "SCC-1-1", "SCC-1-2" ... in the published order
+    fn numbered(&self, prefix: &str, suffix: &str) -> Vec<f64> {
+        self.0
+            .iter()
+            .filter(|r| {
+                r.id.strip_prefix(prefix)
+                    .and_then(|rest| rest.strip_suffix(suffix))
+                    .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit() || b == b'-'))
+            })
+            .filter_map(Reading::number)
+            .collect()
+    }
+}
+
+/// Map AIDA64 ids into `data`
+pub fn apply(readings: &[Reading], data: &mut SensorData) {
+    let ids = Lookup(readings);
+
+    let cpu = &mut data.cpu;
+    cpu.package_temp_c = ids.number("TCPUPKG").or_else(|| ids.number("TCPU"));
+    cpu.core_temps = ids.numbered("TCC-", "");
+    cpu.package_power_w = ids.number("PCPUPKG");
+    cpu.core_power_w = ids.number("PCPUIA");
+    cpu.uncore_power_w = ids.number("PCPUGT");
+    cpu.core_clock_mhz = ids.number("SCPUCLK");
+    cpu.core_clocks_mhz = ids.numbered("SCC-", "");
+    cpu.usage_percent = ids.number("SCPUUTI");
+    cpu.core_usage_percent = ids.numbered("SCPU", "UTI");
+
+    // First GPU; AIDA64 numbers them TGPU1, TGPU2 ... TGPU10, so the digits
+    // after the prefix must end there
+    let gpu = &mut data.gpu;
+    let gpu_temps: Vec<&Reading> = readings
+        .iter()
+        .filter(|r| r.category == "temp")
+        .filter(|r| {
+            r.id.strip_prefix("TGPU1")
+                .is_some_and(|rest| !rest.starts_with(|c: char| c.is_ascii_digit()))
+        })
+        .collect();
+    let gpu_temp = |words: &[&str]| {
+        gpu_temps
+            .iter()
+            .find(|r| words.iter().any(|w| r.label.to_lowercase().contains(w)))
+            .and_then(|r| r.number())
+    };
+    // TGPU1 itself is the core (diode) sensor
+    gpu.core_temp_c = ids.number("TGPU1");
+    gpu.hotspot_temp_c = gpu_temp(&["hotspot", "hot spot"]);
+    gpu.memory_junction_temp_c = gpu_temp(&["memory"]);
+    gpu.power_w = ids.number("PGPU1");
+    gpu.core_clock_mhz = ids.number("SGPU1CLK");
+    gpu.memory_clock_mhz = ids.number("SGPU1MEMCLK");
+    gpu.usage_percent = ids.number("SGPU1UTI");
+    // SUSEDVMEM and SFREEVMEM are the system's virtual memory, not the GPU's;
+    // AIDA64 publishes no dedicated memory total
+    gpu.vram_used_mb = ids.number("SGPU1USEDDEMEM");
+    gpu.fan_speed_rpm = ids.number("FGPU1");
+    gpu.fan_speed_percent = ids.number("DGPU1");
+
+    // THDD1, THDD2 ... labelled with the drive model
+    for reading in readings.iter().filter(|r| r.id.starts_with("THDD")) {
+        data.drives.push(DriveData {
+            name: Some(reading.label.clone()).filter(|l| !l.is_empty()),
+            temp_c: reading.number(),
+            ..DriveData::default()
+        });
+    }
+    if let Some(first) = data.drives.first() {
+        data.storage = StorageData {
+            name: first.name.clone(),
+            nvme_temp_c: first.temp_c,
+            smart_health: "unknown".to_string(),
+        };
+    }
+
+    let used = ids.number("SUSEDMEM");
+    data.system.memory_used_mb = used;
+    data.system.memory_total_mb = used.zip(ids.number("SFREEMEM")).map(|(u, f)| u + f);
+    data.system.uptime_seconds = ids.get("SUPTIME").and_then(|r| parse_uptime(&r.value));
+
+    data.system.fans = readings
+        .iter()
+        .filter(|r| r.category == "fan" && !r.id.starts_with("FGPU"))
+        .filter_map(|r| Some(FanReading { name: r.label.clone(), rpm: r.number()? }))
+        .filter(|f| f.rpm > 0.0)
+        .collect();
+    data.system.fan_status = SystemData::fan_status_for(&data.system.fans);
+}
@@ -0,0 +1,44 @@
+use std::ffi::CString;
+use windows::Win32::Foundation::{CloseHandle, HANDLE};
+use windows::Win32::System::Memory::{
+    MapViewOfFile, OpenFileMappingA, UnmapViewOfFile, VirtualQuery, FILE_MAP_READ,
+    MEMORY_BASIC_INFORMATION,
+};
+
+const AIDA64_SHM_NAME: &str = "AIDA64_SensorValues";
+
+/// Copy the sensor values text out of AIDA64's shared memory
+pub fn read() -> Result<String, String> {
+    unsafe { read_shared_memory() }
+}
+
+unsafe fn read_shared_memory() -> Result<String, String> {
+    let shm_name = CString::new(AIDA64_SHM_NAME).unwrap();
+
+    let handle: HANDLE = OpenFileMappingA(
+        FILE_MAP_READ.0,
+        false,
+        windows::core::PCSTR(shm_name.as_ptr() as *const u8),
+    ).map_err(|e| format!("Failed to open AIDA64 shared memory: {}. Is External Applications > Shared Memory enabled?", e))?;
+
+    let map_view = MapViewOfFile(handle, FILE_MAP_READ, 0, 0, 0);
+    if map_view.Value.is_null() {
+        CloseHandle(handle).ok();
+        return Err("Failed to map AIDA64 shared memory view".to_string());
+    }
+
+    // The text is NUL-terminated; bound the search by the mapped region size
+    let mut info = MEMORY_BASIC_INFORMATION::default();
+    let queried = VirtualQuery(
+        Some(map_view.Value as *const _),
+        &mut info,
+        std::mem::size_of::<MEMORY_BASIC_INFORMATION>(),
+    );
+    let bytes = std::slice::from_raw_parts(map_view.Value as *const u8, if queried == 0 { 0 } else { info.RegionSize });
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    let text = String::from_utf8_lossy(&bytes[..end]).into_owned();
+
+    UnmapViewOfFile(map_view).ok();
+    CloseHandle(handle).ok();
+    Ok(text)
+}
@@ -0,0 +1,6 @@
+//! Stand-in for `shared_memory` on platforms where AIDA64 cannot run.
+
+/// Copy the sensor values text out of AIDA64's shared memory
+pub fn read() -> Result<String, String> {
+    Err("AIDA64 shared memory is only available on Windows".to_string())
+}
@@ -0,0 +1,391 @@
+//! External collector processes for sensors no built-in source knows about.
+//!
+//! A collector is any executable that speaks line-delimited JSON on
+//! stdin/stdout. Its first line announces it:
+//!
+//! `{"type":"hello","protocol":1,"name":"rack-pdu","capabilities":["power","current"]}`
+//!
+//! after which it prints readings whenever it has them, and at least once in
+//! answer to each `{"type":"poll"}` line the app writes to its stdin:
+//!
+//! `{"type":"readings","readings":[{"id":"outlet1","label":"Outlet 1","type":"power","unit":"W","value":212.5}]}`
+//!
+//! Polls are written on a thread of their own and dropped while one is still
+//! waiting, so a collector that stops reading stdin holds up nobody else.
+//!
+//! It may also report `{"type":"error","message":"..."}`. The app closes stdin
+//! when it no longer needs the collector, and then kills it along with any
+//! processes it started. Collectors that exit or break the protocol are
+//! restarted with a growing delay; the last line they wrote to stderr is
+//! reported with the reason.
+
+use crate::hwinfo::types::RawReading;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Protocol version this app speaks
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Restart delays double from `MIN_BACKOFF` up to `MAX_BACKOFF`, and start
+/// over once a collector has stayed up for `MAX_BACKOFF`
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+/// How long to wait for the rest of stderr once a collector has exited
+const STDERR_GRACE: Duration = Duration::from_millis(200);
+
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// A collector as configured in settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectorConfig {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// One reading reported by a collector
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectorReading {
+    pub id: String,
+    pub label: String,
+    /// e.g. "temperature", "power", "current"
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub unit: String,
+    pub value: f64,
+}
+
+/// Lines a collector writes
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    Hello {
+        protocol: u32,
+        name: String,
+        #[serde(default)]
+        capabilities: Vec<String>,
+    },
+    Readings {
+        readings: Vec<CollectorReading>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+pub fn parse_message(line: &str) -> Result<Message, String> {
+    serde_json::from_str(line).map_err(|e| format!("Invalid collector message: {}", e))
+}
+
+/// Supervision state of one collector, sent to the frontend
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectorStatus {
+    pub command: String,
+    /// From the hello message
+    pub name: Option<String>,
+    pub capabilities: Vec<String>,
+    /// True between a valid hello and the process exiting
+    pub running: bool,
+    pub restarts: u32,
+    pub last_error: Option<String>,
+    pub readings: Vec<CollectorReading>,
+}
+
+struct Shared {
+    status: Mutex<CollectorStatus>,
+    child: Mutex<Option<Child>>,
+    /// Hands polls to the thread writing the collector's stdin; dropping it
+    /// closes stdin
+    polls: Mutex<Option<mpsc::SyncSender<()>>>,
+    stop: AtomicBool,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// A running collector process and the thread supervising it. Dropping it
+/// stops the process without waiting for the thread, which finishes once
+/// the process's output is closed.
+pub struct Collector {
+    config: CollectorConfig,
+    shared: Arc<Shared>,
+}
+
+impl Collector {
+    pub fn spawn(config: CollectorConfig) -> Self {
+        let shared = Arc::new(Shared {
+            status: Mutex::new(CollectorStatus {
+                command: config.command.clone(),
+                ..CollectorStatus::default()
+            }),
+            child: Mutex::new(None),
+            polls: Mutex::new(None),
+            stop: AtomicBool::new(false),
+        });
+        {
+            let (config, shared) = (config.clone(), shared.clone());
+            thread::spawn(move || supervise(&config, &shared));
+        }
+        Self { config, shared }
+    }
+
+    pub fn config(&self) -> &CollectorConfig {
+        &self.config
+    }
+
+    /// Ask for fresh readings without waiting on the collector. The answer
+    /// arrives asynchronously; `status` returns the latest readings received.
+    pub fn request(&self) {
+        if let Some(polls) = lock(&self.shared.polls).as_ref() {
+            // Full while the last poll is unwritten: that one still asks for fresh readings
+            let _ = polls.try_send(());
+        }
+    }
+
+    pub fn status(&self) -> CollectorStatus {
+        lock(&self.shared.status).clone()
+    }
+
+    /// Latest readings, grouped under the collector's name
+    pub fn raw_readings(&self) -> Vec<RawReading> {
+        let status = lock(&self.shared.status);
+        let group = status.name.clone().unwrap_or_else(|| status.command.clone());
+        status
+            .readings
+            .iter()
+            .map(|r| RawReading {
+                group: group.clone(),
+                label: r.label.clone(),
+                value: r.value,
+                unit: r.unit.clone(),
+            })
+            .collect()
+    }
+}
+
+impl Drop for Collector {
+    fn drop(&mut self) {
+        self.shared.stop.store(true, Ordering::SeqCst);
+        lock(&self.shared.polls).take();
+        if let Some(child) = lock(&self.shared.child).as_mut() {
+            kill_tree(child);
+        }
+    }
+}
+
+/// Run the collector until stopped, restarting it whenever it exits
+fn supervise(config: &CollectorConfig, shared: &Shared) {
+    let mut backoff = MIN_BACKOFF;
+    while !shared.stop.load(Ordering::SeqCst) {
+        let started = Instant::now();
+        let error = run_once(config, shared);
+
+        let mut status = lock(&shared.status);
+        status.running = false;
+        // Stale values would look live on the dashboard
+        status.readings.clear();
+        if shared.stop.load(Ordering::SeqCst) {
+            break;
+        }
+        status.last_error = Some(error);
+        status.restarts += 1;
+        drop(status);
+
+        if started.elapsed() >= MAX_BACKOFF {
+            backoff = MIN_BACKOFF;
+        }
+        sleep_unless_stopped(backoff, shared);
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+fn sleep_unless_stopped(duration: Duration, shared: &Shared) {
+    let until = Instant::now() + duration;
+    while Instant::now() < until && !shared.stop.load(Ordering::SeqCst) {
+        thread::sleep(Duration::from_millis(50));
+    }
+}
+
+/// Start the process and read its output until it exits or breaks the
+/// protocol. Returns why it stopped.
+fn run_once(config: &CollectorConfig, shared: &Shared) -> String {
+    let mut cmd = Command::new(&config.command);
+    cmd.args(&config.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    // Its own process group, so `kill_tree` reaches whatever it starts
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => return format!("Failed to start {}: {}", config.command, e),
+    };
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let stderr = last_line(child.stderr.take().expect("stderr is piped"));
+    *lock(&shared.polls) = Some(write_polls(child.stdin.take().expect("stdin is piped")));
+    *lock(&shared.child) = Some(child);
+    // Stopped while starting; `Collector::drop` found no process to kill
+    if shared.stop.load(Ordering::SeqCst) {
+        lock(&shared.polls).take();
+        if let Some(child) = lock(&shared.child).as_mut() {
+            kill_tree(child);
+        }
+    }
+
+    let error = read_messages(BufReader::new(stdout), shared);
+
+    lock(&shared.polls).take();
+    let child = lock(&shared.child).take();
+    if let Some(mut child) = child {
+        kill_tree(&mut child);
+        let _ = child.wait();
+    }
+    match stderr.recv_timeout(STDERR_GRACE).ok().flatten() {
+        Some(line) => format!("{}: {}", error, line),
+        None => error,
+    }
+}
+
+/// Write a poll line to `stdin` for each request sent on the returned
+/// channel, on a thread of its own; a write blocked on a full pipe ends when
+/// the process is killed. Dropping the sender closes stdin.
+fn write_polls(mut stdin: ChildStdin) -> mpsc::SyncSender<()> {
+    let (tx, rx) = mpsc::sync_channel(1);
+    thread::spawn(move || {
+        for () in rx {
+            // A collector that has gone away is noticed by the supervisor
+            if stdin.write_all(b"{\"type\":\"poll\"}\n").and_then(|_| stdin.flush()).is_err() {
+                break;
+            }
+        }
+    });
+    tx
+}
+
+/// Read `stderr` on its own thread; the last non-empty line arrives on the
+/// returned channel when it closes
+fn last_line(stderr: impl std::io::Read + Send + 'static) -> mpsc::Receiver<Option<String>> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let last = BufReader::new(stderr)
+            .lines()
+            .map_while(Result::ok)
+            .filter(|line| !line.trim().is_empty())
+            .last();
+        let _ = tx.send(last.map(|line| line.trim().to_string()));
+    });
+    rx
+}
+
+/// Kill the collector and whatever it started, which would otherwise keep
+/// its output open (a wrapper script's interpreter, say)
+fn kill_tree(child: &mut Child) {
+    // Not yet waited for, so its pid and group id can't have been reused
+    #[cfg(unix)]
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        let _ = Command::new("taskkill")
+            .args(["/T", "/F", "/PID", &child.id().to_string()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .creation_flags(CREATE_NO_WINDOW)
+            .status();
+    }
+    let _ = child.kill();
+}
+
+fn read_messages(stdout: impl BufRead, shared: &Shared) -> String {
+    let mut greeted = false;
+    for line in stdout.lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => return format!("Failed to read collector output: {}", e),
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let message = match parse_message(&line) {
+            Ok(message) => message,
+            Err(e) => return e,
+        };
+
+        let mut status = lock(&shared.status);
+        match message {
+            Message::Hello { protocol, name, capabilities } => {
+                if protocol != PROTOCOL_VERSION {
+                    return format!("Unsupported collector protocol {} (expected {})", protocol, PROTOCOL_VERSION);
+                }
+                status.name = Some(name);
+                status.capabilities = capabilities;
+                status.running = true;
+                status.last_error = None;
+                greeted = true;
+            }
+            _ if !greeted => return "Collector did not start with a hello message".to_string(),
+            Message::Readings { readings } => status.readings = readings,
+            Message::Error { message } => status.last_error = Some(message),
+        }
+    }
+    "Collector exited".to_string()
+}
+
+/// The configured collectors, restarted when the configuration changes
+#[derive(Default)]
+pub struct CollectorSet {
+    collectors: Vec<Collector>,
+}
+
+impl CollectorSet {
+    /// Match the running collectors to `configs`, keeping those that are
+    /// unchanged
+    pub fn sync(&mut self, configs: &[CollectorConfig]) {
+        if self.collectors.iter().map(Collector::config).eq(configs.iter()) {
+            return;
+        }
+        let mut running = std::mem::take(&mut self.collectors);
+        for config in configs {
+            match running.iter().position(|c| c.config() == config) {
+                Some(i) => self.collectors.push(running.remove(i)),
+                None => self.collectors.push(Collector::spawn(config.clone())),
+            }
+        }
+    }
+
+    /// Ask every collector for fresh readings
+    pub fn request(&self) {
+        self.collectors.iter().for_each(Collector::request);
+    }
+
+    pub fn raw_readings(&self) -> Vec<RawReading> {
+        self.collectors.iter().flat_map(Collector::raw_readings).collect()
+    }
+
+    pub fn statuses(&self) -> Vec<CollectorStatus> {
+        self.collectors.iter().map(Collector::status).collect()
+    }
+}
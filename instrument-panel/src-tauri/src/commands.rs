@@ -1,7 +1,15 @@
+use crate::collector::{CollectorConfig, CollectorStatus};
+use crate::delta::Update;
+use crate::hwinfo::snapshot::{HostInfo, Snapshot};
 use crate::hwinfo::{self, types::SensorData, shared_memory};
+use crate::poller::{Poller, PollingPolicy};
+use crate::sources::{self, ActiveSource, SourceInfo};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
 use tauri::Manager;
 
 /// Settings stored locally
@@ -11,6 +19,34 @@ pub struct AppSettings {
     pub auto_launch_hwinfo: bool,
     pub hwinfo_path: Option<String>,
     pub mock_mode: bool,
+    pub data_source: Option<String>,        // registry id, e.g. "hwinfo" or "mock"
+    pub sysfs_root: Option<String>,         // Linux source, defaults to /sys
+    pub procfs_root: Option<String>,        // Linux source, defaults to /proc
+    pub lhm_url: Option<String>,            // LibreHardwareMonitor data.json, defaults to localhost:8085
+    pub lm_sensors_command: Option<String>, // lm-sensors source, defaults to `sensors -j`
+    pub lm_sensors_file: Option<String>,    // lm-sensors source reads this file instead when set
+    pub nvidia_smi_path: Option<String>,    // nvidia-smi source, defaults to `nvidia-smi` on PATH
+    pub smartctl_command: Option<String>,   // smartctl source, defaults to `smartctl`; e.g. `sudo -n smartctl`
+    pub node_exporter_url: Option<String>,  // node_exporter /metrics, defaults to localhost:9100
+    pub composite_sources: Option<Vec<String>>, // composite source members, highest priority first
+    pub composite_priorities: Option<BTreeMap<String, Vec<String>>>, // per field or section, e.g. "gpu.hotspotTempC" or "drives"
+    pub collectors: Option<Vec<CollectorConfig>>, // external collector executables, see collector.rs
+    pub mock_scenario: Option<String>,      // bundled scenario id or path to a scenario file; plain mock rig when unset
+    pub mock_shared_memory: Option<bool>,  // mock source decodes its readings from a HWiNFO shared-memory image
+    pub replay_file: Option<String>,        // replay source, a file written by capture_snapshot
+    pub replay_speed: Option<f64>,          // replay source playback rate, defaults to 1.0
+    pub replay_loop: Option<bool>,          // replay source starts over at the end, defaults to true
+    pub polling: Option<PollingPolicy>,     // poll intervals by window focus, power and subscribers, see poller.rs
+}
+
+impl AppSettings {
+    /// Id of the source to poll. `mock_mode` predates `data_source` and still wins.
+    pub fn source_id(&self) -> &str {
+        if self.mock_mode {
+            return sources::mock::DESCRIPTOR.id;
+        }
+        self.data_source.as_deref().unwrap_or(sources::DEFAULT_SOURCE)
+    }
 }
 
 fn settings_path(app: &tauri::AppHandle) -> PathBuf {
@@ -19,16 +55,82 @@ fn settings_path(app: &tauri::AppHandle) -> PathBuf {
     app_dir.join("settings.json")
 }
 
-/// Get current sensor data from HWiNFO shared memory
+fn snapshots_dir(app: &tauri::AppHandle) -> PathBuf {
+    let app_dir = app.path().app_data_dir().unwrap_or_else(|_| PathBuf::from("."));
+    app_dir.join("snapshots")
+}
+
+/// Most polls one snapshot may hold
+const MAX_SNAPSHOT_POLLS: u32 = 600;
+/// Longest wait between snapshot polls
+const MAX_SNAPSHOT_INTERVAL_MS: u64 = 60_000;
+/// Poller subscription held while a snapshot is captured
+const SNAPSHOT_SUBSCRIBER: &str = "capture-snapshot";
+
+/// Latest reading of the configured data source, or `None` before the
+/// first poll. New readings also arrive as `sensor-update` events.
 #[tauri::command]
-pub fn get_sensor_data(app: tauri::AppHandle) -> SensorData {
-    // Check if mock mode is enabled
-    let settings = get_settings(app);
-    if settings.mock_mode {
-        return hwinfo::mock::read_mock_data();
-    }
-    
-    hwinfo::read_sensor_data()
+pub fn get_sensor_data(poller: tauri::State<'_, Poller>) -> Option<SensorData> {
+    poller.latest()
+}
+
+/// The latest reading as a `snapshot` update, for starting the
+/// `sensor-update` stream or picking it up again after a gap
+#[tauri::command]
+pub fn resync_sensor_data(poller: tauri::State<'_, Poller>) -> Option<Update> {
+    poller.resync()
+}
+
+/// Poll now rather than at the next interval, e.g. after launching HWiNFO
+#[tauri::command]
+pub fn refresh_sensor_data(poller: tauri::State<'_, Poller>) {
+    poller.refresh();
+}
+
+/// Keep polling while subscriber `id` shows readings, at `interval_ms` or
+/// faster when given. Polling pauses while nothing is subscribed.
+#[tauri::command]
+pub fn subscribe_sensor_data(poller: tauri::State<'_, Poller>, id: String, interval_ms: Option<u64>) {
+    poller.subscribe(&id, interval_ms.map(Duration::from_millis));
+}
+
+#[tauri::command]
+pub fn unsubscribe_sensor_data(poller: tauri::State<'_, Poller>, id: String) {
+    poller.unsubscribe(&id);
+}
+
+/// List the data sources that can be selected in settings
+#[tauri::command]
+pub fn list_data_sources(poller: tauri::State<'_, Poller>, source: tauri::State<'_, ActiveSource>) -> Vec<SourceInfo> {
+    source.registry().list(&poller.settings())
+}
+
+/// A bundled mock scenario, for the scenario picker
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScenarioInfo {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+}
+
+/// List the scenarios bundled for the mock source
+#[tauri::command]
+pub fn list_mock_scenarios() -> Vec<ScenarioInfo> {
+    hwinfo::scenario::bundled()
+        .into_iter()
+        .map(|(id, scenario)| ScenarioInfo {
+            id: id.to_string(),
+            name: scenario.name,
+            description: scenario.description,
+        })
+        .collect()
+}
+
+/// Supervision state of the configured external collectors
+#[tauri::command]
+pub fn list_collectors(source: tauri::State<'_, ActiveSource>) -> Vec<CollectorStatus> {
+    source.collector_statuses()
 }
 
 /// Check if HWiNFO process is running
@@ -57,13 +159,56 @@ pub fn get_settings(app: tauri::AppHandle) -> AppSettings {
     }
 }
 
-/// Save settings
+/// Save settings and poll with them from now on
 #[tauri::command]
-pub fn save_settings(app: tauri::AppHandle, settings: AppSettings) -> Result<(), String> {
+pub fn save_settings(app: tauri::AppHandle, poller: tauri::State<'_, Poller>, settings: AppSettings) -> Result<(), String> {
     let path = settings_path(&app);
     let json = serde_json::to_string_pretty(&settings)
         .map_err(|e| e.to_string())?;
-    fs::write(path, json).map_err(|e| e.to_string())
+    fs::write(path, json).map_err(|e| e.to_string())?;
+    poller.set_settings(settings);
+    Ok(())
+}
+
+/// Debug: record `polls` consecutive polls (default 1), `interval_ms` apart
+/// (default 1000), of the raw HWiNFO region and the sensor data made from it.
+/// Returns the path of the snapshot file.
+#[tauri::command(async)]
+pub fn capture_snapshot(
+    app: tauri::AppHandle,
+    poller: tauri::State<'_, Poller>,
+    polls: Option<u32>,
+    interval_ms: Option<u64>,
+) -> Result<String, String> {
+    let settings = poller.settings();
+    let polls = polls.unwrap_or(1).clamp(1, MAX_SNAPSHOT_POLLS);
+    let interval = Duration::from_millis(interval_ms.unwrap_or(1000).min(MAX_SNAPSHOT_INTERVAL_MS));
+
+    // Frames without a region record the poller's readings; keep it polling
+    // at least as often as the capture
+    poller.subscribe(SNAPSHOT_SUBSCRIBER, Some(interval));
+    let mut snapshot = Snapshot::new(settings.source_id(), HostInfo::current());
+    let started = Instant::now();
+    for i in 0..polls {
+        // Keep to the schedule however long each poll takes
+        thread::sleep((started + interval * i).saturating_duration_since(Instant::now()));
+        let elapsed = started.elapsed();
+        // Decode this region rather than reading shared memory again, which
+        // HWiNFO may have updated in between
+        let region = shared_memory::read_region();
+        let data = match &region {
+            Ok(region) => shared_memory::from_region(region).ok(),
+            Err(_) => poller.latest(),
+        };
+        snapshot.push(elapsed, region, data);
+    }
+    poller.unsubscribe(SNAPSHOT_SUBSCRIBER);
+
+    let dir = snapshots_dir(&app);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let path = dir.join(snapshot.file_name());
+    snapshot.save(&path)?;
+    Ok(path.display().to_string())
 }
 
 /// Debug: dump all sensor info from HWiNFO shared memory
@@ -0,0 +1,170 @@
+//! Delta encoding of the `sensor-update` stream.
+//!
+//! Most of a reading (names, drive letters, capabilities) does not change
+//! between polls, so after one full snapshot each update carries only the
+//! values that changed since the previous one:
+//!
+//! `{"type":"snapshot","seq":1,"data":{...}}`
+//!
+//! `{"type":"delta","seq":2,"changes":[{"path":["cpu","packageTempC"],"value":61.2}]}`
+//!
+//! Paths are lists of object keys and array indexes, since some keys contain
+//! dots (provenance is keyed by field path). An array that changes length,
+//! or an object or array whose changes would take more bytes than its new
+//! value, is sent whole; a removed key is sent with `removed: true`. Sequence numbers
+//! rise by one per update; a client that sees a gap asks for the latest
+//! snapshot with `resync_sensor_data` and applies deltas after its `seq`.
+
+use crate::hwinfo::types::SensorData;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// One message on the update stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Update {
+    /// The whole reading, as `SensorData` JSON
+    Snapshot { seq: u64, data: Value },
+    /// What changed since update `seq - 1`
+    Delta { seq: u64, changes: Vec<Change> },
+}
+
+impl Update {
+    pub fn seq(&self) -> u64 {
+        match self {
+            Update::Snapshot { seq, .. } | Update::Delta { seq, .. } => *seq,
+        }
+    }
+}
+
+/// A step in a path: an object key or an array index
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Key {
+    Index(usize),
+    Field(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Change {
+    pub path: Vec<Key>,
+    #[serde(default)]
+    pub value: Value,
+    /// The key at `path` is gone
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub removed: bool,
+}
+
+/// Changes that turn `old` into `new`
+pub fn diff(old: &Value, new: &Value) -> Vec<Change> {
+    let mut changes = Vec::new();
+    diff_into(&mut Vec::new(), old, new, &mut changes);
+    changes
+}
+
+fn diff_into(path: &mut Vec<Key>, old: &Value, new: &Value, changes: &mut Vec<Change>) {
+    match (old, new) {
+        (Value::Object(_), Value::Object(_)) | (Value::Array(_), Value::Array(_)) if !path.is_empty() => {
+            let mut nested = Vec::new();
+            diff_children(path, old, new, &mut nested);
+            // Paths add up when most of a list or object changed; send it whole then
+            if !nested.is_empty() && encoded_len(&nested) > encoded_len(new) {
+                changes.push(Change { path: path.clone(), value: new.clone(), removed: false });
+            } else {
+                changes.extend(nested);
+            }
+        }
+        _ => diff_children(path, old, new, changes),
+    }
+}
+
+fn encoded_len(value: &impl Serialize) -> usize {
+    serde_json::to_vec(value).map_or(0, |json| json.len())
+}
+
+fn diff_children(path: &mut Vec<Key>, old: &Value, new: &Value, changes: &mut Vec<Change>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, value) in new {
+                path.push(Key::Field(key.clone()));
+                match old.get(key) {
+                    Some(previous) => diff_into(path, previous, value, changes),
+                    None => changes.push(Change { path: path.clone(), value: value.clone(), removed: false }),
+                }
+                path.pop();
+            }
+            for key in old.keys().filter(|key| !new.contains_key(*key)) {
+                let mut path = path.clone();
+                path.push(Key::Field(key.clone()));
+                changes.push(Change { path, value: Value::Null, removed: true });
+            }
+        }
+        (Value::Array(old), Value::Array(new)) if old.len() == new.len() => {
+            for (i, (previous, value)) in old.iter().zip(new).enumerate() {
+                path.push(Key::Index(i));
+                diff_into(path, previous, value, changes);
+                path.pop();
+            }
+        }
+        (old, new) if old != new => changes.push(Change { path: path.clone(), value: new.clone(), removed: false }),
+        _ => {}
+    }
+}
+
+/// Apply `changes` to `value`, as the frontend does. Changes whose path does
+/// not exist are skipped.
+pub fn apply(value: &mut Value, changes: &[Change]) {
+    for change in changes {
+        let Some((last, parents)) = change.path.split_last() else {
+            *value = change.value.clone();
+            continue;
+        };
+        let Some(target) = parents.iter().try_fold(&mut *value, child) else { continue };
+        match (target, last) {
+            (Value::Object(map), Key::Field(field)) if change.removed => {
+                map.remove(field);
+            }
+            (Value::Object(map), Key::Field(field)) => {
+                map.insert(field.clone(), change.value.clone());
+            }
+            (Value::Array(items), Key::Index(i)) if *i < items.len() => items[*i] = change.value.clone(),
+            _ => {}
+        }
+    }
+}
+
+fn child<'a>(value: &'a mut Value, key: &Key) -> Option<&'a mut Value> {
+    match (value, key) {
+        (Value::Object(map), Key::Field(field)) => map.get_mut(field),
+        (Value::Array(items), Key::Index(i)) => items.get_mut(*i),
+        _ => None,
+    }
+}
+
+/// Numbers the updates and remembers the last reading sent
+#[derive(Debug, Default)]
+pub struct Encoder {
+    seq: u64,
+    last: Option<Value>,
+}
+
+impl Encoder {
+    /// The update that takes clients from the previous reading to `data`;
+    /// a snapshot the first time
+    pub fn push(&mut self, data: &SensorData) -> Update {
+        let value = serde_json::to_value(data).expect("SensorData serializes");
+        self.seq += 1;
+        let update = match &self.last {
+            None => Update::Snapshot { seq: self.seq, data: value.clone() },
+            Some(last) => Update::Delta { seq: self.seq, changes: diff(last, &value) },
+        };
+        self.last = Some(value);
+        update
+    }
+
+    /// The latest reading in full, numbered like the update that sent it
+    pub fn snapshot(&self) -> Option<Update> {
+        self.last.as_ref().map(|data| Update::Snapshot { seq: self.seq, data: data.clone() })
+    }
+}
@@ -0,0 +1,473 @@
+//! Decoder for the HWiNFO shared-memory region.
+//!
+//! Works on a copy of the region's bytes, so it runs on any platform: live on
+//! Windows, and against snapshots and test images everywhere else. The header
+//! comes from another process, so every offset, size and count is checked
+//! against the bytes actually present before anything is read.
+
+// The mapping heuristics check the reading type, then the label
+#![allow(clippy::collapsible_if)]
+
+use super::types::*;
+use crate::commands::{DebugDumpResult, HeaderDebugInfo, ReadingDebugInfo, SensorDebugInfo};
+use chrono::Utc;
+use std::mem::size_of;
+use std::ptr;
+
+/// Read a `T` at `offset`, or `None` past the end of `bytes`.
+/// Only for the packed HWiNFO structs, which are valid for any bit pattern.
+fn read_at<T: Copy>(bytes: &[u8], offset: u64) -> Option<T> {
+    let start = usize::try_from(offset).ok()?;
+    let slice = bytes.get(start..start.checked_add(size_of::<T>())?)?;
+    Some(unsafe { ptr::read_unaligned(slice.as_ptr() as *const T) })
+}
+
+/// A validated view of a region image
+pub struct Region<'a> {
+    bytes: &'a [u8],
+    header: HWiNFOHeader,
+}
+
+impl<'a> Region<'a> {
+    /// Check the signature and that both sections lie within `bytes`
+    pub fn new(bytes: &'a [u8]) -> Result<Self, String> {
+        let header: HWiNFOHeader =
+            read_at(bytes, 0).ok_or_else(|| format!("HWiNFO shared memory is only {} bytes", bytes.len()))?;
+        let signature = header.signature;
+        if signature != HWINFO_SIGNATURE {
+            return Err(format!("Invalid HWiNFO signature: {:#X}", signature));
+        }
+        let region = Self { bytes, header };
+        region.check_section("sensor", header.sensor_section_offset, header.sensor_section_size, header.sensor_count, size_of::<HWiNFOSensor>())?;
+        region.check_section("reading", header.reading_section_offset, header.reading_section_size, header.reading_count, size_of::<HWiNFOReading>())?;
+        Ok(region)
+    }
+
+    fn check_section(&self, name: &str, offset: u32, entry_size: u32, count: u32, needed: usize) -> Result<(), String> {
+        if count == 0 {
+            return Ok(());
+        }
+        if (entry_size as usize) < needed {
+            return Err(format!("HWiNFO {} entries are {} bytes, expected at least {}", name, entry_size, needed));
+        }
+        // u64 arithmetic cannot overflow for u32 inputs
+        let end = offset as u64 + entry_size as u64 * (count as u64 - 1) + needed as u64;
+        if end > self.bytes.len() as u64 {
+            return Err(format!(
+                "HWiNFO {} section ends at byte {} but the region is {} bytes",
+                name,
+                end,
+                self.bytes.len()
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn header(&self) -> HWiNFOHeader {
+        self.header
+    }
+
+    /// (index, sensor) for every sensor entry
+    pub fn sensors(&self) -> impl Iterator<Item = (u32, HWiNFOSensor)> + '_ {
+        let (offset, size) = (self.header.sensor_section_offset as u64, self.header.sensor_section_size as u64);
+        (0..self.header.sensor_count).map_while(move |i| Some((i, read_at(self.bytes, offset + size * i as u64)?)))
+    }
+
+    pub fn readings(&self) -> impl Iterator<Item = HWiNFOReading> + '_ {
+        let (offset, size) = (self.header.reading_section_offset as u64, self.header.reading_section_size as u64);
+        (0..self.header.reading_count).map_while(move |i| read_at(self.bytes, offset + size * i as u64))
+    }
+}
+
+/// Map a region image to `SensorData`. Fields the region does not carry
+/// (disk space, uptime, host name) are left empty for the caller to fill.
+pub fn decode(bytes: &[u8]) -> Result<SensorData, String> {
+    let region = Region::new(bytes)?;
+
+    // CPU data
+    let mut cpu_temp: Option<f64> = None;
+    let mut cpu_power: Option<f64> = None;
+    let mut cpu_name: Option<String> = None;
+    let mut cpu_clock: Option<f64> = None;
+    let mut cpu_usage: Option<f64> = None;
+    let mut core_temps: Vec<f64> = Vec::new();
+    let mut cpu_sensor_indices: Vec<u32> = Vec::new();
+
+    // GPU data
+    let mut gpu_core: Option<f64> = None;
+    let mut gpu_hotspot: Option<f64> = None;
+    let mut gpu_mem_junction: Option<f64> = None;
+    let mut gpu_power: Option<f64> = None;
+    let mut gpu_name: Option<String> = None;
+    let mut gpu_sensor_index: Option<u32> = None;
+    let mut gpu_core_clock: Option<f64> = None;
+    let mut gpu_mem_clock: Option<f64> = None;
+    let mut gpu_usage: Option<f64> = None;
+    let mut gpu_vram_used: Option<f64> = None;
+    let mut gpu_vram_total: Option<f64> = None;
+    let mut gpu_fan_rpm: Option<f64> = None;
+    let mut gpu_fan_percent: Option<f64> = None;
+
+    // Storage data - collect all drives
+    let mut drives: Vec<(u32, String, Option<String>)> = Vec::new(); // (sensor_index, name, drive_letter)
+    let mut drive_temps: std::collections::HashMap<u32, f64> = std::collections::HashMap::new();
+    let mut drive_health: std::collections::HashMap<u32, String> = std::collections::HashMap::new();
+
+    // Fan data
+    let mut fan_readings: Vec<FanReading> = Vec::new();
+
+    // Read sensor names from sensor section
+    for (i, sensor) in region.sensors() {
+        let sensor_name = String::from_utf8_lossy(&sensor.sensor_name_original)
+            .trim_end_matches('\0')
+            .to_string();
+        let sensor_name_lower = sensor_name.to_lowercase();
+
+        // CPU name - look for AMD Ryzen or Intel Core processors
+        // Collect ALL matching sensor indices since CPU readings may be spread across multiple sensors
+        if sensor_name_lower.contains("ryzen") || sensor_name_lower.contains("intel") || sensor_name_lower.contains("core i") {
+            if cpu_name.is_none() {
+                cpu_name = Some(sensor_name.clone());
+            }
+            cpu_sensor_indices.push(i);
+        }
+
+        // GPU name - prioritize discrete GPUs (NVIDIA) over integrated (AMD Radeon)
+        let is_discrete = sensor_name_lower.contains("geforce") || sensor_name_lower.contains("rtx") || sensor_name_lower.contains("gtx");
+        if is_discrete || (gpu_name.is_none() && sensor_name_lower.contains("radeon")) {
+            gpu_name = Some(sensor_name.clone());
+            gpu_sensor_index = Some(i);
+        }
+
+        // Storage - collect all S.M.A.R.T. sensors
+        if sensor_name_lower.starts_with("s.m.a.r.t.") {
+            // Extract drive letter if present
+            let drive_letter = sensor_name.split_once('[')
+                .and_then(|(_, rest)| rest.split_once(']'))
+                .map(|(letter, _)| letter.to_string());
+            drives.push((i, sensor_name.clone(), drive_letter));
+        }
+    }
+
+    // Parse readings
+    for reading in region.readings() {
+        let label = String::from_utf8_lossy(&reading.label_original)
+            .trim_end_matches('\0')
+            .to_lowercase();
+        let label_original = String::from_utf8_lossy(&reading.label_original)
+            .trim_end_matches('\0')
+            .to_string();
+
+        // Copy sensor_index to local var to avoid unaligned reference from packed struct
+        let reading_sensor_index = reading.sensor_index;
+        let is_cpu = cpu_sensor_indices.contains(&reading_sensor_index);
+        let is_gpu = gpu_sensor_index == Some(reading_sensor_index);
+
+        // CPU readings
+        if is_cpu {
+            // Package temperature - AMD uses "CPU (Tctl/Tdie)" or just "Tctl" or "Tdie"
+            if cpu_temp.is_none() && reading.reading_type == ReadingType::Temp as u32 {
+                if label == "cpu temp" || label.contains("tctl") || label.contains("tdie")
+                    || (label.contains("cpu") && label.contains("package")) {
+                    cpu_temp = Some(reading.value);
+                }
+            }
+            // Per-core temperatures - AMD uses "Core X (CCD Y)" or similar
+            if reading.reading_type == ReadingType::Temp as u32 {
+                if (label.starts_with("core") && (label.contains("temp") || label.contains("ccd")))
+                    || label.contains("ccd") {
+                    core_temps.push(reading.value);
+                }
+            }
+            // CPU power - AMD uses "CPU PPT" (Package Power Tracking)
+            if cpu_power.is_none() && reading.reading_type == ReadingType::Power as u32 {
+                if label == "cpu power" || label.contains("cpu package power")
+                    || label == "cpu ppt" || label.contains("ppt") {
+                    cpu_power = Some(reading.value);
+                }
+            }
+            // CPU clock (average or effective)
+            if cpu_clock.is_none() && reading.reading_type == ReadingType::Clock as u32 {
+                if label.contains("core") && (label.contains("clock") || label.contains("effective")) {
+                    cpu_clock = Some(reading.value);
+                }
+            }
+            // CPU usage
+            if cpu_usage.is_none() && reading.reading_type == ReadingType::Usage as u32 {
+                if label.contains("total") || label.contains("cpu") {
+                    cpu_usage = Some(reading.value);
+                }
+            }
+        }
+
+        // GPU readings
+        if is_gpu {
+            // GPU Temperature is the core sensor; the hot spot runs hotter
+            if gpu_core.is_none() && reading.reading_type == ReadingType::Temp as u32 {
+                if label == "gpu temp" || label == "gpu temperature" {
+                    gpu_core = Some(reading.value);
+                }
+            }
+            if gpu_hotspot.is_none() && reading.reading_type == ReadingType::Temp as u32 {
+                if label.contains("gpu hot spot") || label.contains("hotspot") {
+                    gpu_hotspot = Some(reading.value);
+                }
+            }
+            // Memory Junction Temperature
+            if gpu_mem_junction.is_none() && label.contains("memory junction") {
+                if reading.reading_type == ReadingType::Temp as u32 {
+                    gpu_mem_junction = Some(reading.value);
+                }
+            }
+            // GPU Power
+            if gpu_power.is_none() && reading.reading_type == ReadingType::Power as u32 {
+                if label == "gpu power" || (label.contains("gpu") && label.contains("power") 
+                    && !label.contains("limit") && !label.contains("percent")) {
+                    gpu_power = Some(reading.value);
+                }
+            }
+            // GPU Core Clock
+            if gpu_core_clock.is_none() && reading.reading_type == ReadingType::Clock as u32 {
+                if label == "gpu clock" || label.contains("core clock") {
+                    gpu_core_clock = Some(reading.value);
+                }
+            }
+            // GPU Memory Clock
+            if gpu_mem_clock.is_none() && reading.reading_type == ReadingType::Clock as u32 {
+                if label.contains("memory clock") || label.contains("mem clock") {
+                    gpu_mem_clock = Some(reading.value);
+                }
+            }
+            // GPU Usage
+            if gpu_usage.is_none() && reading.reading_type == ReadingType::Usage as u32 {
+                if label == "gpu utilization" || label.contains("gpu core load") || label == "gpu usage" {
+                    gpu_usage = Some(reading.value);
+                }
+            }
+            // VRAM Used
+            if gpu_vram_used.is_none() && reading.reading_type == ReadingType::Other as u32 {
+                if label.contains("gpu memory used") || label.contains("vram used") 
+                    || label.contains("d3d dedicated") {
+                    gpu_vram_used = Some(reading.value);
+                }
+            }
+            // VRAM Total (often reported as "GPU Memory Allocated" or similar)
+            if gpu_vram_total.is_none() && reading.reading_type == ReadingType::Other as u32 {
+                if label.contains("gpu memory total") || label.contains("vram total") {
+                    gpu_vram_total = Some(reading.value);
+                }
+            }
+            // GPU Fan RPM
+            if gpu_fan_rpm.is_none() && reading.reading_type == ReadingType::Fan as u32 {
+                if label.contains("gpu") || label.contains("fan") {
+                    gpu_fan_rpm = Some(reading.value);
+                }
+            }
+            // GPU Fan %
+            if gpu_fan_percent.is_none() && reading.reading_type == ReadingType::Usage as u32 {
+                if label.contains("fan") && (label.contains("speed") || label.contains("%")) {
+                    gpu_fan_percent = Some(reading.value);
+                }
+            }
+        }
+
+        // Storage readings - match by sensor index
+        for (drive_idx, _, _) in &drives {
+            if reading.sensor_index == *drive_idx {
+                // Drive temperature - include "Drive Airflow Temperature" for SATA SSDs
+                if reading.reading_type == ReadingType::Temp as u32 {
+                    if label == "drive temperature" || label.contains("drive temp")
+                        || label.contains("airflow") {
+                        // Only store if we don't have a temp yet, or prefer non-airflow over airflow
+                        if !drive_temps.contains_key(drive_idx) {
+                            drive_temps.insert(*drive_idx, reading.value);
+                        }
+                    }
+                }
+                // SMART Health - look for remaining life or health indicators
+                // 70%+ = good, 30-70% = warning, <30% = critical
+                if label.contains("remaining life") || label.contains("health")
+                    || label.contains("life remaining") {
+                    let health = if reading.value >= 70.0 {
+                        "good".to_string()
+                    } else if reading.value >= 30.0 {
+                        "warning".to_string()
+                    } else {
+                        "critical".to_string()
+                    };
+                    drive_health.insert(*drive_idx, health);
+                }
+            }
+        }
+
+        // Fan readings (non-GPU fans)
+        if reading.reading_type == ReadingType::Fan as u32 && reading.value > 0.0 {
+            if !is_gpu {
+                fan_readings.push(FanReading {
+                    name: label_original,
+                    rpm: reading.value,
+                });
+            }
+        }
+    }
+
+    // Build drive data
+    let mut drive_data: Vec<DriveData> = Vec::new();
+    let mut primary_storage = StorageData::default();
+
+    for (idx, name, letter) in &drives {
+        let temp = drive_temps.get(idx).copied();
+        let health = drive_health.get(idx).cloned().unwrap_or_else(|| "unknown".to_string());
+        
+        let drive = DriveData {
+            name: Some(name.clone()),
+            drive_letter: letter.clone(),
+            temp_c: temp,
+            smart_health: health.clone(),
+            ..DriveData::default()
+        };
+
+        // Set primary storage (prefer C: drive)
+        if letter.as_deref() == Some("C:") || primary_storage.name.is_none() {
+            primary_storage = StorageData {
+                name: Some(name.clone()),
+                nvme_temp_c: temp,
+                smart_health: health,
+            };
+        }
+
+        drive_data.push(drive);
+    }
+
+    // Sort drives by letter
+    drive_data.sort_by(|a, b| {
+        a.drive_letter.as_deref().unwrap_or("Z")
+            .cmp(b.drive_letter.as_deref().unwrap_or("Z"))
+    });
+
+
+    // Determine fan status
+    let fan_status = SystemData::fan_status_for(&fan_readings);
+
+    Ok(SensorData {
+        status: "connected".to_string(),
+        last_read_at: Some(Utc::now().to_rfc3339()),
+        diagnostics: Diagnostics {
+            hwinfo_process_detected: true,
+            shared_memory_detected: true,
+            message: None,
+            source: Some("hwinfo".to_string()),
+            provenance: Default::default(),
+        },
+        cpu: CpuData {
+            name: cpu_name,
+            package_temp_c: cpu_temp,
+            package_power_w: cpu_power,
+            core_power_w: None,
+            uncore_power_w: None,
+            core_clock_mhz: cpu_clock,
+            max_clock_mhz: None,
+            usage_percent: cpu_usage,
+            core_temps,
+            core_clocks_mhz: Vec::new(),
+            core_usage_percent: Vec::new(),
+        },
+        gpu: GpuData {
+            name: gpu_name,
+            core_temp_c: gpu_core,
+            hotspot_temp_c: gpu_hotspot,
+            memory_junction_temp_c: gpu_mem_junction,
+            power_w: gpu_power,
+            core_clock_mhz: gpu_core_clock,
+            memory_clock_mhz: gpu_mem_clock,
+            usage_percent: gpu_usage,
+            vram_used_mb: gpu_vram_used,
+            vram_total_mb: gpu_vram_total,
+            fan_speed_rpm: gpu_fan_rpm,
+            fan_speed_percent: gpu_fan_percent,
+        },
+        gpus: Vec::new(),
+        storage: primary_storage,
+        drives: drive_data,
+        system: SystemData {
+            name: None,
+            uptime_seconds: None,
+            fan_status,
+            fans: fan_readings,
+            memory_total_mb: None,
+            memory_used_mb: None,
+            load_average: None,
+        },
+        raw_readings: Vec::new(),
+    })
+}
+
+/// Copy what a region does not carry (host name, uptime, disk space) from
+/// `from`, a reading of the same machine
+pub fn carry_over(data: &mut SensorData, from: &SensorData) {
+    data.system.name = from.system.name.clone();
+    data.system.uptime_seconds = from.system.uptime_seconds;
+    for drive in &mut data.drives {
+        if let Some(original) = from.drives.iter().find(|d| d.name == drive.name) {
+            drive.total_gb = original.total_gb;
+            drive.free_gb = original.free_gb;
+        }
+    }
+}
+
+fn text(field: &[u8]) -> String {
+    String::from_utf8_lossy(field).trim_end_matches('\0').to_string()
+}
+
+/// Debug dump of the header and every sensor entry
+pub fn dump_sensors(bytes: &[u8]) -> Result<DebugDumpResult, String> {
+    let region = Region::new(bytes)?;
+    let header = region.header();
+    let signature = header.signature;
+
+    Ok(DebugDumpResult {
+        header: HeaderDebugInfo {
+            signature: format!("{:#X}", signature),
+            version: header.version,
+            revision: header.revision,
+            sensor_section_offset: header.sensor_section_offset,
+            sensor_section_size: header.sensor_section_size,
+            sensor_count: header.sensor_count,
+            reading_section_offset: header.reading_section_offset,
+            reading_section_size: header.reading_section_size,
+            reading_count: header.reading_count,
+        },
+        sensors: region
+            .sensors()
+            .map(|(index, sensor)| SensorDebugInfo {
+                index,
+                sensor_id: sensor.sensor_id,
+                sensor_instance: sensor.sensor_instance,
+                name_original: text(&sensor.sensor_name_original),
+                name_user: text(&sensor.sensor_name_user),
+            })
+            .collect(),
+    })
+}
+
+/// Debug dump of every reading whose original label contains `filter`
+/// (case-insensitive)
+pub fn dump_readings(bytes: &[u8], filter: Option<&str>) -> Result<Vec<ReadingDebugInfo>, String> {
+    let region = Region::new(bytes)?;
+    let filter = filter.map(str::to_lowercase);
+
+    Ok(region
+        .readings()
+        .enumerate()
+        .map(|(index, reading)| ReadingDebugInfo {
+            index: index as u32,
+            sensor_index: reading.sensor_index,
+            reading_type: reading.reading_type,
+            label_original: text(&reading.label_original),
+            label_user: text(&reading.label_user),
+            unit: text(&reading.unit),
+            value: reading.value,
+        })
+        .filter(|r| filter.as_ref().is_none_or(|f| r.label_original.to_lowercase().contains(f)))
+        .collect())
+}
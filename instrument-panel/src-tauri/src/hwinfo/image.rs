@@ -0,0 +1,252 @@
+//! Builder for synthetic HWiNFO shared-memory images.
+//!
+//! Produces the same bytes HWiNFO publishes, for tests, fuzzing seeds and the
+//! mock source, so they all go through the real decoder:
+//!
+//! ```
+//! use instrument_panel_lib::hwinfo::image::ImageBuilder;
+//! use instrument_panel_lib::hwinfo::types::ReadingType;
+//!
+//! let image = ImageBuilder::new()
+//!     .sensor("CPU [#0]: AMD Ryzen 7 7800X3D")
+//!     .reading(ReadingType::Temp, "CPU (Tctl/Tdie)", "°C", 61.5)
+//!     .stats(38.0, 88.1, 55.2)
+//!     .build();
+//! let data = instrument_panel_lib::hwinfo::decode::decode(&image).unwrap();
+//! assert_eq!(data.cpu.package_temp_c, Some(61.5));
+//! ```
+
+use super::decode;
+use super::types::*;
+use std::mem::size_of;
+
+const HEADER_SIZE: usize = size_of::<HWiNFOHeader>();
+const SENSOR_SIZE: usize = size_of::<HWiNFOSensor>();
+const READING_SIZE: usize = size_of::<HWiNFOReading>();
+
+struct Sensor {
+    id: u32,
+    instance: u32,
+    name: String,
+}
+
+struct Reading {
+    kind: ReadingType,
+    sensor_index: u32,
+    label: String,
+    unit: String,
+    value: f64,
+    min: f64,
+    max: f64,
+    avg: f64,
+}
+
+/// Builds a region image: header, then the sensor section, then the reading
+/// section, little-endian and packed like HWiNFO's own
+pub struct ImageBuilder {
+    version: u32,
+    revision: u32,
+    poll_time: i64,
+    /// Bytes after each entry, as newer HWiNFO versions append fields
+    sensor_padding: usize,
+    reading_padding: usize,
+    sensors: Vec<Sensor>,
+    readings: Vec<Reading>,
+}
+
+impl Default for ImageBuilder {
+    fn default() -> Self {
+        Self {
+            version: 2,
+            revision: 1,
+            poll_time: 0,
+            sensor_padding: 0,
+            reading_padding: 0,
+            sensors: Vec::new(),
+            readings: Vec::new(),
+        }
+    }
+}
+
+impl ImageBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn version(mut self, version: u32, revision: u32) -> Self {
+        self.version = version;
+        self.revision = revision;
+        self
+    }
+
+    /// Milliseconds since system start, as HWiNFO stamps each poll
+    pub fn poll_time(mut self, poll_time: i64) -> Self {
+        self.poll_time = poll_time;
+        self
+    }
+
+    /// Make every sensor and reading entry longer than the struct by this many bytes
+    pub fn padding(mut self, sensor: usize, reading: usize) -> Self {
+        self.sensor_padding = sensor;
+        self.reading_padding = reading;
+        self
+    }
+
+    /// Add a sensor; readings added after it belong to it
+    pub fn sensor(mut self, name: &str) -> Self {
+        self.sensors.push(Sensor {
+            id: 0xF000_0000 + self.sensors.len() as u32,
+            instance: 0,
+            name: name.to_string(),
+        });
+        self
+    }
+
+    /// Add a reading to the latest sensor, with min, max and average equal to `value`
+    pub fn reading(mut self, kind: ReadingType, label: &str, unit: &str, value: f64) -> Self {
+        self.readings.push(Reading {
+            kind,
+            // A reading before any sensor points at a sensor that does not exist, as a broken region might
+            sensor_index: self.sensors.len().saturating_sub(1) as u32,
+            label: label.to_string(),
+            unit: unit.to_string(),
+            value,
+            min: value,
+            max: value,
+            avg: value,
+        });
+        self
+    }
+
+    /// Set min, max and average of the latest reading
+    pub fn stats(mut self, min: f64, max: f64, avg: f64) -> Self {
+        if let Some(reading) = self.readings.last_mut() {
+            (reading.min, reading.max, reading.avg) = (min, max, avg);
+        }
+        self
+    }
+
+    pub fn build(&self) -> Vec<u8> {
+        let sensor_size = SENSOR_SIZE + self.sensor_padding;
+        let reading_size = READING_SIZE + self.reading_padding;
+        let sensor_offset = HEADER_SIZE;
+        let reading_offset = sensor_offset + sensor_size * self.sensors.len();
+
+        let mut bytes = Vec::with_capacity(reading_offset + reading_size * self.readings.len());
+        for field in [HWINFO_SIGNATURE, self.version, self.revision] {
+            bytes.extend(field.to_le_bytes());
+        }
+        bytes.extend(self.poll_time.to_le_bytes());
+        for field in [
+            sensor_offset,
+            sensor_size,
+            self.sensors.len(),
+            reading_offset,
+            reading_size,
+            self.readings.len(),
+        ] {
+            bytes.extend((field as u32).to_le_bytes());
+        }
+
+        for sensor in &self.sensors {
+            bytes.extend(sensor.id.to_le_bytes());
+            bytes.extend(sensor.instance.to_le_bytes());
+            bytes.extend(text(&sensor.name, 128));
+            bytes.extend(text(&sensor.name, 128));
+            bytes.resize(bytes.len() + self.sensor_padding, 0);
+        }
+        for (i, reading) in self.readings.iter().enumerate() {
+            bytes.extend((reading.kind as u32).to_le_bytes());
+            bytes.extend(reading.sensor_index.to_le_bytes());
+            bytes.extend((i as u32).to_le_bytes());
+            bytes.extend(text(&reading.label, 128));
+            bytes.extend(text(&reading.label, 128));
+            bytes.extend(text(&reading.unit, 16));
+            for value in [reading.value, reading.min, reading.max, reading.avg] {
+                bytes.extend(value.to_le_bytes());
+            }
+            bytes.resize(bytes.len() + self.reading_padding, 0);
+        }
+        bytes
+    }
+}
+
+/// NUL-padded fixed-size text field; long text is cut to leave room for the NUL
+fn text(s: &str, len: usize) -> Vec<u8> {
+    let mut field: Vec<u8> = s.bytes().take(len - 1).collect();
+    field.resize(len, 0);
+    field
+}
+
+/// An image from which the decoder recovers `data`'s CPU, GPU, drive and fan
+/// readings, using the labels HWiNFO gives them
+pub fn encode(data: &SensorData) -> Vec<u8> {
+    let mut image = ImageBuilder::new();
+    let reading = |image: ImageBuilder, kind, label: &str, unit, value: Option<f64>| match value {
+        Some(value) => image.reading(kind, label, unit, value),
+        None => image,
+    };
+
+    let cpu = &data.cpu;
+    image = image.sensor(cpu.name.as_deref().unwrap_or("CPU [#0]: Intel Core i7"));
+    image = reading(image, ReadingType::Temp, "CPU (Tctl/Tdie)", "°C", cpu.package_temp_c);
+    for (i, temp) in cpu.core_temps.iter().enumerate() {
+        image = image.reading(ReadingType::Temp, &format!("Core{} (CCD1)", i), "°C", *temp);
+    }
+    image = reading(image, ReadingType::Power, "CPU PPT", "W", cpu.package_power_w);
+    image = reading(image, ReadingType::Clock, "Core Clocks (avg)", "MHz", cpu.core_clock_mhz);
+    image = reading(image, ReadingType::Usage, "Total CPU Usage", "%", cpu.usage_percent);
+
+    let gpu = &data.gpu;
+    if let Some(name) = &gpu.name {
+        image = image.sensor(&format!("GPU [#0]: {}", name));
+        image = reading(image, ReadingType::Temp, "GPU Temperature", "°C", gpu.core_temp_c);
+        image = reading(image, ReadingType::Temp, "GPU Hot Spot Temperature", "°C", gpu.hotspot_temp_c);
+        image = reading(image, ReadingType::Temp, "GPU Memory Junction Temperature", "°C", gpu.memory_junction_temp_c);
+        image = reading(image, ReadingType::Power, "GPU Power", "W", gpu.power_w);
+        image = reading(image, ReadingType::Clock, "GPU Clock", "MHz", gpu.core_clock_mhz);
+        image = reading(image, ReadingType::Clock, "GPU Memory Clock", "MHz", gpu.memory_clock_mhz);
+        image = reading(image, ReadingType::Usage, "GPU Core Load", "%", gpu.usage_percent);
+        image = reading(image, ReadingType::Other, "GPU Memory Used", "MB", gpu.vram_used_mb);
+        image = reading(image, ReadingType::Other, "GPU Memory Total", "MB", gpu.vram_total_mb);
+        image = reading(image, ReadingType::Fan, "GPU Fan1", "RPM", gpu.fan_speed_rpm);
+        image = reading(image, ReadingType::Usage, "GPU Fan1 Speed", "%", gpu.fan_speed_percent);
+    }
+
+    for drive in &data.drives {
+        let name = drive.name.as_deref().unwrap_or("Drive");
+        let name = match &drive.drive_letter {
+            _ if name.to_lowercase().starts_with("s.m.a.r.t.") => name.to_string(),
+            Some(letter) => format!("S.M.A.R.T.: {} [{}]", name, letter),
+            None => format!("S.M.A.R.T.: {}", name),
+        };
+        image = image.sensor(&name);
+        image = reading(image, ReadingType::Temp, "Drive Temperature", "°C", drive.temp_c);
+        // The decoder grades remaining life: 70% and up is good, 30% and up a warning
+        let remaining_life = match drive.smart_health.as_str() {
+            "good" => Some((100.0 - drive.wear_percent.unwrap_or(0.0)).max(70.0)),
+            "warning" => Some(50.0),
+            "critical" => Some(10.0),
+            _ => None,
+        };
+        image = reading(image, ReadingType::Other, "Drive Remaining Life", "%", remaining_life);
+    }
+
+    if !data.system.fans.is_empty() {
+        image = image.sensor("Nuvoton NCT6799D");
+        for fan in &data.system.fans {
+            image = image.reading(ReadingType::Fan, &fan.name, "RPM", fan.rpm);
+        }
+    }
+    image.build()
+}
+
+/// `data` as the HWiNFO source would have read it: encoded, then decoded.
+/// What a region does not carry, and the diagnostics, are kept from `data`.
+pub fn round_trip(data: &SensorData) -> Result<SensorData, String> {
+    let mut decoded = decode::decode(&encode(data))?;
+    decode::carry_over(&mut decoded, data);
+    decoded.last_read_at = data.last_read_at.clone();
+    decoded.diagnostics = data.diagnostics.clone();
+    Ok(decoded)
+}
@@ -1,34 +1,143 @@
+//! Fake readings for development without HWiNFO.
+//!
+//! `MockGenerator` takes its time from a `Clock` and its noise from a seeded
+//! `Rng`, so the same clock, seed and scenario give the same readings every
+//! run.
+
+use super::scenario::{Rng, Scenario};
 use super::types::*;
-use chrono::Utc;
+use chrono::{DateTime, Duration, Utc};
+
+/// Where the mock gets the time of each reading
+pub trait Clock: Send {
+    fn now(&mut self) -> DateTime<Utc>;
+}
+
+/// Wall time
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&mut self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Starts at a fixed time and moves `step` forward on every reading
+pub struct SteppedClock {
+    next: DateTime<Utc>,
+    step: Duration,
+}
+
+impl SteppedClock {
+    pub fn new(start: DateTime<Utc>, step: Duration) -> Self {
+        Self { next: start, step }
+    }
+}
+
+impl Clock for SteppedClock {
+    fn now(&mut self) -> DateTime<Utc> {
+        let now = self.next;
+        self.next += self.step;
+        now
+    }
+}
+
+/// Produces mock readings: the default rig, or a scenario's timeline
+pub struct MockGenerator {
+    scenario: Option<Scenario>,
+    clock: Box<dyn Clock>,
+    rng: Rng,
+    /// First reading; timelines count from here
+    started: Option<DateTime<Utc>>,
+    /// Seconds from the first reading to the latest
+    elapsed_secs: f64,
+}
+
+impl MockGenerator {
+    /// The default rig, with noise from `seed`
+    pub fn new(clock: Box<dyn Clock>, seed: u64) -> Self {
+        Self {
+            scenario: None,
+            clock,
+            rng: Rng::new(seed),
+            started: None,
+            elapsed_secs: 0.0,
+        }
+    }
+
+    /// Follow `scenario`, with noise from its seed
+    pub fn with_scenario(scenario: Scenario, clock: Box<dyn Clock>) -> Self {
+        Self {
+            rng: Rng::new(scenario.seed),
+            scenario: Some(scenario),
+            ..Self::new(clock, 0)
+        }
+    }
 
-static START_TIME: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+    pub fn scenario(&self) -> Option<&Scenario> {
+        self.scenario.as_ref()
+    }
+
+    /// Whether the latest reading found the fake HWiNFO running
+    pub fn is_connected(&self) -> bool {
+        self.scenario.as_ref().is_none_or(|s| s.is_connected(self.elapsed_secs))
+    }
+
+    /// The next reading, or the scenario's disconnect error
+    pub fn next_reading(&mut self) -> Result<SensorData, String> {
+        let now = self.clock.now();
+        let started = *self.started.get_or_insert(now);
+        self.elapsed_secs = (now - started).num_milliseconds().max(0) as f64 / 1000.0;
+
+        let mut data = match &self.scenario {
+            Some(scenario) => scenario.sample(self.elapsed_secs, &mut self.rng)?,
+            None => rig(self.elapsed_secs, &mut self.rng),
+        };
+        data.last_read_at = Some(now.to_rfc3339());
+        Ok(data)
+    }
+}
 
-pub fn read_mock_data() -> SensorData {
-    let start = START_TIME.get_or_init(std::time::Instant::now);
-    let elapsed_secs = start.elapsed().as_secs();
+/// The default rig `elapsed_secs` into the run
+fn rig(elapsed_secs: f64, rng: &mut Rng) -> SensorData {
+    let elapsed_secs = elapsed_secs as u64;
     let uptime_base: u64 = 3600 * 24 * 2; // 2 days base
 
     // Add some variation based on time
-    let variation = ((elapsed_secs % 10) as f64 - 5.0) * 0.5;
+    let variation = ((elapsed_secs % 10) as f64 - 5.0) * 0.5 + rng.noise(0.25);
 
     SensorData {
         status: "connected".to_string(),
-        last_read_at: Some(Utc::now().to_rfc3339()),
+        last_read_at: None,
         diagnostics: Diagnostics {
             hwinfo_process_detected: true,
             shared_memory_detected: true,
             message: Some("Mock mode active".to_string()),
+            source: Some("mock".to_string()),
+            provenance: Default::default(),
         },
         cpu: CpuData {
             name: Some("AMD Ryzen 7 7800X3D".to_string()),
             package_temp_c: Some(45.0 + variation),
             package_power_w: Some(65.0 + variation * 2.0),
+            core_power_w: Some(48.0 + variation * 2.0),
+            uncore_power_w: None,
             core_clock_mhz: Some(4500.0 + variation * 100.0),
+            max_clock_mhz: Some(5050.0),
             usage_percent: Some(25.0 + variation * 5.0),
             core_temps: vec![44.0, 45.0, 43.0, 46.0, 44.0, 45.0, 43.0, 44.0],
+            core_clocks_mhz: vec![4550.0, 4480.0, 4420.0, 4600.0, 4500.0, 4470.0, 4530.0, 4450.0]
+                .into_iter()
+                .map(|c| c + variation * 100.0)
+                .collect(),
+            core_usage_percent: vec![30.0, 22.0, 18.0, 35.0, 25.0, 20.0, 28.0, 22.0]
+                .into_iter()
+                .map(|u| u + variation * 5.0)
+                .collect(),
         },
         gpu: GpuData {
             name: Some("NVIDIA GeForce RTX 5070".to_string()),
+            core_temp_c: Some(45.0 + variation),
             hotspot_temp_c: Some(55.0 + variation),
             memory_junction_temp_c: Some(60.0 + variation),
             power_w: Some(120.0 + variation * 5.0),
@@ -40,6 +149,7 @@ pub fn read_mock_data() -> SensorData {
             fan_speed_rpm: Some(1200.0 + variation * 100.0),
             fan_speed_percent: Some(35.0),
         },
+        gpus: Vec::new(),
         storage: StorageData {
             name: Some("S.M.A.R.T.: Lexar SSD NQ780 2TB [C:]".to_string()),
             nvme_temp_c: Some(38.0 + variation * 0.5),
@@ -53,6 +163,10 @@ pub fn read_mock_data() -> SensorData {
                 smart_health: "good".to_string(),
                 total_gb: Some(1863.0),
                 free_gb: Some(1245.0),
+                wear_percent: Some(3.0),
+                media_errors: Some(0),
+                reallocated_sectors: None,
+                smart_passed: Some(true),
             },
             DriveData {
                 name: Some("S.M.A.R.T.: Samsung 970 EVO 1TB [D:]".to_string()),
@@ -61,6 +175,10 @@ pub fn read_mock_data() -> SensorData {
                 smart_health: "good".to_string(),
                 total_gb: Some(931.0),
                 free_gb: Some(512.0),
+                wear_percent: Some(11.0),
+                media_errors: Some(0),
+                reallocated_sectors: None,
+                smart_passed: Some(true),
             },
         ],
         system: SystemData {
@@ -71,6 +189,10 @@ pub fn read_mock_data() -> SensorData {
                 FanReading { name: "CPU Fan".to_string(), rpm: 1100.0 },
                 FanReading { name: "Chassis Fan 1".to_string(), rpm: 900.0 },
             ],
+            memory_total_mb: Some(32768.0),
+            memory_used_mb: Some(11264.0 + variation * 64.0),
+            load_average: None,
         },
+        raw_readings: Vec::new(),
     }
 }
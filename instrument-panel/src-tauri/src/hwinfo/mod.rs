@@ -1,33 +1,14 @@
+pub mod decode;
+pub mod image;
 pub mod process;
-pub mod shared_memory;
 pub mod types;
 pub mod mock;
+pub mod scenario;
+pub mod snapshot;
 
-use types::{SensorData, Diagnostics, CpuData, GpuData, StorageData, SystemData};
+#[cfg(windows)]
+pub mod shared_memory;
 
-/// Main entry point: read sensor data from HWiNFO
-pub fn read_sensor_data() -> SensorData {
-    let process_running = process::is_running();
-    let shm_result = shared_memory::read();
-    
-    match shm_result {
-        Ok(data) => data,
-        Err(msg) => {
-            // Return not_connected state with diagnostics
-            SensorData {
-                status: "not_connected".to_string(),
-                last_read_at: None,
-                diagnostics: Diagnostics {
-                    hwinfo_process_detected: process_running,
-                    shared_memory_detected: false,
-                    message: Some(msg),
-                },
-                cpu: CpuData::default(),
-                gpu: GpuData::default(),
-                storage: StorageData::default(),
-                drives: Vec::new(),
-                system: SystemData::default(),
-            }
-        }
-    }
-}
+#[cfg(not(windows))]
+#[path = "shared_memory_unsupported.rs"]
+pub mod shared_memory;
@@ -0,0 +1,286 @@
+//! Scripted scenarios for the mock source.
+//!
+//! A scenario is a JSON file with a hardware inventory and a timeline per
+//! metric:
+//!
+//! ```json
+//! {
+//!   "name": "Thermal runaway",
+//!   "inventory": { "cpu": { "name": "AMD Ryzen 7 7800X3D", "coreTemps": [0, 0, 0, 0] } },
+//!   "metrics": {
+//!     "cpu.packageTempC": { "value": 48, "noise": 0.5, "events": [{ "at": 20, "rampTo": 98, "over": 120 }] },
+//!     "cpu.coreTemps": { "value": 46, "noise": 1.5 },
+//!     "connected": { "value": true, "events": [{ "at": 150, "set": false }] }
+//!   }
+//! }
+//! ```
+//!
+//! The inventory is laid over an empty connected reading, in the same
+//! camelCase shape the frontend receives. Metric keys are paths into that
+//! shape ("drives.0.tempC", "system.fans.1.rpm"); a path that ends at a list
+//! sets every element, each with its own noise. The `connected` metric makes
+//! polls fail while it is false, the way a crashed HWiNFO does.
+//!
+//! Events apply in time order from `at` seconds after the scenario starts:
+//! `set` jumps to a number or string, `rampTo`/`over` moves linearly,
+//! `dropout` blanks the metric for that many seconds and `noise` changes the
+//! ± amplitude of uniform noise.
+
+use super::types::{Diagnostics, SensorData, SystemData};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// Scenarios shipped with the app, by id
+pub const BUNDLED: &[(&str, &str)] = &[
+    ("idle", include_str!("scenarios/idle.json")),
+    ("gaming", include_str!("scenarios/gaming.json")),
+    ("thermal-runaway", include_str!("scenarios/thermal_runaway.json")),
+    ("dying-ssd", include_str!("scenarios/dying_ssd.json")),
+    ("hwinfo-crash", include_str!("scenarios/hwinfo_crash.json")),
+];
+
+/// Metric that makes polls fail while false
+const CONNECTED: &str = "connected";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Scenario {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Restart the timeline after this many seconds
+    #[serde(default)]
+    pub loop_secs: Option<f64>,
+    /// Seed for the noise generator
+    #[serde(default)]
+    pub seed: u64,
+    /// Poll error while `connected` is false
+    #[serde(default)]
+    pub disconnected_message: Option<String>,
+    #[serde(default)]
+    inventory: Map<String, Value>,
+    #[serde(default)]
+    metrics: BTreeMap<String, Timeline>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Timeline {
+    value: Value,
+    #[serde(default)]
+    noise: f64,
+    #[serde(default)]
+    events: Vec<Event>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Event {
+    at: f64,
+    #[serde(flatten)]
+    change: Change,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum Change {
+    #[serde(rename_all = "camelCase")]
+    Ramp { ramp_to: f64, over: f64 },
+    Set { set: Value },
+    Dropout { dropout: f64 },
+    Noise { noise: f64 },
+}
+
+/// Small xorshift generator; scenarios need repeatable noise, not good randomness
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // xorshift is stuck at zero, so never start there
+        Self(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    /// Uniform in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform in [-amplitude, amplitude]
+    pub fn noise(&mut self, amplitude: f64) -> f64 {
+        if amplitude == 0.0 {
+            return 0.0;
+        }
+        (self.next_f64() * 2.0 - 1.0) * amplitude
+    }
+}
+
+/// (id, scenario) for every bundled scenario
+pub fn bundled() -> Vec<(&'static str, Scenario)> {
+    BUNDLED
+        .iter()
+        .map(|(id, json)| (*id, parse(json).expect("bundled scenarios are valid")))
+        .collect()
+}
+
+/// A bundled scenario by id, or else a scenario file at `id_or_path`
+pub fn load(id_or_path: &str) -> Result<Scenario, String> {
+    if let Some((_, json)) = BUNDLED.iter().find(|(id, _)| *id == id_or_path) {
+        return parse(json);
+    }
+    let json = std::fs::read_to_string(id_or_path)
+        .map_err(|e| format!("Failed to read scenario {}: {}", id_or_path, e))?;
+    parse(&json)
+}
+
+/// Parse a scenario, checking every metric path exists in its inventory
+pub fn parse(json: &str) -> Result<Scenario, String> {
+    let mut scenario: Scenario = serde_json::from_str(json).map_err(|e| format!("Invalid scenario: {}", e))?;
+
+    let base = scenario.base();
+    for (path, timeline) in &mut scenario.metrics {
+        if path != CONNECTED && base.pointer(&pointer(path)).is_none() {
+            return Err(format!("Scenario metric {} is not in the inventory", path));
+        }
+        timeline.events.sort_by(|a, b| a.at.total_cmp(&b.at));
+    }
+    Ok(scenario)
+}
+
+fn pointer(path: &str) -> String {
+    format!("/{}", path.replace('.', "/"))
+}
+
+/// Lay `overlay` over `base`, merging objects key by key
+fn overlay(base: &mut Value, overlay: &Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(key) {
+                    Some(existing) => self::overlay(existing, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay.clone(),
+    }
+}
+
+impl Timeline {
+    /// Value at `t` seconds, before noise. Null during a dropout.
+    fn value_at(&self, t: f64) -> (Value, f64) {
+        let mut value = self.value.clone();
+        let mut noise = self.noise;
+        let mut dropped = false;
+
+        for (i, event) in self.events.iter().enumerate().take_while(|(_, e)| e.at <= t) {
+            match &event.change {
+                Change::Set { set } => value = set.clone(),
+                Change::Ramp { ramp_to, over } => {
+                    // A later set or ramp takes over from wherever this one had got to
+                    let until = self.events[i + 1..]
+                        .iter()
+                        .find(|e| matches!(e.change, Change::Set { .. } | Change::Ramp { .. }))
+                        .map(|e| e.at)
+                        .filter(|at| *at <= t)
+                        .unwrap_or(t);
+                    let progress = if *over > 0.0 { ((until - event.at) / over).clamp(0.0, 1.0) } else { 1.0 };
+                    let from = value.as_f64().unwrap_or(*ramp_to);
+                    value = Value::from(from + (ramp_to - from) * progress);
+                }
+                Change::Dropout { dropout } => dropped = t < event.at + dropout,
+                Change::Noise { noise: amplitude } => noise = *amplitude,
+            }
+        }
+
+        if dropped {
+            (Value::Null, 0.0)
+        } else {
+            (value, noise)
+        }
+    }
+}
+
+/// `SensorData` fields that are unsigned integers; ramps and noise on them are rounded
+const INTEGER_FIELDS: &[&str] = &["uptimeSeconds", "mediaErrors", "reallocatedSectors"];
+
+/// `value` plus noise; no mocked metric goes below zero
+fn with_noise(value: &Value, noise: f64, integer: bool, rng: &mut Rng) -> Value {
+    let Some(v) = value.as_f64() else { return value.clone() };
+    let v = (v + rng.noise(noise)).max(0.0);
+    if integer {
+        Value::from(v.round() as u64)
+    } else {
+        Value::from(v)
+    }
+}
+
+impl Scenario {
+    /// Empty connected reading with the inventory laid over it
+    fn base(&self) -> Value {
+        let diagnostics = Diagnostics {
+            hwinfo_process_detected: true,
+            shared_memory_detected: true,
+            message: Some(format!("Mock scenario: {}", self.name)),
+            source: Some("mock".to_string()),
+            provenance: Default::default(),
+        };
+        let mut base = serde_json::to_value(SensorData::connected(diagnostics)).expect("SensorData serializes");
+        overlay(&mut base, &Value::Object(self.inventory.clone()));
+        base
+    }
+
+    /// Whether the `connected` metric is true (or absent) at `t`
+    pub fn is_connected(&self, t: f64) -> bool {
+        let t = self.timeline_secs(t);
+        self.metrics
+            .get(CONNECTED)
+            .is_none_or(|timeline| timeline.value_at(t).0 != Value::Bool(false))
+    }
+
+    fn timeline_secs(&self, t: f64) -> f64 {
+        match self.loop_secs {
+            Some(period) if period > 0.0 => t % period,
+            _ => t,
+        }
+    }
+
+    /// The reading `t` seconds into the scenario, or the disconnect error
+    pub fn sample(&self, t: f64, rng: &mut Rng) -> Result<SensorData, String> {
+        if !self.is_connected(t) {
+            return Err(self
+                .disconnected_message
+                .clone()
+                .unwrap_or_else(|| "HWiNFO shared memory not found".to_string()));
+        }
+        let t = self.timeline_secs(t);
+
+        let mut data = self.base();
+        for (path, timeline) in self.metrics.iter().filter(|(path, _)| *path != CONNECTED) {
+            let (value, noise) = timeline.value_at(t);
+            let integer = path.rsplit('.').next().is_some_and(|field| INTEGER_FIELDS.contains(&field));
+            let Some(target) = data.pointer_mut(&pointer(path)) else { continue };
+            match target {
+                // Lists can't hold nulls; a dropout empties them
+                Value::Array(items) if value.is_null() => items.clear(),
+                Value::Array(items) if !value.is_array() => {
+                    for item in items.iter_mut() {
+                        *item = with_noise(&value, noise, integer, rng);
+                    }
+                }
+                target => *target = with_noise(&value, noise, integer, rng),
+            }
+        }
+
+        let mut data: SensorData =
+            serde_json::from_value(data).map_err(|e| format!("Scenario {} produced an invalid reading: {}", self.name, e))?;
+        if !self.metrics.contains_key("system.fanStatus") {
+            data.system.fan_status = SystemData::fan_status_for(&data.system.fans);
+        }
+        Ok(data)
+    }
+}
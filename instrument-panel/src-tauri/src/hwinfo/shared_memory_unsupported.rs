@@ -0,0 +1,32 @@
+//! Stand-in for `shared_memory` on platforms where HWiNFO cannot run.
+//! Keeps the command layer and the HWiNFO source compiling everywhere.
+
+use super::types::SensorData;
+use crate::commands::{DebugDumpResult, ReadingDebugInfo};
+
+const UNSUPPORTED: &str = "HWiNFO shared memory is only available on Windows";
+
+/// Read sensor data from HWiNFO shared memory
+pub fn read() -> Result<SensorData, String> {
+    Err(UNSUPPORTED.to_string())
+}
+
+/// Sensor data from a region copied by `read_region`
+pub fn from_region(_region: &[u8]) -> Result<SensorData, String> {
+    Err(UNSUPPORTED.to_string())
+}
+
+/// Copy the raw shared-memory region, for snapshots
+pub fn read_region() -> Result<Vec<u8>, String> {
+    Err(UNSUPPORTED.to_string())
+}
+
+/// Debug function to dump all sensor info
+pub fn debug_dump_sensors() -> Result<DebugDumpResult, String> {
+    Err(UNSUPPORTED.to_string())
+}
+
+/// Debug function to dump all readings from HWiNFO shared memory
+pub fn debug_dump_readings(_filter: Option<String>) -> Result<Vec<ReadingDebugInfo>, String> {
+    Err(UNSUPPORTED.to_string())
+}
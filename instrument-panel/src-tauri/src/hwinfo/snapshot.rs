@@ -0,0 +1,243 @@
+//! Snapshot files: raw HWiNFO shared-memory regions captured on someone's
+//! machine, for attaching to bug reports and replaying elsewhere.
+//!
+//! A snapshot is JSON. Each frame holds one poll: the region bytes as hex
+//! (header, sensor and reading sections, exactly as HWiNFO wrote them) and
+//! the `SensorData` the panel made of that poll, so a mis-mapped reading can
+//! be compared against its source. `version` is bumped whenever the layout
+//! changes incompatibly.
+//!
+//! Played back, a frame goes through the same decoder as live HWiNFO data;
+//! frames without a region (HWiNFO was not running, or another source was
+//! selected) replay the recorded `SensorData` instead.
+
+use super::decode;
+use super::types::SensorData;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use sysinfo::{CpuRefreshKind, RefreshKind, System};
+
+/// Marks a file as a snapshot
+pub const FORMAT: &str = "instrument-panel-snapshot";
+/// Snapshot layout this build writes and the newest it reads
+pub const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Snapshot {
+    pub format: String,
+    pub version: u32,
+    /// Version of the app that captured it
+    pub app_version: String,
+    pub captured_at: String, // ISO-8601 timestamp of the first frame
+    /// Source that was selected when capturing
+    pub source: String,
+    pub host: HostInfo,
+    pub frames: Vec<Frame>,
+}
+
+/// The machine a snapshot was captured on
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostInfo {
+    pub hostname: Option<String>,
+    pub os: Option<String>,
+    pub os_version: Option<String>,
+    pub kernel_version: Option<String>,
+    pub cpu: Option<String>,
+    pub cpu_count: usize,
+    pub memory_total_mb: f64,
+}
+
+impl HostInfo {
+    pub fn current() -> Self {
+        let system = System::new_with_specifics(
+            RefreshKind::new()
+                .with_cpu(CpuRefreshKind::new())
+                .with_memory(sysinfo::MemoryRefreshKind::new().with_ram()),
+        );
+        Self {
+            hostname: System::host_name(),
+            os: System::name(),
+            os_version: System::os_version(),
+            kernel_version: System::kernel_version(),
+            cpu: system.cpus().first().map(|c| c.brand().trim().to_string()),
+            cpu_count: system.cpus().len(),
+            memory_total_mb: system.total_memory() as f64 / (1024.0 * 1024.0),
+        }
+    }
+}
+
+/// One poll
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Frame {
+    /// Milliseconds after the first frame
+    pub elapsed_ms: u64,
+    pub captured_at: String,
+    /// The HWiNFO shared-memory region, hex encoded
+    #[serde(default, with = "hex", skip_serializing_if = "Option::is_none")]
+    pub region: Option<Vec<u8>>,
+    /// Why the region could not be read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region_error: Option<String>,
+    /// What the panel made of this poll
+    pub data: Option<SensorData>,
+}
+
+impl Snapshot {
+    pub fn new(source: &str, host: HostInfo) -> Self {
+        Self {
+            format: FORMAT.to_string(),
+            version: FORMAT_VERSION,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            captured_at: chrono::Utc::now().to_rfc3339(),
+            source: source.to_string(),
+            host,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Add a poll taken `elapsed` after the first
+    pub fn push(&mut self, elapsed: Duration, region: Result<Vec<u8>, String>, data: Option<SensorData>) {
+        let (region, region_error) = match region {
+            Ok(region) => (Some(region), None),
+            Err(e) => (None, Some(e)),
+        };
+        self.frames.push(Frame {
+            elapsed_ms: elapsed.as_millis() as u64,
+            captured_at: chrono::Utc::now().to_rfc3339(),
+            region,
+            region_error,
+            data,
+        });
+    }
+
+    /// File name for this snapshot, from its capture time
+    pub fn file_name(&self) -> String {
+        let stamp = chrono::DateTime::parse_from_rfc3339(&self.captured_at)
+            .map(|t| t.format("%Y%m%d-%H%M%S").to_string())
+            .unwrap_or_else(|_| "unknown".to_string());
+        format!("snapshot-{}.json", stamp)
+    }
+
+    /// One pass through the frames: up to the last frame plus the average
+    /// interval between frames
+    pub fn duration_ms(&self) -> u64 {
+        match self.frames.as_slice() {
+            [] => 0,
+            [only] => only.elapsed_ms.saturating_add(1000),
+            [first, .., last] => {
+                let interval = last.elapsed_ms.saturating_sub(first.elapsed_ms) / (self.frames.len() as u64 - 1);
+                last.elapsed_ms.saturating_add(interval.max(1))
+            }
+        }
+    }
+
+    /// Index of the frame showing `elapsed_ms` into playback, or `None` once
+    /// playback without looping has passed the end
+    pub fn frame_at(&self, elapsed_ms: u64, looping: bool) -> Option<usize> {
+        let duration = self.duration_ms();
+        if duration == 0 || (!looping && elapsed_ms >= duration) {
+            return None;
+        }
+        let t = elapsed_ms % duration;
+        Some(self.frames.iter().rposition(|f| f.elapsed_ms <= t).unwrap_or(0))
+    }
+
+    /// The reading for frame `index`
+    pub fn replay(&self, index: usize) -> Result<SensorData, String> {
+        let frame = self
+            .frames
+            .get(index)
+            .ok_or_else(|| format!("Snapshot has no frame {}", index))?;
+        let recorded = frame.data.as_ref();
+
+        let Some(region) = &frame.region else {
+            return match recorded {
+                Some(data) if data.status != "not_connected" => Ok(data.clone()),
+                _ => Err(frame
+                    .region_error
+                    .clone()
+                    .or_else(|| recorded.and_then(|d| d.diagnostics.message.clone()))
+                    .unwrap_or_else(|| "Snapshot frame has no readings".to_string())),
+            };
+        };
+
+        let mut data = decode::decode(region)?;
+        // What the region does not carry comes from the capturing machine
+        if let Some(recorded) = recorded {
+            decode::carry_over(&mut data, recorded);
+        }
+        data.system.name = self.host.hostname.clone().or(data.system.name);
+        Ok(data)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, json).map_err(|e| format!("Failed to write snapshot {}: {}", path.display(), e))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let json = fs::read_to_string(path).map_err(|e| format!("Failed to read snapshot {}: {}", path.display(), e))?;
+        parse(&json)
+    }
+}
+
+pub fn parse(json: &str) -> Result<Snapshot, String> {
+    #[derive(Deserialize)]
+    struct Version {
+        format: String,
+        version: u32,
+    }
+    // Check the version first, so a newer layout gets a clear error rather than a field mismatch
+    let header: Version = serde_json::from_str(json).map_err(|e| format!("Not a snapshot file: {}", e))?;
+    if header.format != FORMAT {
+        return Err(format!("Not a snapshot file: format is {}", header.format));
+    }
+    if header.version > FORMAT_VERSION {
+        return Err(format!(
+            "Snapshot version {} is newer than this app reads ({})",
+            header.version, FORMAT_VERSION
+        ));
+    }
+    serde_json::from_str(json).map_err(|e| format!("Invalid snapshot: {}", e))
+}
+
+/// Region bytes as a lowercase hex string
+mod hex {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::fmt::Write;
+
+    pub fn serialize<S: Serializer>(bytes: &Option<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error> {
+        match bytes {
+            Some(bytes) => {
+                let mut hex = String::with_capacity(bytes.len() * 2);
+                for b in bytes {
+                    let _ = write!(hex, "{:02x}", b);
+                }
+                serializer.serialize_some(&hex)
+            }
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<u8>>, D::Error> {
+        let Some(hex) = Option::<String>::deserialize(deserializer)? else { return Ok(None) };
+        if hex.len() % 2 != 0 {
+            return Err(serde::de::Error::custom("region has an odd number of hex digits"));
+        }
+        hex.as_bytes()
+            .chunks(2)
+            .map(|pair| {
+                std::str::from_utf8(pair)
+                    .ok()
+                    .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+            })
+            .collect::<Option<Vec<u8>>>()
+            .map(Some)
+            .ok_or_else(|| serde::de::Error::custom("region is not hex"))
+    }
+}
@@ -1,17 +1,50 @@
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Main sensor data structure returned to frontend
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SensorData {
-    pub status: String, // "connected" | "not_connected"
+    pub status: String, // "connected" | "not_connected" | "fallback"
     pub last_read_at: Option<String>, // ISO-8601 timestamp
     pub diagnostics: Diagnostics,
     pub cpu: CpuData,
-    pub gpu: GpuData,
+    pub gpu: GpuData, // primary GPU
+    #[serde(default)]
+    pub gpus: Vec<GpuData>, // every GPU, for sources that report more than one
     pub storage: StorageData,
     pub drives: Vec<DriveData>,
     pub system: SystemData,
+    #[serde(default)]
+    pub raw_readings: Vec<RawReading>, // readings the source could not map into the fields above
+}
+
+impl SensorData {
+    /// Empty reading returned when a source cannot be read
+    pub fn not_connected(diagnostics: Diagnostics) -> Self {
+        Self {
+            status: "not_connected".to_string(),
+            last_read_at: None,
+            diagnostics,
+            cpu: CpuData::default(),
+            gpu: GpuData::default(),
+            gpus: Vec::new(),
+            storage: StorageData::default(),
+            drives: Vec::new(),
+            system: SystemData::default(),
+            raw_readings: Vec::new(),
+        }
+    }
+
+    /// Connected reading with every section empty, for sources that fill it piecemeal
+    pub fn connected(diagnostics: Diagnostics) -> Self {
+        Self {
+            status: "connected".to_string(),
+            last_read_at: Some(Utc::now().to_rfc3339()),
+            ..Self::not_connected(diagnostics)
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +53,9 @@ pub struct Diagnostics {
     pub hwinfo_process_detected: bool,
     pub shared_memory_detected: bool,
     pub message: Option<String>,
+    pub source: Option<String>, // id of the data source that produced this reading
+    #[serde(default)]
+    pub provenance: BTreeMap<String, String>, // field path → source id, for merged readings
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -28,15 +64,25 @@ pub struct CpuData {
     pub name: Option<String>,
     pub package_temp_c: Option<f64>,
     pub package_power_w: Option<f64>,
+    pub core_power_w: Option<f64>,   // cores only (RAPL "core" domain)
+    pub uncore_power_w: Option<f64>, // integrated graphics/ring (RAPL "uncore" domain)
     pub core_clock_mhz: Option<f64>,
+    pub max_clock_mhz: Option<f64>,
     pub usage_percent: Option<f64>,
     pub core_temps: Vec<f64>,
+    #[serde(default)]
+    pub core_clocks_mhz: Vec<f64>,
+    #[serde(default)]
+    pub core_usage_percent: Vec<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct GpuData {
     pub name: Option<String>,
+    /// Core (edge) temperature, from sources with no hot spot sensor
+    #[serde(default)]
+    pub core_temp_c: Option<f64>,
     pub hotspot_temp_c: Option<f64>,
     pub memory_junction_temp_c: Option<f64>,
     pub power_w: Option<f64>,
@@ -76,6 +122,14 @@ pub struct DriveData {
     pub smart_health: String,
     pub total_gb: Option<f64>,
     pub free_gb: Option<f64>,
+    #[serde(default)]
+    pub wear_percent: Option<f64>, // rated endurance used, 0 when new
+    #[serde(default)]
+    pub media_errors: Option<u64>, // uncorrectable media/data integrity errors
+    #[serde(default)]
+    pub reallocated_sectors: Option<u64>,
+    #[serde(default)]
+    pub smart_passed: Option<bool>, // the drive's own overall self-assessment
 }
 
 impl Default for DriveData {
@@ -87,6 +141,23 @@ impl Default for DriveData {
             smart_health: "unknown".to_string(),
             total_gb: None,
             free_gb: None,
+            wear_percent: None,
+            media_errors: None,
+            reallocated_sectors: None,
+            smart_passed: None,
+        }
+    }
+}
+
+impl DriveData {
+    /// S.M.A.R.T. health from remaining life: 70%+ good, 30-70% warning, below critical
+    pub fn health_for_remaining_life(percent: f64) -> String {
+        if percent >= 70.0 {
+            "good".to_string()
+        } else if percent >= 30.0 {
+            "warning".to_string()
+        } else {
+            "critical".to_string()
         }
     }
 }
@@ -98,6 +169,9 @@ pub struct SystemData {
     pub uptime_seconds: Option<u64>,
     pub fan_status: String, // "ok" | "warning" | "unknown"
     pub fans: Vec<FanReading>,
+    pub memory_total_mb: Option<f64>,
+    pub memory_used_mb: Option<f64>,
+    pub load_average: Option<LoadAverage>, // Unix 1/5/15 minute run-queue averages
 }
 
 impl Default for SystemData {
@@ -107,6 +181,22 @@ impl Default for SystemData {
             uptime_seconds: None,
             fan_status: "unknown".to_string(),
             fans: Vec::new(),
+            memory_total_mb: None,
+            memory_used_mb: None,
+            load_average: None,
+        }
+    }
+}
+
+impl SystemData {
+    /// Overall fan status: "warning" if any fan looks stalled (< 200 RPM)
+    pub fn fan_status_for(fans: &[FanReading]) -> String {
+        if fans.is_empty() {
+            "unknown".to_string()
+        } else if fans.iter().any(|f| f.rpm < 200.0) {
+            "warning".to_string()
+        } else {
+            "ok".to_string()
         }
     }
 }
@@ -118,6 +208,23 @@ pub struct FanReading {
     pub rpm: f64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RawReading {
+    pub group: String, // chip or device the reading belongs to
+    pub label: String,
+    pub value: f64,
+    pub unit: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadAverage {
+    pub one: f64,
+    pub five: f64,
+    pub fifteen: f64,
+}
+
 // ============================================================
 // HWiNFO Shared Memory Structures
 // Based on HWiNFO SDK documentation
@@ -168,7 +275,7 @@ pub enum ReadingType {
 }
 
 /// A single sensor reading
-/// Total size: 312 bytes (0x138)
+/// Total size: 316 bytes (0x13C)
 #[repr(C, packed)]
 #[derive(Debug, Clone, Copy)]
 pub struct HWiNFOReading {
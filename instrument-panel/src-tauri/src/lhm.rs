@@ -0,0 +1,378 @@
+//! LibreHardwareMonitor's remote web server (`Options > Remote Web Server`).
+//!
+//! `data.json` is a tree: the root's only child is the machine, whose children
+//! are hardware nodes (CPU, GPU, drives, mainboard with its Super I/O chip as a
+//! sub-node). Each hardware node groups its sensors by type ("Temperatures",
+//! "Clocks", ...). Values are formatted strings such as "45.3 °C", using the
+//! decimal separator of the machine's locale.
+
+use crate::hwinfo::types::*;
+use serde::Deserialize;
+use std::time::Duration;
+
+/// Where LibreHardwareMonitor serves its tree by default
+pub const DEFAULT_URL: &str = "http://localhost:8085/data.json";
+
+/// One node of `data.json`, either a hardware/group node or a sensor leaf
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Node {
+    #[serde(default)]
+    pub text: String,
+    #[serde(default)]
+    pub value: String,
+    #[serde(default)]
+    pub min: String,
+    #[serde(default)]
+    pub max: String,
+    /// e.g. "/amdcpu/0/temperature/2", only present on sensors in 0.9+
+    #[serde(default)]
+    pub sensor_id: Option<String>,
+    /// e.g. "/gpu-nvidia/0", only present on hardware nodes in 0.9+
+    #[serde(default)]
+    pub hardware_id: Option<String>,
+    /// e.g. "Temperature", only present on sensors in 0.9+
+    #[serde(default, rename = "Type")]
+    pub kind: Option<String>,
+    #[serde(default, rename = "ImageURL")]
+    pub image_url: Option<String>,
+    #[serde(default)]
+    pub children: Vec<Node>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SensorKind {
+    Temperature,
+    Load,
+    Clock,
+    Power,
+    Fan,
+    Control,
+    Voltage,
+    Level,
+    Data,
+    SmallData,
+    Other,
+}
+
+impl SensorKind {
+    /// From a `Type` field, a sensor id segment or a group name
+    fn parse(name: &str) -> Self {
+        match name.to_lowercase().as_str() {
+            "temperature" | "temperatures" => Self::Temperature,
+            "load" => Self::Load,
+            "clock" | "clocks" => Self::Clock,
+            "power" | "powers" => Self::Power,
+            "fan" | "fans" => Self::Fan,
+            "control" | "controls" => Self::Control,
+            "voltage" | "voltages" => Self::Voltage,
+            "level" | "levels" => Self::Level,
+            "data" => Self::Data,
+            "smalldata" => Self::SmallData,
+            _ => Self::Other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HardwareKind {
+    Cpu,
+    Gpu,
+    Storage,
+    Memory,
+    Motherboard,
+    SuperIo,
+    Other,
+}
+
+/// A sensor leaf with its value parsed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sensor {
+    pub name: String,
+    pub kind: SensorKind,
+    pub value: Option<f64>,
+    /// Unit as displayed, e.g. "°C", "MHz", "GB"
+    pub unit: String,
+}
+
+/// A hardware node and the sensors directly under its groups
+#[derive(Debug, Clone)]
+pub struct Hardware {
+    pub name: String,
+    pub kind: HardwareKind,
+    pub sensors: Vec<Sensor>,
+}
+
+impl Hardware {
+    fn sensors(&self, kind: SensorKind) -> impl Iterator<Item = &Sensor> {
+        self.sensors.iter().filter(move |s| s.kind == kind)
+    }
+
+    /// Value of the first sensor of `kind` whose lowercase name matches
+    fn find(&self, kind: SensorKind, matches: impl Fn(&str) -> bool) -> Option<f64> {
+        self.sensors(kind)
+            .find(|s| s.value.is_some() && matches(&s.name.to_lowercase()))
+            .and_then(|s| s.value)
+    }
+}
+
+/// Split a displayed value such as "4,450.0 MHz" or "45,3 °C" into number and unit
+pub fn parse_value(text: &str) -> Option<(f64, String)> {
+    let text = text.trim();
+    let (number, unit) = text.split_once(' ').unwrap_or((text, ""));
+    let number = if number.contains('.') {
+        number.replace(',', "")
+    } else {
+        number.replace(',', ".")
+    };
+    Some((number.parse().ok()?, unit.trim().to_string()))
+}
+
+pub fn parse(json: &str) -> Result<Node, String> {
+    serde_json::from_str(json).map_err(|e| format!("Invalid LibreHardwareMonitor JSON: {}", e))
+}
+
+/// GET `url` and parse the tree
+pub fn fetch(agent: &ureq::Agent, url: &str) -> Result<Node, String> {
+    let response = agent.get(url).call().map_err(|e| match e {
+        ureq::Error::Status(code, _) => format!("LibreHardwareMonitor returned HTTP {} for {}", code, url),
+        ureq::Error::Transport(e) => format!(
+            "Could not reach LibreHardwareMonitor at {}: {}. Is its remote web server running?",
+            url, e
+        ),
+    })?;
+    let body = response
+        .into_string()
+        .map_err(|e| format!("Failed to read LibreHardwareMonitor response: {}", e))?;
+    parse(&body)
+}
+
+/// Agent with a timeout short enough for the UI's poll interval
+pub fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new().timeout(Duration::from_secs(2)).build()
+}
+
+fn is_group(node: &Node) -> bool {
+    !node.children.is_empty() && node.children.iter().all(|c| c.children.is_empty())
+}
+
+/// Every hardware node in the tree, sub-hardware (e.g. the Super I/O chip under
+/// the mainboard) listed separately after its parent
+pub fn hardware(root: &Node) -> Vec<Hardware> {
+    let mut found = Vec::new();
+    collect(root, &mut found);
+    found
+}
+
+fn collect(node: &Node, found: &mut Vec<Hardware>) {
+    if node.children.iter().any(is_group) {
+        let sensors: Vec<Sensor> = node
+            .children
+            .iter()
+            .filter(|c| is_group(c))
+            .flat_map(|group| group.children.iter().filter_map(move |leaf| sensor(group, leaf)))
+            .collect();
+        found.push(Hardware {
+            name: node.text.clone(),
+            kind: hardware_kind(node, &sensor_ids(node)),
+            sensors,
+        });
+    }
+    for child in node.children.iter().filter(|c| !is_group(c)) {
+        collect(child, found);
+    }
+}
+
+fn sensor_ids(node: &Node) -> Vec<&str> {
+    node.children
+        .iter()
+        .filter(|c| is_group(c))
+        .flat_map(|g| g.children.iter().filter_map(|l| l.sensor_id.as_deref()))
+        .collect()
+}
+
+fn sensor(group: &Node, leaf: &Node) -> Option<Sensor> {
+    if leaf.value.trim().is_empty() {
+        return None;
+    }
+    // "/amdcpu/0/temperature/2": the type is the second-to-last segment
+    let kind = leaf
+        .kind
+        .as_deref()
+        .or_else(|| leaf.sensor_id.as_deref()?.rsplit('/').nth(1))
+        .map(SensorKind::parse)
+        .unwrap_or_else(|| SensorKind::parse(&group.text));
+    let parsed = parse_value(&leaf.value);
+    Some(Sensor {
+        name: leaf.text.clone(),
+        kind,
+        value: parsed.as_ref().map(|(v, _)| *v),
+        unit: parsed.map(|(_, u)| u).unwrap_or_default(),
+    })
+}
+
+/// From the hardware id or the sensor ids' first segment ("/amdcpu/0/...",
+/// "/gpu-nvidia/0/...") or, for older versions without ids, the node's icon
+fn hardware_kind(node: &Node, sensor_ids: &[&str]) -> HardwareKind {
+    let hint = node
+        .hardware_id
+        .as_deref()
+        .or(sensor_ids.first().copied())
+        .and_then(|id| id.trim_start_matches('/').split('/').next())
+        .or(node.image_url.as_deref())
+        .unwrap_or("")
+        .to_lowercase();
+
+    if hint.contains("cpu") {
+        HardwareKind::Cpu
+    } else if hint.contains("gpu") || hint.contains("nvidia") || hint.contains("ati") {
+        HardwareKind::Gpu
+    } else if hint.contains("nvme") || hint.contains("hdd") || hint.contains("ssd") || hint.contains("storage") {
+        HardwareKind::Storage
+    } else if hint.contains("ram") || hint.contains("memory") {
+        HardwareKind::Memory
+    } else if hint.contains("lpc") || hint.contains("chip") {
+        HardwareKind::SuperIo
+    } else if hint.contains("mainboard") {
+        HardwareKind::Motherboard
+    } else {
+        HardwareKind::Other
+    }
+}
+
+fn to_megabytes(sensor: &Sensor) -> Option<f64> {
+    let value = sensor.value?;
+    Some(match sensor.unit.as_str() {
+        "GB" => value * 1024.0,
+        "KB" => value / 1024.0,
+        _ => value,
+    })
+}
+
+fn average(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+/// Map the tree into `data`
+pub fn apply(root: &Node, data: &mut SensorData) {
+    let hardware = hardware(root);
+
+    data.system.name = root.children.first().map(|m| m.text.clone()).filter(|t| !t.is_empty());
+
+    if let Some(cpu) = hardware.iter().find(|h| h.kind == HardwareKind::Cpu) {
+        apply_cpu(cpu, &mut data.cpu);
+    }
+
+    // The card with the most VRAM is the discrete one; integrated GPUs report none
+    let vram_total = |h: &Hardware| h.find(SensorKind::SmallData, |n| n.contains("memory total")).unwrap_or(0.0);
+    if let Some(gpu) = hardware
+        .iter()
+        .filter(|h| h.kind == HardwareKind::Gpu)
+        .reduce(|best, h| if vram_total(h) > vram_total(best) { h } else { best })
+    {
+        apply_gpu(gpu, &mut data.gpu);
+    }
+
+    for drive in hardware.iter().filter(|h| h.kind == HardwareKind::Storage) {
+        data.drives.push(drive_data(drive));
+    }
+    if let Some(first) = data.drives.first() {
+        data.storage = StorageData {
+            name: first.name.clone(),
+            nvme_temp_c: first.temp_c,
+            smart_health: first.smart_health.clone(),
+        };
+    }
+
+    if let Some(memory) = hardware.iter().find(|h| h.kind == HardwareKind::Memory) {
+        let used = memory.sensors(SensorKind::Data).find(|s| s.name == "Memory Used").and_then(to_megabytes);
+        let available = memory.sensors(SensorKind::Data).find(|s| s.name == "Memory Available").and_then(to_megabytes);
+        data.system.memory_used_mb = used;
+        data.system.memory_total_mb = used.zip(available).map(|(u, a)| u + a);
+    }
+
+    data.system.fans = hardware
+        .iter()
+        .filter(|h| matches!(h.kind, HardwareKind::SuperIo | HardwareKind::Motherboard))
+        .flat_map(|h| h.sensors(SensorKind::Fan))
+        .filter_map(|s| Some(FanReading { name: s.name.clone(), rpm: s.value? }))
+        .filter(|f| f.rpm > 0.0)
+        .collect();
+    data.system.fan_status = SystemData::fan_status_for(&data.system.fans);
+}
+
+fn apply_cpu(hw: &Hardware, cpu: &mut CpuData) {
+    cpu.name = Some(hw.name.clone());
+    // Intel "CPU Package", AMD "Core (Tctl/Tdie)"; per-CCD readings are "CCD1 (Tdie)"
+    cpu.package_temp_c = hw
+        .find(SensorKind::Temperature, |n| n.contains("package"))
+        .or_else(|| hw.find(SensorKind::Temperature, |n| n.contains("tctl")))
+        .or_else(|| hw.find(SensorKind::Temperature, |n| n.contains("tdie") && !n.starts_with("ccd")));
+    cpu.core_temps = hw
+        .sensors(SensorKind::Temperature)
+        .filter(|s| {
+            let name = s.name.to_lowercase();
+            (name.starts_with("core #") && !name.contains("distance")) || name.starts_with("ccd")
+        })
+        .filter_map(|s| s.value)
+        .collect();
+    cpu.package_power_w = hw.find(SensorKind::Power, |n| n.contains("package"));
+    cpu.core_power_w = hw.find(SensorKind::Power, |n| n == "cpu cores");
+    cpu.core_clocks_mhz = hw
+        .sensors(SensorKind::Clock)
+        .filter(|s| s.name.starts_with("Core #"))
+        .filter_map(|s| s.value)
+        .collect();
+    cpu.core_clock_mhz = average(&cpu.core_clocks_mhz);
+    cpu.usage_percent = hw.find(SensorKind::Load, |n| n == "cpu total");
+    // "CPU Core #1", or "CPU Core #1 Thread #1" with SMT on newer versions
+    cpu.core_usage_percent = hw
+        .sensors(SensorKind::Load)
+        .filter(|s| s.name.starts_with("CPU Core #"))
+        .filter_map(|s| s.value)
+        .collect();
+}
+
+fn apply_gpu(hw: &Hardware, gpu: &mut GpuData) {
+    gpu.name = Some(hw.name.clone());
+    gpu.core_temp_c = hw.find(SensorKind::Temperature, |n| n == "gpu core");
+    gpu.hotspot_temp_c = hw.find(SensorKind::Temperature, |n| n.contains("hot spot"));
+    gpu.memory_junction_temp_c = hw.find(SensorKind::Temperature, |n| n.contains("memory junction") || n == "gpu memory");
+    gpu.power_w = hw
+        .find(SensorKind::Power, |n| n.contains("package") || n.contains("ppt"))
+        .or_else(|| hw.find(SensorKind::Power, |n| n == "gpu power" || n == "gpu core"));
+    gpu.core_clock_mhz = hw.find(SensorKind::Clock, |n| n == "gpu core");
+    gpu.memory_clock_mhz = hw.find(SensorKind::Clock, |n| n == "gpu memory");
+    gpu.usage_percent = hw.find(SensorKind::Load, |n| n == "gpu core");
+    let vram = |name: &str| {
+        hw.sensors(SensorKind::SmallData)
+            .find(|s| s.name.eq_ignore_ascii_case(name))
+            .and_then(to_megabytes)
+    };
+    gpu.vram_used_mb = vram("GPU Memory Used").or_else(|| vram("D3D Dedicated Memory Used"));
+    gpu.vram_total_mb = vram("GPU Memory Total");
+    gpu.fan_speed_rpm = hw.sensors(SensorKind::Fan).find_map(|s| s.value);
+    gpu.fan_speed_percent = hw.sensors(SensorKind::Control).find_map(|s| s.value);
+}
+
+fn drive_data(hw: &Hardware) -> DriveData {
+    // NVMe reports "Percentage Used" (wear, 0 when new); SATA SSDs "Remaining Life"
+    let remaining_life = hw
+        .find(SensorKind::Level, |n| n == "remaining life")
+        .or_else(|| hw.find(SensorKind::Level, |n| n == "percentage used").map(|used| 100.0 - used));
+
+    DriveData {
+        name: Some(hw.name.clone()),
+        temp_c: hw
+            .find(SensorKind::Temperature, |n| n.contains("composite"))
+            .or_else(|| hw.sensors(SensorKind::Temperature).find_map(|s| s.value)),
+        smart_health: remaining_life
+            .map(DriveData::health_for_remaining_life)
+            .unwrap_or_else(|| "unknown".to_string()),
+        ..DriveData::default()
+    }
+}
@@ -1,19 +1,78 @@
+pub mod aida64;
+pub mod collector;
 mod commands;
-mod hwinfo;
+pub mod delta;
+pub mod hwinfo;
+pub mod lhm;
+#[cfg(target_os = "linux")]
+pub mod linux;
+pub mod merge;
+pub mod node_exporter;
+pub mod nvidia_smi;
+pub mod poller;
+pub mod power;
+pub mod smartctl;
+pub mod sources;
+pub mod subprocess;
 
-use commands::{get_sensor_data, is_hwinfo_running, launch_hwinfo, get_settings, save_settings, debug_dump_sensors, debug_dump_readings};
+pub use commands::AppSettings;
+
+use commands::{get_sensor_data, resync_sensor_data, refresh_sensor_data, subscribe_sensor_data, unsubscribe_sensor_data, list_data_sources, list_collectors, list_mock_scenarios, is_hwinfo_running, launch_hwinfo, get_settings, save_settings, capture_snapshot, debug_dump_sensors, debug_dump_readings};
+use poller::{Poller, WindowState};
+use sources::{ActiveSource, SourceRegistry};
+use tauri::{Emitter, Manager, WindowEvent};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_shell::init())
+        .manage(ActiveSource::new(SourceRegistry::with_builtin()))
+        .setup(|app| {
+            let handle = app.handle().clone();
+            let poller = Poller::spawn(
+                commands::get_settings(handle.clone()),
+                {
+                    let handle = handle.clone();
+                    move |settings| handle.state::<ActiveSource>().poll(settings)
+                },
+                move |update| {
+                    let _ = handle.emit(poller::UPDATE_EVENT, update);
+                },
+            );
+            app.manage(poller);
+            Ok(())
+        })
+        // Poll less often while no window is in front
+        .on_window_event(|window, event| {
+            let Some(poller) = window.app_handle().try_state::<Poller>() else { return };
+            match event {
+                WindowEvent::Focused(_) | WindowEvent::Resized(_) => {
+                    let focused = match event {
+                        WindowEvent::Focused(focused) => *focused,
+                        _ => window.is_focused().unwrap_or(true),
+                    };
+                    let visible = window.is_visible().unwrap_or(true) && !window.is_minimized().unwrap_or(false);
+                    poller.set_window(window.label(), WindowState { visible, focused });
+                }
+                WindowEvent::Destroyed => poller.close_window(window.label()),
+                _ => {}
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             get_sensor_data,
+            resync_sensor_data,
+            refresh_sensor_data,
+            subscribe_sensor_data,
+            unsubscribe_sensor_data,
+            list_data_sources,
+            list_collectors,
+            list_mock_scenarios,
             is_hwinfo_running,
             launch_hwinfo,
             get_settings,
             save_settings,
+            capture_snapshot,
             debug_dump_sensors,
             debug_dump_readings
         ])
@@ -0,0 +1,56 @@
+//! Per-CPU clocks from `/sys/devices/system/cpu/cpu*/cpufreq`.
+//!
+//! `scaling_cur_freq` and `cpuinfo_max_freq` are in kHz. Logical CPUs are
+//! reported individually, so SMT siblings appear as separate entries.
+
+use super::read_number;
+use crate::hwinfo::types::CpuData;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CpuFrequency {
+    pub cpu: u32,
+    pub current_mhz: f64,
+    pub max_mhz: Option<f64>,
+}
+
+/// Read every CPU with a cpufreq directory, ordered by CPU number
+pub fn read_frequencies(sysfs_root: &Path) -> Vec<CpuFrequency> {
+    let dir = sysfs_root.join("devices/system/cpu");
+    let Ok(entries) = fs::read_dir(&dir) else { return Vec::new() };
+
+    let mut frequencies: Vec<CpuFrequency> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            // cpu0, cpu1, ... but not cpufreq/cpuidle
+            let cpu: u32 = e.file_name().to_str()?.strip_prefix("cpu")?.parse().ok()?;
+            let cpufreq = e.path().join("cpufreq");
+            Some(CpuFrequency {
+                cpu,
+                current_mhz: read_number(&cpufreq.join("scaling_cur_freq"))? / 1000.0,
+                max_mhz: read_number(&cpufreq.join("cpuinfo_max_freq")).map(|khz| khz / 1000.0),
+            })
+        })
+        .collect();
+
+    frequencies.sort_by_key(|f| f.cpu);
+    frequencies
+}
+
+/// Fill the CPU clock fields. Fields that are already set are left alone.
+pub fn apply(frequencies: &[CpuFrequency], cpu: &mut CpuData) {
+    if frequencies.is_empty() {
+        return;
+    }
+    if cpu.core_clocks_mhz.is_empty() {
+        cpu.core_clocks_mhz = frequencies.iter().map(|f| f.current_mhz).collect();
+    }
+    if cpu.core_clock_mhz.is_none() {
+        let total: f64 = frequencies.iter().map(|f| f.current_mhz).sum();
+        cpu.core_clock_mhz = Some(total / frequencies.len() as f64);
+    }
+    if cpu.max_clock_mhz.is_none() {
+        cpu.max_clock_mhz = frequencies.iter().filter_map(|f| f.max_mhz).reduce(f64::max);
+    }
+}
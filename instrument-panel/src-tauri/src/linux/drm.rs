@@ -0,0 +1,117 @@
+//! amdgpu metrics from `/sys/class/drm/card*/device`.
+//!
+//! The amdgpu driver exposes utilization (`gpu_busy_percent`), VRAM usage
+//! (`mem_info_vram_used`/`mem_info_vram_total`, bytes) and the DPM clock tables
+//! (`pp_dpm_sclk`/`pp_dpm_mclk`, active level marked with `*`) on the PCI
+//! device, with temperatures, power and fan on its own hwmon directory.
+
+use super::hwmon::{self, Chip};
+use super::{read_number, read_string};
+use crate::hwinfo::types::GpuData;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const AMD_VENDOR_ID: &str = "0x1002";
+
+/// One amdgpu card
+#[derive(Debug, Clone)]
+pub struct DrmCard {
+    /// e.g. "card1"
+    pub card: String,
+    pub device_path: PathBuf,
+    pub product_name: Option<String>,
+    pub busy_percent: Option<f64>,
+    pub vram_used_mb: Option<f64>,
+    pub vram_total_mb: Option<f64>,
+    pub core_clock_mhz: Option<f64>,
+    pub memory_clock_mhz: Option<f64>,
+    pub hwmon: Option<Chip>,
+}
+
+/// Read every AMD card under `<sysfs_root>/class/drm`, ordered by card number
+pub fn read_cards(sysfs_root: &Path) -> Vec<DrmCard> {
+    let dir = sysfs_root.join("class/drm");
+    let Ok(entries) = fs::read_dir(&dir) else { return Vec::new() };
+
+    let mut cards: Vec<DrmCard> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let card = e.file_name().to_str()?.to_string();
+            // Skip connectors such as "card1-DP-2" and render nodes
+            let number = card.strip_prefix("card")?;
+            if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            read_card(&e.path().join("device"), card)
+        })
+        .collect();
+
+    cards.sort_by_key(|c| c.card.trim_start_matches("card").parse::<u32>().unwrap_or(u32::MAX));
+    cards
+}
+
+fn read_card(device: &Path, card: String) -> Option<DrmCard> {
+    if read_string(&device.join("vendor"))?.to_lowercase() != AMD_VENDOR_ID {
+        return None;
+    }
+
+    let bytes_to_mb = |v: f64| v / (1024.0 * 1024.0);
+    let hwmon = fs::read_dir(device.join("hwmon"))
+        .ok()
+        .and_then(|mut entries| entries.find_map(|e| hwmon::read_chip(&e.ok()?.path())));
+
+    Some(DrmCard {
+        product_name: read_string(&device.join("product_name")),
+        busy_percent: read_number(&device.join("gpu_busy_percent")),
+        vram_used_mb: read_number(&device.join("mem_info_vram_used")).map(bytes_to_mb),
+        vram_total_mb: read_number(&device.join("mem_info_vram_total")).map(bytes_to_mb),
+        core_clock_mhz: read_string(&device.join("pp_dpm_sclk")).and_then(|t| parse_dpm_active(&t)),
+        memory_clock_mhz: read_string(&device.join("pp_dpm_mclk")).and_then(|t| parse_dpm_active(&t)),
+        device_path: device.to_path_buf(),
+        hwmon,
+        card,
+    })
+}
+
+/// Clock of the active DPM level, e.g. "1: 1800Mhz *" -> 1800.0
+pub fn parse_dpm_active(text: &str) -> Option<f64> {
+    let line = text.lines().find(|l| l.trim_end().ends_with('*'))?;
+    let (_, level) = line.split_once(':')?;
+    let level = level.trim().trim_end_matches('*').trim().to_lowercase();
+    level.strip_suffix("mhz")?.trim().parse().ok()
+}
+
+/// Fill the GPU fields from the card with the most VRAM, which is the discrete
+/// card on APU systems. Fields that are already set are left alone.
+pub fn apply(cards: &[DrmCard], gpu: &mut GpuData) {
+    let Some(card) = cards.iter().max_by(|a, b| {
+        a.vram_total_mb.unwrap_or(0.0).total_cmp(&b.vram_total_mb.unwrap_or(0.0))
+    }) else {
+        return;
+    };
+
+    if gpu.name.is_none() {
+        gpu.name = card
+            .product_name
+            .clone()
+            .or_else(|| Some(format!("AMD Radeon ({})", card.card)));
+    }
+    if gpu.usage_percent.is_none() {
+        gpu.usage_percent = card.busy_percent;
+    }
+    if gpu.vram_used_mb.is_none() {
+        gpu.vram_used_mb = card.vram_used_mb;
+    }
+    if gpu.vram_total_mb.is_none() {
+        gpu.vram_total_mb = card.vram_total_mb;
+    }
+    if gpu.core_clock_mhz.is_none() {
+        gpu.core_clock_mhz = card.core_clock_mhz;
+    }
+    if gpu.memory_clock_mhz.is_none() {
+        gpu.memory_clock_mhz = card.memory_clock_mhz;
+    }
+    if let Some(chip) = &card.hwmon {
+        hwmon::apply_amdgpu(chip, gpu);
+    }
+}
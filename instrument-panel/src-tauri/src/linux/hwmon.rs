@@ -0,0 +1,361 @@
+//! `/sys/class/hwmon` reader.
+//!
+//! Each `hwmonN` directory is one chip with a `name` file and a set of
+//! channel attributes: `temp*_input` (m°C), `fan*_input` (RPM), `in*_input` (mV),
+//! `power*_input`/`power*_average` (µW) and `freq*_input` (Hz), each with an
+//! optional `*_label`. Values are converted to °C, RPM, V, W and MHz.
+
+use super::{read_number, read_string};
+use crate::hwinfo::types::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChannelKind {
+    Temp,
+    Fan,
+    Voltage,
+    Power,
+    Frequency,
+}
+
+impl ChannelKind {
+    fn from_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            "temp" => Some(Self::Temp),
+            "fan" => Some(Self::Fan),
+            "in" => Some(Self::Voltage),
+            "power" => Some(Self::Power),
+            "freq" => Some(Self::Frequency),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn prefix(self) -> &'static str {
+        match self {
+            Self::Temp => "temp",
+            Self::Fan => "fan",
+            Self::Voltage => "in",
+            Self::Power => "power",
+            Self::Frequency => "freq",
+        }
+    }
+
+    /// Display unit after scaling
+    pub fn unit(self) -> &'static str {
+        match self {
+            Self::Temp => "°C",
+            Self::Fan => "RPM",
+            Self::Voltage => "V",
+            Self::Power => "W",
+            Self::Frequency => "MHz",
+        }
+    }
+
+    /// Divisor from the raw sysfs unit to the unit used in `SensorData`
+    fn scale(self) -> f64 {
+        match self {
+            Self::Temp => 1000.0,
+            Self::Fan => 1.0,
+            Self::Voltage => 1000.0,
+            Self::Power => 1_000_000.0,
+            Self::Frequency => 1_000_000.0,
+        }
+    }
+}
+
+/// One reading on a chip, already converted to display units
+#[derive(Debug, Clone)]
+pub struct Channel {
+    pub kind: ChannelKind,
+    pub index: u32,
+    pub label: Option<String>,
+    pub value: f64,
+}
+
+impl Channel {
+    /// The channel label, or e.g. "fan2" when the driver provides none
+    pub fn name(&self) -> String {
+        self.label
+            .clone()
+            .unwrap_or_else(|| format!("{}{}", self.kind.prefix(), self.index))
+    }
+}
+
+/// A single `hwmonN` directory
+#[derive(Debug, Clone)]
+pub struct Chip {
+    pub name: String,
+    pub path: PathBuf,
+    /// `device/model` or `device/product_name`, when the driver exposes one
+    pub device_name: Option<String>,
+    pub channels: Vec<Channel>,
+}
+
+impl Chip {
+    pub fn channels(&self, kind: ChannelKind) -> impl Iterator<Item = &Channel> {
+        self.channels.iter().filter(move |c| c.kind == kind)
+    }
+
+    /// Value of the channel whose label matches `label` (case-insensitive)
+    pub fn find(&self, kind: ChannelKind, label: &str) -> Option<f64> {
+        self.channels(kind)
+            .find(|c| c.label.as_deref().is_some_and(|l| l.eq_ignore_ascii_case(label)))
+            .map(|c| c.value)
+    }
+}
+
+/// Chip families we know how to map
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ChipFamily {
+    AmdCpu,
+    IntelCpu,
+    Nvme,
+    AmdGpu,
+    SuperIo,
+    Unknown,
+}
+
+fn chip_family(name: &str) -> ChipFamily {
+    match name {
+        "k10temp" | "zenpower" => ChipFamily::AmdCpu,
+        "coretemp" => ChipFamily::IntelCpu,
+        "nvme" => ChipFamily::Nvme,
+        "amdgpu" => ChipFamily::AmdGpu,
+        // Board Super I/O chips name themselves after the part (it8688, nct6798, ...)
+        n if n.starts_with("it87") || n.starts_with("it86") || n.starts_with("nct6") => ChipFamily::SuperIo,
+        _ => ChipFamily::Unknown,
+    }
+}
+
+/// Read every chip under `<sysfs_root>/class/hwmon`, ordered by hwmon number
+pub fn read_chips(sysfs_root: &Path) -> Result<Vec<Chip>, String> {
+    let dir = sysfs_root.join("class/hwmon");
+    let entries = fs::read_dir(&dir)
+        .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+
+    let mut chips: Vec<Chip> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| read_chip(&entry.path()))
+        .collect();
+
+    chips.sort_by_key(|c| hwmon_number(&c.path));
+    Ok(chips)
+}
+
+fn hwmon_number(path: &Path) -> u32 {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .and_then(|n| n.strip_prefix("hwmon"))
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(u32::MAX)
+}
+
+/// Read one `hwmonN` directory; `None` if it has no `name`
+pub fn read_chip(path: &Path) -> Option<Chip> {
+    let name = read_string(&path.join("name"))?;
+    let device_name = read_string(&path.join("device/model"))
+        .or_else(|| read_string(&path.join("device/product_name")));
+
+    let mut channels: Vec<Channel> = Vec::new();
+    for entry in fs::read_dir(path).ok()?.filter_map(|e| e.ok()) {
+        let file_name = entry.file_name();
+        let Some(file_name) = file_name.to_str() else { continue };
+        let Some((kind, index, suffix)) = parse_attribute(file_name) else { continue };
+
+        // power*_average is only a fallback for drivers without power*_input
+        if suffix == "average" && path.join(format!("power{}_input", index)).exists() {
+            continue;
+        }
+
+        let Some(raw) = read_number(&entry.path()) else { continue };
+        let label = read_string(&path.join(format!("{}{}_label", kind.prefix(), index)));
+
+        channels.push(Channel {
+            kind,
+            index,
+            label,
+            value: raw / kind.scale(),
+        });
+    }
+
+    channels.sort_by_key(|c| (c.kind.prefix(), c.index));
+
+    Some(Chip {
+        name,
+        path: path.to_path_buf(),
+        device_name,
+        channels,
+    })
+}
+
+/// Split e.g. "temp3_input" into (Temp, 3, "input")
+pub(crate) fn parse_attribute(file_name: &str) -> Option<(ChannelKind, u32, &str)> {
+    let (channel, suffix) = file_name.split_once('_')?;
+    if suffix != "input" && suffix != "average" {
+        return None;
+    }
+    let digits_at = channel.find(|c: char| c.is_ascii_digit())?;
+    let (prefix, index) = channel.split_at(digits_at);
+    let kind = ChannelKind::from_prefix(prefix)?;
+    if suffix == "average" && kind != ChannelKind::Power {
+        return None;
+    }
+    Some((kind, index.parse().ok()?, suffix))
+}
+
+/// Channels that `apply` does not map anywhere, e.g. Super I/O voltages or
+/// every channel of an unrecognized chip
+pub fn unmapped_channels(chip: &Chip) -> Vec<&Channel> {
+    let label_starts = |c: &Channel, prefixes: &[&str]| {
+        c.label.as_deref().is_some_and(|l| prefixes.iter().any(|p| l.starts_with(p)))
+    };
+    let nvme_temp = chip
+        .channels(ChannelKind::Temp)
+        .find(|c| c.label.as_deref() == Some("Composite"))
+        .or_else(|| chip.channels(ChannelKind::Temp).next());
+
+    chip.channels
+        .iter()
+        .filter(|c| {
+            let mapped = match chip_family(&chip.name) {
+                ChipFamily::AmdCpu => c.kind == ChannelKind::Temp && label_starts(c, &["Tdie", "Tctl", "Tccd"]),
+                ChipFamily::IntelCpu => c.kind == ChannelKind::Temp && label_starts(c, &["Package id", "Core "]),
+                ChipFamily::Nvme => nvme_temp.is_some_and(|t| std::ptr::eq(t, *c)),
+                ChipFamily::SuperIo => c.kind == ChannelKind::Fan,
+                ChipFamily::AmdGpu => c.kind != ChannelKind::Voltage,
+                ChipFamily::Unknown => false,
+            };
+            !mapped
+        })
+        .collect()
+}
+
+/// Fill `data` from recognized chips. Fields that are already set are left alone.
+pub fn apply(chips: &[Chip], data: &mut SensorData) {
+    for chip in chips {
+        match chip_family(&chip.name) {
+            ChipFamily::AmdCpu => apply_k10temp(chip, &mut data.cpu),
+            ChipFamily::IntelCpu => apply_coretemp(chip, &mut data.cpu),
+            ChipFamily::Nvme => data.drives.push(nvme_drive(chip)),
+            ChipFamily::SuperIo => data.system.fans.extend(superio_fans(chip)),
+            ChipFamily::AmdGpu | ChipFamily::Unknown => {}
+        }
+    }
+
+    // A GPU already identified by the DRM reader owns the GPU fields. Otherwise
+    // pick the discrete card, which has more sensors than an APU's amdgpu chip.
+    if data.gpu.name.is_none() {
+        let discrete = chips
+            .iter()
+            .filter(|c| chip_family(&c.name) == ChipFamily::AmdGpu)
+            .max_by_key(|c| c.channels.len());
+        if let Some(gpu) = discrete {
+            apply_amdgpu(gpu, &mut data.gpu);
+        }
+    }
+
+    if data.storage.name.is_none() {
+        if let Some(first) = data.drives.first() {
+            data.storage = StorageData {
+                name: first.name.clone(),
+                nvme_temp_c: first.temp_c,
+                smart_health: first.smart_health.clone(),
+            };
+        }
+    }
+
+    data.system.fan_status = SystemData::fan_status_for(&data.system.fans);
+}
+
+/// AMD Zen: Tctl (control temp, may carry an offset), Tdie, and Tccd1..N
+fn apply_k10temp(chip: &Chip, cpu: &mut CpuData) {
+    if cpu.package_temp_c.is_none() {
+        cpu.package_temp_c = chip
+            .find(ChannelKind::Temp, "Tdie")
+            .or_else(|| chip.find(ChannelKind::Temp, "Tctl"));
+    }
+    if cpu.core_temps.is_empty() {
+        cpu.core_temps = chip
+            .channels(ChannelKind::Temp)
+            .filter(|c| c.label.as_deref().is_some_and(|l| l.starts_with("Tccd")))
+            .map(|c| c.value)
+            .collect();
+    }
+}
+
+/// Intel: "Package id N" plus one "Core N" per physical core
+fn apply_coretemp(chip: &Chip, cpu: &mut CpuData) {
+    if cpu.package_temp_c.is_none() {
+        cpu.package_temp_c = chip
+            .channels(ChannelKind::Temp)
+            .find(|c| c.label.as_deref().is_some_and(|l| l.starts_with("Package id")))
+            .map(|c| c.value);
+    }
+    // Multi-socket systems have one coretemp chip per package
+    cpu.core_temps.extend(
+        chip.channels(ChannelKind::Temp)
+            .filter(|c| c.label.as_deref().is_some_and(|l| l.starts_with("Core ")))
+            .map(|c| c.value),
+    );
+}
+
+/// amdgpu: edge/junction/mem temps, PPT power, sclk/mclk, fan1 and pwm1
+pub(crate) fn apply_amdgpu(chip: &Chip, gpu: &mut GpuData) {
+    if gpu.name.is_none() {
+        gpu.name = chip.device_name.clone();
+    }
+    if gpu.core_temp_c.is_none() {
+        gpu.core_temp_c = chip.find(ChannelKind::Temp, "edge");
+    }
+    if gpu.hotspot_temp_c.is_none() {
+        gpu.hotspot_temp_c = chip.find(ChannelKind::Temp, "junction");
+    }
+    if gpu.memory_junction_temp_c.is_none() {
+        gpu.memory_junction_temp_c = chip.find(ChannelKind::Temp, "mem");
+    }
+    if gpu.power_w.is_none() {
+        gpu.power_w = chip.channels(ChannelKind::Power).next().map(|c| c.value);
+    }
+    if gpu.core_clock_mhz.is_none() {
+        gpu.core_clock_mhz = chip.find(ChannelKind::Frequency, "sclk");
+    }
+    if gpu.memory_clock_mhz.is_none() {
+        gpu.memory_clock_mhz = chip.find(ChannelKind::Frequency, "mclk");
+    }
+    if gpu.fan_speed_rpm.is_none() {
+        gpu.fan_speed_rpm = chip.channels(ChannelKind::Fan).next().map(|c| c.value);
+    }
+    if gpu.fan_speed_percent.is_none() {
+        // pwm1 is a 0-255 duty cycle
+        gpu.fan_speed_percent = read_number(&chip.path.join("pwm1")).map(|pwm| pwm / 255.0 * 100.0);
+    }
+}
+
+/// NVMe: "Composite" is the controller's headline temperature
+fn nvme_drive(chip: &Chip) -> DriveData {
+    let temp = chip
+        .find(ChannelKind::Temp, "Composite")
+        .or_else(|| chip.channels(ChannelKind::Temp).next().map(|c| c.value));
+    let name = chip.device_name.clone().unwrap_or_else(|| {
+        let hwmon = chip.path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        format!("NVMe ({})", hwmon)
+    });
+
+    DriveData {
+        name: Some(name),
+        temp_c: temp,
+        ..DriveData::default()
+    }
+}
+
+/// Board fan headers; stopped headers (0 RPM) are usually unpopulated
+fn superio_fans(chip: &Chip) -> Vec<FanReading> {
+    chip.channels(ChannelKind::Fan)
+        .filter(|c| c.value > 0.0)
+        .map(|c| FanReading {
+            name: c.name(),
+            rpm: c.value,
+        })
+        .collect()
+}
@@ -0,0 +1,106 @@
+//! `sensors -j` output from lm-sensors.
+//!
+//! The JSON is chip → feature → subfeature:
+//! `{"k10temp-pci-00c3": {"Adapter": "PCI adapter", "Tctl": {"temp1_input": 45.25}}}`.
+//! Feature names are the channel labels (or e.g. "fan1" when there is none) and
+//! libsensors has already scaled the values to °C, RPM, V and W. Chips are turned
+//! into the same `Chip`s the sysfs reader produces so `hwmon::apply` maps both.
+
+use super::hwmon::{self, Channel, Chip};
+use crate::hwinfo::types::RawReading;
+use crate::subprocess;
+use serde_json::{Map, Value};
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+
+/// Command run when none is configured
+pub const DEFAULT_COMMAND: &str = "sensors -j";
+
+/// A chip on a stuck bus can hold `sensors` up; give up on it after this
+pub const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Run `command` (split on whitespace, no shell) and return its stdout
+pub fn run(command: &str) -> Result<String, String> {
+    let mut parts = command.split_whitespace();
+    let program = parts.next().ok_or("lm-sensors command is empty")?;
+    let output = subprocess::output(Command::new(program).args(parts), TIMEOUT)
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+
+    // sensors exits non-zero when a single chip fails to read but still prints the rest
+    if output.stdout.iter().all(u8::is_ascii_whitespace) {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("{} produced no output: {}", command, stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Parse the whole document into chips, ordered by chip id
+pub fn parse(json: &str) -> Result<Vec<Chip>, String> {
+    let root: Map<String, Value> =
+        serde_json::from_str(json).map_err(|e| format!("Invalid sensors -j output: {}", e))?;
+
+    Ok(root
+        .iter()
+        .filter_map(|(id, features)| Some(parse_chip(id, features.as_object()?)))
+        .collect())
+}
+
+fn parse_chip(id: &str, features: &Map<String, Value>) -> Chip {
+    let mut channels: Vec<Channel> = Vec::new();
+
+    for (feature, subfeatures) in features {
+        let Some(subfeatures) = subfeatures.as_object() else { continue };
+        // power*_input wins over power*_average, as in the sysfs reader
+        let mut attributes: Vec<_> = subfeatures
+            .iter()
+            .filter_map(|(name, value)| {
+                let (kind, index, suffix) = hwmon::parse_attribute(name)?;
+                Some((kind, index, suffix, value.as_f64()?))
+            })
+            .collect();
+        attributes.sort_by_key(|(_, _, suffix, _)| *suffix != "input");
+
+        let Some(&(kind, index, _, value)) = attributes.first() else { continue };
+        let default_name = format!("{}{}", kind.prefix(), index);
+        channels.push(Channel {
+            kind,
+            index,
+            label: Some(feature.clone()).filter(|f| *f != default_name),
+            value,
+        });
+    }
+    channels.sort_by_key(|c| (c.kind.prefix(), c.index));
+
+    Chip {
+        name: chip_name(id).to_string(),
+        // Chips from JSON have no sysfs directory; keep the id for display
+        path: PathBuf::from(id),
+        device_name: None,
+        channels,
+    }
+}
+
+/// "nct6798-isa-0290" → "nct6798", the hwmon `name` of the chip
+fn chip_name(id: &str) -> &str {
+    let mut parts = id.rsplitn(3, '-');
+    let (Some(_address), Some(_bus), Some(name)) = (parts.next(), parts.next(), parts.next()) else {
+        return id;
+    };
+    name
+}
+
+/// Every channel `hwmon::apply` leaves unmapped, grouped under its chip id
+pub fn raw_readings(chips: &[Chip]) -> Vec<RawReading> {
+    chips
+        .iter()
+        .flat_map(|chip| {
+            hwmon::unmapped_channels(chip).into_iter().map(move |c| RawReading {
+                group: chip.path.to_string_lossy().into_owned(),
+                label: c.name(),
+                value: c.value,
+                unit: c.kind.unit().to_string(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,29 @@
+//! Native Linux readers for sysfs/procfs.
+//!
+//! Every reader takes the filesystem root it reads from (normally `/sys` or
+//! `/proc`) so it can be pointed at a fake directory tree in tests.
+
+pub mod cpufreq;
+pub mod drm;
+pub mod hwmon;
+pub mod lm_sensors;
+pub mod power_supply;
+pub mod procfs;
+pub mod rapl;
+pub mod thermal;
+
+use std::fs;
+use std::path::Path;
+
+/// Read a sysfs/procfs attribute, trimmed. Missing or unreadable files are `None`.
+pub(crate) fn read_string(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Read a numeric attribute such as `temp1_input`
+pub(crate) fn read_number(path: &Path) -> Option<f64> {
+    read_string(path).and_then(|s| s.parse::<f64>().ok())
+}
@@ -0,0 +1,37 @@
+//! AC adapter and battery state from `/sys/class/power_supply`.
+//!
+//! Used by the poller to slow down on battery.
+
+use super::read_string;
+use std::fs;
+use std::path::Path;
+
+/// Whether the machine is running on battery: no AC adapter is online, or
+/// without an adapter entry, a battery is discharging. `None` when there is
+/// neither (desktops, VMs).
+pub fn on_battery(sysfs_root: &Path) -> Option<bool> {
+    let dir = sysfs_root.join("class/power_supply");
+    let Ok(entries) = fs::read_dir(&dir) else { return None };
+
+    let (mut mains, mut batteries) = (Vec::new(), Vec::new());
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        // Batteries of peripherals such as wireless mice
+        if read_string(&path.join("scope")).as_deref() == Some("Device") {
+            continue;
+        }
+        match read_string(&path.join("type")).as_deref() {
+            Some("Mains") => mains.push(read_string(&path.join("online")).as_deref() == Some("1")),
+            Some("Battery") => batteries.push(read_string(&path.join("status")).as_deref() == Some("Discharging")),
+            _ => {}
+        }
+    }
+
+    if !mains.is_empty() {
+        Some(!mains.contains(&true))
+    } else if !batteries.is_empty() {
+        Some(batteries.contains(&true))
+    } else {
+        None
+    }
+}
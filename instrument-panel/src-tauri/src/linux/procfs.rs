@@ -0,0 +1,214 @@
+//! `/proc` reader for CPU utilization, memory, uptime and load.
+//!
+//! Utilization is a rate, so it needs two `/proc/stat` samples; `ProcReader`
+//! keeps the previous one between calls.
+
+use super::read_string;
+use crate::hwinfo::types::{LoadAverage, SensorData};
+use std::path::PathBuf;
+
+/// Cumulative jiffies for one `cpu` line of `/proc/stat`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CpuTimes {
+    pub user: u64,
+    pub nice: u64,
+    pub system: u64,
+    pub idle: u64,
+    pub iowait: u64,
+    pub irq: u64,
+    pub softirq: u64,
+    pub steal: u64,
+}
+
+impl CpuTimes {
+    fn total(&self) -> u64 {
+        self.busy() + self.idle + self.iowait
+    }
+
+    fn busy(&self) -> u64 {
+        self.user + self.nice + self.system + self.irq + self.softirq + self.steal
+    }
+
+    /// Busy percentage between an earlier sample and this one
+    pub fn usage_since(&self, earlier: &CpuTimes) -> Option<f64> {
+        let total = self.total().checked_sub(earlier.total())?;
+        let busy = self.busy().checked_sub(earlier.busy())?;
+        if total == 0 {
+            return None;
+        }
+        Some(busy as f64 / total as f64 * 100.0)
+    }
+}
+
+/// One parse of `/proc/stat`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatSample {
+    pub total: CpuTimes,
+    /// (cpu number, times); offline CPUs are absent, so numbers can have gaps
+    pub cores: Vec<(u32, CpuTimes)>,
+}
+
+/// Memory figures from `/proc/meminfo`, in MB
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemInfo {
+    pub total_mb: f64,
+    pub available_mb: f64,
+}
+
+impl MemInfo {
+    pub fn used_mb(&self) -> f64 {
+        self.total_mb - self.available_mb
+    }
+}
+
+/// Everything read from procfs in one poll
+#[derive(Debug, Clone, Default)]
+pub struct ProcSnapshot {
+    pub cpu_model: Option<String>,
+    pub usage_percent: Option<f64>,
+    pub core_usage_percent: Vec<f64>,
+    pub memory: Option<MemInfo>,
+    pub uptime_seconds: Option<u64>,
+    pub load_average: Option<LoadAverage>,
+}
+
+pub fn parse_stat(text: &str) -> StatSample {
+    let mut sample = StatSample::default();
+    for line in text.lines() {
+        let mut fields = line.split_whitespace();
+        let Some(name) = fields.next() else { continue };
+        let Some(cpu) = name.strip_prefix("cpu") else { continue };
+
+        let values: Vec<u64> = fields.map(|f| f.parse().unwrap_or(0)).collect();
+        let field = |i: usize| values.get(i).copied().unwrap_or(0);
+        let times = CpuTimes {
+            user: field(0),
+            nice: field(1),
+            system: field(2),
+            idle: field(3),
+            iowait: field(4),
+            irq: field(5),
+            softirq: field(6),
+            steal: field(7),
+        };
+
+        if cpu.is_empty() {
+            sample.total = times;
+        } else if let Ok(n) = cpu.parse() {
+            sample.cores.push((n, times));
+        }
+    }
+    sample
+}
+
+pub fn parse_meminfo(text: &str) -> Option<MemInfo> {
+    let field = |key: &str| -> Option<f64> {
+        text.lines()
+            .find_map(|l| l.strip_prefix(key)?.strip_prefix(':'))
+            .and_then(|rest| rest.split_whitespace().next())
+            .and_then(|kb| kb.parse::<f64>().ok())
+    };
+
+    let total_kb = field("MemTotal")?;
+    // MemAvailable appeared in 3.14; approximate it on older kernels
+    let available_kb = field("MemAvailable").or_else(|| {
+        Some(field("MemFree")? + field("Buffers").unwrap_or(0.0) + field("Cached").unwrap_or(0.0))
+    })?;
+
+    Some(MemInfo {
+        total_mb: total_kb / 1024.0,
+        available_mb: available_kb / 1024.0,
+    })
+}
+
+/// First field of `/proc/uptime`: seconds since boot, including suspend
+pub fn parse_uptime(text: &str) -> Option<u64> {
+    let seconds: f64 = text.split_whitespace().next()?.parse().ok()?;
+    Some(seconds as u64)
+}
+
+pub fn parse_loadavg(text: &str) -> Option<LoadAverage> {
+    let mut fields = text.split_whitespace().map(|f| f.parse::<f64>().ok());
+    Some(LoadAverage {
+        one: fields.next()??,
+        five: fields.next()??,
+        fifteen: fields.next()??,
+    })
+}
+
+/// "model name" of the first processor in `/proc/cpuinfo`
+pub fn parse_cpu_model(text: &str) -> Option<String> {
+    text.lines()
+        .find(|l| l.starts_with("model name"))
+        .and_then(|l| l.split_once(':'))
+        .map(|(_, model)| model.trim().to_string())
+        .filter(|m| !m.is_empty())
+}
+
+/// Stateful procfs reader; utilization figures appear from the second `read` on
+pub struct ProcReader {
+    procfs_root: PathBuf,
+    previous: Option<StatSample>,
+}
+
+impl ProcReader {
+    pub fn new(procfs_root: PathBuf) -> Self {
+        Self {
+            procfs_root,
+            previous: None,
+        }
+    }
+
+    pub fn read(&mut self) -> Result<ProcSnapshot, String> {
+        let stat_path = self.procfs_root.join("stat");
+        let stat = read_string(&stat_path)
+            .map(|text| parse_stat(&text))
+            .ok_or_else(|| format!("Failed to read {}", stat_path.display()))?;
+
+        let mut snapshot = ProcSnapshot {
+            cpu_model: read_string(&self.procfs_root.join("cpuinfo")).and_then(|t| parse_cpu_model(&t)),
+            memory: read_string(&self.procfs_root.join("meminfo")).and_then(|t| parse_meminfo(&t)),
+            uptime_seconds: read_string(&self.procfs_root.join("uptime")).and_then(|t| parse_uptime(&t)),
+            load_average: read_string(&self.procfs_root.join("loadavg")).and_then(|t| parse_loadavg(&t)),
+            ..ProcSnapshot::default()
+        };
+
+        if let Some(previous) = &self.previous {
+            snapshot.usage_percent = stat.total.usage_since(&previous.total);
+            snapshot.core_usage_percent = stat
+                .cores
+                .iter()
+                .filter_map(|(n, times)| {
+                    let (_, earlier) = previous.cores.iter().find(|(p, _)| p == n)?;
+                    times.usage_since(earlier)
+                })
+                .collect();
+        }
+
+        self.previous = Some(stat);
+        Ok(snapshot)
+    }
+}
+
+/// Fill `data` from a snapshot. Fields that are already set are left alone.
+pub fn apply(snapshot: &ProcSnapshot, data: &mut SensorData) {
+    if data.cpu.name.is_none() {
+        data.cpu.name = snapshot.cpu_model.clone();
+    }
+    if data.cpu.usage_percent.is_none() {
+        data.cpu.usage_percent = snapshot.usage_percent;
+    }
+    if data.cpu.core_usage_percent.is_empty() {
+        data.cpu.core_usage_percent = snapshot.core_usage_percent.clone();
+    }
+    if let Some(memory) = snapshot.memory {
+        data.system.memory_total_mb.get_or_insert(memory.total_mb);
+        data.system.memory_used_mb.get_or_insert(memory.used_mb());
+    }
+    if data.system.uptime_seconds.is_none() {
+        data.system.uptime_seconds = snapshot.uptime_seconds;
+    }
+    if data.system.load_average.is_none() {
+        data.system.load_average = snapshot.load_average;
+    }
+}
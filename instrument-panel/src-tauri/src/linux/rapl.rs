@@ -0,0 +1,138 @@
+//! RAPL energy counters under `/sys/class/powercap/intel-rapl:*`.
+//!
+//! Each zone exposes a monotonically increasing `energy_uj` that wraps at
+//! `max_energy_range_uj`; power is the difference between two samples divided
+//! by the time between them. AMD Zen exposes its package and core counters
+//! through the same interface. Since kernel 5.10 `energy_uj` is only readable
+//! by root unless permissions are relaxed, so unreadable zones are skipped.
+
+use super::read_string;
+use crate::hwinfo::types::CpuData;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// One powercap zone, e.g. `intel-rapl:0` ("package-0") or `intel-rapl:0:0` ("core")
+#[derive(Debug, Clone, PartialEq)]
+pub struct RaplZone {
+    pub id: String,
+    pub name: String,
+    pub energy_uj: u64,
+    pub max_energy_range_uj: Option<u64>,
+}
+
+/// Average power of a zone over the last sampling interval
+#[derive(Debug, Clone, PartialEq)]
+pub struct DomainPower {
+    pub id: String,
+    pub name: String,
+    pub watts: f64,
+}
+
+/// Read every readable `intel-rapl:*` zone under `<sysfs_root>/class/powercap`
+pub fn read_zones(sysfs_root: &Path) -> Vec<RaplZone> {
+    let dir = sysfs_root.join("class/powercap");
+    let Ok(entries) = fs::read_dir(&dir) else { return Vec::new() };
+
+    let mut zones: Vec<RaplZone> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let id = e.file_name().to_str()?.to_string();
+            // "intel-rapl" itself is the control type, not a zone
+            if !id.starts_with("intel-rapl:") {
+                return None;
+            }
+            read_zone(&e.path(), id)
+        })
+        .collect();
+
+    zones.sort_by(|a, b| a.id.cmp(&b.id));
+    zones
+}
+
+fn read_zone(path: &Path, id: String) -> Option<RaplZone> {
+    Some(RaplZone {
+        name: read_string(&path.join("name"))?,
+        energy_uj: read_string(&path.join("energy_uj"))?.parse().ok()?,
+        max_energy_range_uj: read_string(&path.join("max_energy_range_uj")).and_then(|v| v.parse().ok()),
+        id,
+    })
+}
+
+/// Energy used between two counter readings, accounting for one wraparound.
+/// `None` if the counter went backwards and the wrap point is unknown.
+pub fn energy_delta_uj(previous: u64, current: u64, max_energy_range_uj: Option<u64>) -> Option<u64> {
+    if current >= previous {
+        Some(current - previous)
+    } else {
+        Some(max_energy_range_uj?.checked_sub(previous)? + current)
+    }
+}
+
+/// Stateful sampler; power figures appear from the second sample on
+pub struct RaplReader {
+    sysfs_root: PathBuf,
+    previous: Option<(Instant, HashMap<String, u64>)>,
+}
+
+impl RaplReader {
+    pub fn new(sysfs_root: PathBuf) -> Self {
+        Self {
+            sysfs_root,
+            previous: None,
+        }
+    }
+
+    pub fn read(&mut self) -> Vec<DomainPower> {
+        self.read_at(Instant::now())
+    }
+
+    /// Sample the counters as of `now`
+    pub fn read_at(&mut self, now: Instant) -> Vec<DomainPower> {
+        let zones = read_zones(&self.sysfs_root);
+        let mut powers = Vec::new();
+
+        if let Some((then, energies)) = &self.previous {
+            let seconds = now.saturating_duration_since(*then).as_secs_f64();
+            if seconds > 0.0 {
+                for zone in &zones {
+                    let Some(&previous) = energies.get(&zone.id) else { continue };
+                    let Some(delta) = energy_delta_uj(previous, zone.energy_uj, zone.max_energy_range_uj) else { continue };
+                    powers.push(DomainPower {
+                        id: zone.id.clone(),
+                        name: zone.name.clone(),
+                        watts: delta as f64 / 1_000_000.0 / seconds,
+                    });
+                }
+            }
+        }
+
+        let energies = zones.into_iter().map(|z| (z.id, z.energy_uj)).collect();
+        self.previous = Some((now, energies));
+        powers
+    }
+}
+
+/// Sum of every zone whose name matches, across sockets
+fn total_watts(powers: &[DomainPower], matches: impl Fn(&str) -> bool) -> Option<f64> {
+    let selected: Vec<f64> = powers.iter().filter(|p| matches(&p.name)).map(|p| p.watts).collect();
+    if selected.is_empty() {
+        None
+    } else {
+        Some(selected.iter().sum())
+    }
+}
+
+/// Fill the CPU power fields. Fields that are already set are left alone.
+pub fn apply(powers: &[DomainPower], cpu: &mut CpuData) {
+    if cpu.package_power_w.is_none() {
+        cpu.package_power_w = total_watts(powers, |n| n.starts_with("package"));
+    }
+    if cpu.core_power_w.is_none() {
+        cpu.core_power_w = total_watts(powers, |n| n == "core");
+    }
+    if cpu.uncore_power_w.is_none() {
+        cpu.uncore_power_w = total_watts(powers, |n| n == "uncore");
+    }
+}
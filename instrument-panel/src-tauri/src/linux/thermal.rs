@@ -0,0 +1,70 @@
+//! ACPI/platform thermal zones from `/sys/class/thermal/thermal_zone*`.
+//!
+//! Only used as a fallback CPU package temperature when hwmon has no
+//! recognized CPU chip (VMs, ARM boards, some laptops).
+
+use super::{read_number, read_string};
+use crate::hwinfo::types::CpuData;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThermalZone {
+    pub zone: u32,
+    pub kind: String,
+    pub temp_c: f64,
+}
+
+/// Read every thermal zone with a valid temperature, ordered by zone number
+pub fn read_zones(sysfs_root: &Path) -> Vec<ThermalZone> {
+    let dir = sysfs_root.join("class/thermal");
+    let Ok(entries) = fs::read_dir(&dir) else { return Vec::new() };
+
+    let mut zones: Vec<ThermalZone> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let zone = e.file_name().to_str()?.strip_prefix("thermal_zone")?.parse().ok()?;
+            let path = e.path();
+            Some(ThermalZone {
+                zone,
+                kind: read_string(&path.join("type"))?,
+                temp_c: read_number(&path.join("temp"))? / 1000.0,
+            })
+        })
+        .collect();
+
+    zones.sort_by_key(|z| z.zone);
+    zones
+}
+
+/// Lower is better; `None` for zones that say nothing about the CPU
+fn cpu_zone_rank(kind: &str) -> Option<u8> {
+    let kind = kind.to_lowercase();
+    if kind == "x86_pkg_temp" {
+        Some(0)
+    } else if kind.starts_with("cpu") || kind.contains("cpu-thermal") || kind.contains("cpu_thermal") {
+        Some(1)
+    } else if kind.starts_with("soc") {
+        Some(2)
+    } else if kind == "acpitz" {
+        Some(3)
+    } else {
+        None
+    }
+}
+
+/// The zone most likely to be the CPU package
+pub fn cpu_zone(zones: &[ThermalZone]) -> Option<&ThermalZone> {
+    zones
+        .iter()
+        .filter_map(|z| Some((cpu_zone_rank(&z.kind)?, z)))
+        .min_by_key(|(rank, z)| (*rank, z.zone))
+        .map(|(_, z)| z)
+}
+
+/// Fill the package temperature if nothing better has set it
+pub fn apply(zones: &[ThermalZone], cpu: &mut CpuData) {
+    if cpu.package_temp_c.is_none() {
+        cpu.package_temp_c = cpu_zone(zones).map(|z| z.temp_c);
+    }
+}
@@ -0,0 +1,212 @@
+//! Field-by-field merge of readings from several sources.
+//!
+//! Fields are addressed by their path in the frontend's JSON, e.g.
+//! "gpu.hotspotTempC", "system.fans" or "drives". Each field in the `cpu`,
+//! `gpu`, `storage` and `system` sections is merged on its own. `gpus` and
+//! `drives` are merged element by element: elements from different sources
+//! that name the same device (the same GPU name, drive name or letter, or a
+//! disk and one of its partitions) become one element whose fields are
+//! merged like a section's, with "drives.tempC" or "drives" as the priority
+//! path; elements no other source knows are kept as they are.
+//! `rawReadings` is taken whole from one source.
+//! A field counts as missing when it is null, an empty list, or "unknown".
+
+use crate::hwinfo::types::{Diagnostics, SensorData};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Sections whose fields are merged one by one
+const SECTIONS: &[&str] = &["cpu", "gpu", "storage", "system"];
+/// Top-level lists taken from a single source
+const LISTS: &[&str] = &["rawReadings"];
+/// Top-level lists merged element by element, with the fields that identify an element
+const ELEMENT_LISTS: &[(&str, &[&str])] = &[("gpus", &["name"]), ("drives", &["name", "driveLetter"])];
+
+/// Which source to prefer for each field
+#[derive(Debug, Clone, Default)]
+pub struct Priorities {
+    /// Source ids, highest priority first
+    default: Vec<String>,
+    /// Field path or section name → source ids, highest priority first
+    fields: BTreeMap<String, Vec<String>>,
+}
+
+impl Priorities {
+    pub fn new(default: Vec<String>, fields: BTreeMap<String, Vec<String>>) -> Self {
+        Self { default, fields }
+    }
+
+    /// Source ids in the order they are tried for `path`: the field's own
+    /// list, then its section's, then the default order. Sources named in none
+    /// of them come last, in the order they were passed to `merge`.
+    pub fn order<'a>(&'a self, path: &str, available: &[&'a str]) -> Vec<&'a str> {
+        let section = path.split('.').next().unwrap_or(path);
+        let mut order: Vec<&str> = Vec::new();
+        let named = self
+            .fields
+            .get(path)
+            .into_iter()
+            .chain(self.fields.get(section))
+            .flatten()
+            .chain(&self.default)
+            .map(String::as_str);
+        for id in named.chain(available.iter().copied()) {
+            if available.contains(&id) && !order.contains(&id) {
+                order.push(id);
+            }
+        }
+        order
+    }
+}
+
+fn is_missing(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Array(items) => items.is_empty(),
+        Value::String(s) => s == "unknown",
+        _ => false,
+    }
+}
+
+/// Merge `readings` (source id, reading) into one reading. Every field that
+/// some source supplied is recorded in `diagnostics.provenance`.
+pub fn merge(readings: &[(&str, SensorData)], priorities: &Priorities, diagnostics: Diagnostics) -> SensorData {
+    let ids: Vec<&str> = readings.iter().map(|(id, _)| *id).collect();
+    let values: BTreeMap<&str, Value> = readings
+        .iter()
+        .map(|(id, data)| (*id, serde_json::to_value(data).expect("SensorData serializes")))
+        .collect();
+
+    let mut merged = serde_json::to_value(SensorData::connected(diagnostics)).expect("SensorData serializes");
+    let mut provenance = BTreeMap::new();
+
+    let mut pick = |path: &str, pointer: &str| -> Option<Value> {
+        let (id, value) = priorities.order(path, &ids).into_iter().find_map(|id| {
+            let value = values[id].pointer(pointer)?;
+            (!is_missing(value)).then_some((id, value))
+        })?;
+        provenance.insert(path.to_string(), id.to_string());
+        Some(value.clone())
+    };
+
+    for section in SECTIONS {
+        let mut fields: Vec<String> = values
+            .values()
+            .filter_map(|v| v.get(section)?.as_object())
+            .flat_map(|fields| fields.keys().cloned())
+            .collect();
+        fields.sort();
+        fields.dedup();
+        for field in fields {
+            if let Some(value) = pick(&format!("{}.{}", section, field), &format!("/{}/{}", section, field)) {
+                merged[section][field] = value;
+            }
+        }
+    }
+    for list in LISTS {
+        if let Some(value) = pick(list, &format!("/{}", list)) {
+            merged[list] = value;
+        }
+    }
+    for (list, fields) in ELEMENT_LISTS {
+        let elements = group_elements(list, fields, &values, &priorities.order(list, &ids))
+            .iter()
+            .enumerate()
+            .map(|(i, group)| merge_element(&format!("{}[{}]", list, i), list, group, priorities, &mut provenance))
+            .collect();
+        merged[list] = Value::Array(elements);
+    }
+
+    let mut data: SensorData = serde_json::from_value(merged).expect("merged value keeps the SensorData shape");
+    data.diagnostics.provenance = provenance;
+    data
+}
+
+/// The elements of `list` from each source, grouped by the device they
+/// describe. Groups are in the order their first element was seen, taking
+/// sources in `order`; a group holds at most one element per source.
+fn group_elements<'a>(
+    list: &str,
+    fields: &[&str],
+    values: &'a BTreeMap<&str, Value>,
+    order: &[&'a str],
+) -> Vec<Vec<(&'a str, &'a Value)>> {
+    let mut groups: Vec<Vec<(&str, &Value)>> = Vec::new();
+    for &id in order {
+        let Some(elements) = values[id].get(list).and_then(Value::as_array) else { continue };
+        for element in elements {
+            let keys = identity(element, fields);
+            let same = groups.iter_mut().find(|group| {
+                group.iter().all(|(other, _)| *other != id)
+                    && group
+                        .iter()
+                        .flat_map(|(_, e)| identity(e, fields))
+                        .any(|key| keys.iter().any(|k| same_device(k, &key)))
+            });
+            match same {
+                Some(group) => group.push((id, element)),
+                None => groups.push(vec![(id, element)]),
+            }
+        }
+    }
+    groups
+}
+
+/// One element from a group's elements, field by field. Provenance is
+/// recorded under `path`, e.g. "drives[0].tempC".
+fn merge_element(
+    path: &str,
+    list: &str,
+    group: &[(&str, &Value)],
+    priorities: &Priorities,
+    provenance: &mut BTreeMap<String, String>,
+) -> Value {
+    let ids: Vec<&str> = group.iter().map(|(id, _)| *id).collect();
+    let field = |id: &str, name: &str| group.iter().find(|(other, _)| *other == id).and_then(|(_, e)| e.get(name));
+
+    // Start from the first element, so fields no source has keep its shape
+    let mut merged = group[0].1.clone();
+    let names: Vec<String> = merged.as_object().map(|o| o.keys().cloned().collect()).unwrap_or_default();
+    for name in names {
+        let found = priorities
+            .order(&format!("{}.{}", list, name), &ids)
+            .into_iter()
+            .find_map(|id| field(id, &name).filter(|v| !is_missing(v)).map(|v| (id, v)));
+        if let Some((id, value)) = found {
+            merged[&name] = value.clone();
+            provenance.insert(format!("{}.{}", path, name), id.to_string());
+        }
+    }
+    merged
+}
+
+/// Values that identify an element across sources, compared without case or
+/// a `/dev/` prefix
+fn identity(element: &Value, fields: &[&str]) -> Vec<String> {
+    fields
+        .iter()
+        .filter_map(|field| element.get(field)?.as_str())
+        .map(|key| {
+            let key = key.trim().to_lowercase();
+            key.strip_prefix("/dev/").map(str::to_string).unwrap_or(key)
+        })
+        .filter(|key| !key.is_empty())
+        .collect()
+}
+
+fn same_device(a: &str, b: &str) -> bool {
+    a == b || is_partition(a, b) || is_partition(b, a)
+}
+
+/// `part` is a partition or namespace of disk `disk`: sda → sda1,
+/// nvme0 → nvme0n1p2, but not nvme1 → nvme10n1
+fn is_partition(disk: &str, part: &str) -> bool {
+    let Some(rest) = part.strip_prefix(disk) else { return false };
+    let numbered = disk.ends_with(|c: char| c.is_ascii_digit());
+    let mut chars = rest.chars();
+    match chars.next() {
+        Some('n') if numbered => chars.next().is_some_and(|c| c.is_ascii_digit()),
+        Some(c) if c.is_ascii_digit() => !numbered,
+        _ => false,
+    }
+}
@@ -0,0 +1,341 @@
+//! Prometheus node_exporter `/metrics` scrapes, for watching another machine.
+//!
+//! The text exposition format is one sample per line:
+//! `node_hwmon_temp_celsius{chip="pci0000:00_0000:00:18_3",sensor="temp1"} 45.25`,
+//! with `#` lines for HELP/TYPE comments. hwmon readings are keyed by chip and
+//! sensor; `node_hwmon_chip_names` and `node_hwmon_sensor_label` give the chip's
+//! driver name and the channel label, which is what the mapping goes by.
+//!
+//! CPU utilization comes from the `node_cpu_seconds_total` counters, so like
+//! `/proc/stat` it needs the previous scrape to turn into a percentage.
+
+use crate::hwinfo::types::*;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// node_exporter's default listen address
+pub const DEFAULT_URL: &str = "http://localhost:9100/metrics";
+
+/// One sample line
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    pub name: String,
+    pub labels: Vec<(String, String)>,
+    pub value: f64,
+}
+
+impl Sample {
+    pub fn label(&self, name: &str) -> Option<&str> {
+        self.labels.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+    }
+}
+
+/// Parse a whole scrape. Comments and blank lines are skipped; any other line
+/// that isn't a sample is an error, since it means this isn't a metrics page.
+pub fn parse(text: &str) -> Result<Vec<Sample>, String> {
+    text.lines()
+        .enumerate()
+        .map(|(n, line)| (n, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(n, line)| parse_line(line).ok_or_else(|| format!("Invalid metrics line {}: {}", n + 1, line)))
+        .collect()
+}
+
+fn parse_line(line: &str) -> Option<Sample> {
+    let name_end = line.find(|c: char| c == '{' || c.is_whitespace())?;
+    let (name, mut rest) = line.split_at(name_end);
+
+    let mut labels = Vec::new();
+    if let Some(after_brace) = rest.strip_prefix('{') {
+        let (parsed, after) = parse_labels(after_brace)?;
+        labels = parsed;
+        rest = after;
+    }
+
+    // An optional millisecond timestamp may follow the value
+    let value = rest.split_whitespace().next()?.parse().ok()?;
+    Some(Sample { name: name.to_string(), labels, value })
+}
+
+/// `key="value",...}` → labels and the text after the closing brace
+fn parse_labels(mut text: &str) -> Option<(Vec<(String, String)>, &str)> {
+    let mut labels = Vec::new();
+    loop {
+        text = text.trim_start_matches([',', ' ']);
+        if let Some(after) = text.strip_prefix('}') {
+            return Some((labels, after));
+        }
+
+        let (key, after_key) = text.split_once("=\"")?;
+        let mut value = String::new();
+        let mut chars = after_key.char_indices();
+        let end = loop {
+            match chars.next()? {
+                (i, '"') => break i,
+                (_, '\\') => match chars.next()?.1 {
+                    'n' => value.push('\n'),
+                    other => value.push(other),
+                },
+                (_, c) => value.push(c),
+            }
+        };
+        labels.push((key.trim().to_string(), value));
+        text = &after_key[end + 1..];
+    }
+}
+
+pub fn fetch(agent: &ureq::Agent, url: &str) -> Result<Vec<Sample>, String> {
+    let response = agent.get(url).call().map_err(|e| match e {
+        ureq::Error::Status(code, _) => format!("node_exporter returned HTTP {} for {}", code, url),
+        ureq::Error::Transport(e) => format!("Could not reach node_exporter at {}: {}", url, e),
+    })?;
+    let body = response
+        .into_string()
+        .map_err(|e| format!("Failed to read node_exporter response: {}", e))?;
+    parse(&body)
+}
+
+/// Agent with a timeout short enough for the UI's poll interval
+pub fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new().timeout(Duration::from_secs(2)).build()
+}
+
+/// Samples of one metric
+fn metric<'a>(samples: &'a [Sample], name: &'a str) -> impl Iterator<Item = &'a Sample> + 'a {
+    samples.iter().filter(move |s| s.name == name)
+}
+
+fn single(samples: &[Sample], name: &str) -> Option<f64> {
+    metric(samples, name).next().map(|s| s.value).filter(|v| v.is_finite())
+}
+
+/// Cumulative busy and total CPU seconds for one `cpu` label
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct CpuSeconds {
+    busy: f64,
+    total: f64,
+}
+
+impl CpuSeconds {
+    fn sum<'a>(cpus: impl Iterator<Item = &'a CpuSeconds>) -> CpuSeconds {
+        cpus.fold(CpuSeconds::default(), |acc, s| CpuSeconds {
+            busy: acc.busy + s.busy,
+            total: acc.total + s.total,
+        })
+    }
+
+    fn usage_since(&self, earlier: &CpuSeconds) -> Option<f64> {
+        let total = self.total - earlier.total;
+        let busy = self.busy - earlier.busy;
+        // Counters reset when the remote machine reboots
+        if total <= 0.0 || busy < 0.0 {
+            return None;
+        }
+        Some((busy / total * 100.0).min(100.0))
+    }
+}
+
+/// Per-CPU seconds, ordered by CPU number
+fn cpu_seconds(samples: &[Sample]) -> Vec<(u32, CpuSeconds)> {
+    let mut cpus: HashMap<u32, CpuSeconds> = HashMap::new();
+    for sample in metric(samples, "node_cpu_seconds_total") {
+        let (Some(cpu), Some(mode)) = (sample.label("cpu").and_then(|c| c.parse().ok()), sample.label("mode")) else {
+            continue;
+        };
+        let entry = cpus.entry(cpu).or_default();
+        entry.total += sample.value;
+        if mode != "idle" && mode != "iowait" {
+            entry.busy += sample.value;
+        }
+    }
+    let mut cpus: Vec<_> = cpus.into_iter().collect();
+    cpus.sort_by_key(|(cpu, _)| *cpu);
+    cpus
+}
+
+/// One hwmon reading with its chip's driver name and channel label resolved
+struct HwmonReading<'a> {
+    chip: &'a str,
+    chip_name: &'a str,
+    /// The label when the driver provides one, else the sensor, e.g. "temp1"
+    label: &'a str,
+    value: f64,
+}
+
+fn hwmon_readings<'a>(samples: &'a [Sample], name: &'a str) -> Vec<HwmonReading<'a>> {
+    let chip_names: HashMap<&str, &str> = metric(samples, "node_hwmon_chip_names")
+        .filter_map(|s| Some((s.label("chip")?, s.label("chip_name")?)))
+        .collect();
+    let labels: HashMap<(&str, &str), &str> = metric(samples, "node_hwmon_sensor_label")
+        .filter_map(|s| Some(((s.label("chip")?, s.label("sensor")?), s.label("label")?)))
+        .collect();
+
+    metric(samples, name)
+        .filter(|s| s.value.is_finite())
+        .filter_map(|s| {
+            let chip = s.label("chip")?;
+            let sensor = s.label("sensor")?;
+            Some(HwmonReading {
+                chip,
+                chip_name: chip_names.get(chip).copied().unwrap_or(chip),
+                label: labels.get(&(chip, sensor)).copied().unwrap_or(sensor),
+                value: s.value,
+            })
+        })
+        .collect()
+}
+
+/// Filesystems that aren't backed by a disk
+const VIRTUAL_FILESYSTEMS: &[&str] = &["tmpfs", "devtmpfs", "ramfs", "overlay", "squashfs", "nsfs", "fuse.lxcfs"];
+
+/// Map a scrape into `data`. `previous` is the scrape before it (empty on the
+/// first poll), used for CPU utilization.
+pub fn apply(samples: &[Sample], previous: &[Sample], data: &mut SensorData) {
+    data.system.name = metric(samples, "node_uname_info")
+        .find_map(|s| s.label("nodename"))
+        .map(str::to_string);
+
+    apply_cpu(samples, previous, &mut data.cpu);
+
+    let temps = hwmon_readings(samples, "node_hwmon_temp_celsius");
+    let fans = hwmon_readings(samples, "node_hwmon_fan_rpm");
+    let mut nvme_temps: Vec<(&str, f64)> = Vec::new();
+
+    for reading in &temps {
+        let cpu = &mut data.cpu;
+        match (reading.chip_name, reading.label) {
+            ("k10temp" | "zenpower", "Tdie") => cpu.package_temp_c = Some(reading.value),
+            ("k10temp" | "zenpower", "Tctl") => {
+                cpu.package_temp_c.get_or_insert(reading.value);
+            }
+            ("k10temp" | "zenpower", l) if l.starts_with("Tccd") => cpu.core_temps.push(reading.value),
+            ("coretemp", l) if l.starts_with("Package id") => {
+                cpu.package_temp_c.get_or_insert(reading.value);
+            }
+            ("coretemp", l) if l.starts_with("Core ") => cpu.core_temps.push(reading.value),
+            ("amdgpu", "junction") => data.gpu.hotspot_temp_c = Some(reading.value),
+            ("amdgpu", "edge") => data.gpu.core_temp_c = Some(reading.value),
+            ("amdgpu", "mem") => data.gpu.memory_junction_temp_c = Some(reading.value),
+            ("nvme", "Composite") => nvme_temps.push((reading.chip, reading.value)),
+            _ => data.raw_readings.push(RawReading {
+                group: reading.chip_name.to_string(),
+                label: reading.label.to_string(),
+                value: reading.value,
+                unit: "°C".to_string(),
+            }),
+        }
+    }
+
+    if let Some(gpu) = hwmon_readings(samples, "node_hwmon_power_average_watt")
+        .into_iter()
+        .find(|r| r.chip_name == "amdgpu")
+    {
+        data.gpu.power_w = Some(gpu.value);
+    }
+    for fan in fans.iter().filter(|f| f.value > 0.0) {
+        if fan.chip_name == "amdgpu" {
+            data.gpu.fan_speed_rpm = Some(fan.value);
+        } else {
+            data.system.fans.push(FanReading {
+                name: format!("{} {}", fan.chip_name, fan.label),
+                rpm: fan.value,
+            });
+        }
+    }
+    data.system.fan_status = SystemData::fan_status_for(&data.system.fans);
+
+    apply_filesystems(samples, &nvme_temps, &mut data.drives);
+    if let Some(&(chip, temp)) = nvme_temps.first() {
+        data.storage = StorageData {
+            name: Some(format!("NVMe ({})", nvme_device(chip))),
+            nvme_temp_c: Some(temp),
+            smart_health: "unknown".to_string(),
+        };
+    }
+
+    let available = single(samples, "node_memory_MemAvailable_bytes");
+    let total = single(samples, "node_memory_MemTotal_bytes");
+    data.system.memory_total_mb = total.map(|b| b / 1_048_576.0);
+    data.system.memory_used_mb = total.zip(available).map(|(t, a)| (t - a) / 1_048_576.0);
+
+    data.system.uptime_seconds = single(samples, "node_time_seconds")
+        .zip(single(samples, "node_boot_time_seconds"))
+        .map(|(now, boot)| (now - boot).max(0.0) as u64);
+
+    if let (Some(one), Some(five), Some(fifteen)) = (
+        single(samples, "node_load1"),
+        single(samples, "node_load5"),
+        single(samples, "node_load15"),
+    ) {
+        data.system.load_average = Some(LoadAverage { one, five, fifteen });
+    }
+}
+
+fn apply_cpu(samples: &[Sample], previous: &[Sample], cpu: &mut CpuData) {
+    // Only exported with --collector.cpu.info
+    cpu.name = metric(samples, "node_cpu_info")
+        .find_map(|s| s.label("model_name"))
+        .map(str::to_string);
+
+    let now = cpu_seconds(samples);
+    let before: HashMap<u32, CpuSeconds> = cpu_seconds(previous).into_iter().collect();
+    cpu.core_usage_percent = now
+        .iter()
+        .filter_map(|(n, seconds)| seconds.usage_since(before.get(n)?))
+        .collect();
+    if !before.is_empty() {
+        let total_now = CpuSeconds::sum(now.iter().map(|(_, s)| s));
+        cpu.usage_percent = total_now.usage_since(&CpuSeconds::sum(before.values()));
+    }
+
+    let mut clocks: Vec<(u32, f64)> = metric(samples, "node_cpu_scaling_frequency_hertz")
+        .filter_map(|s| Some((s.label("cpu")?.parse().ok()?, s.value / 1_000_000.0)))
+        .collect();
+    clocks.sort_by_key(|(cpu, _)| *cpu);
+    cpu.core_clocks_mhz = clocks.into_iter().map(|(_, mhz)| mhz).collect();
+    if !cpu.core_clocks_mhz.is_empty() {
+        cpu.core_clock_mhz = Some(cpu.core_clocks_mhz.iter().sum::<f64>() / cpu.core_clocks_mhz.len() as f64);
+    }
+    cpu.max_clock_mhz = metric(samples, "node_cpu_scaling_frequency_max_hertz")
+        .map(|s| s.value / 1_000_000.0)
+        .reduce(f64::max);
+}
+
+/// "nvme_nvme0" (node_exporter's sanitized hwmon device path) → "nvme0"
+fn nvme_device(chip: &str) -> &str {
+    chip.rsplit('_').next().unwrap_or(chip)
+}
+
+/// One drive per disk-backed filesystem, at its shortest mount point
+fn apply_filesystems(samples: &[Sample], nvme_temps: &[(&str, f64)], drives: &mut Vec<DriveData>) {
+    let avail: HashMap<(&str, &str), f64> = metric(samples, "node_filesystem_avail_bytes")
+        .filter_map(|s| Some(((s.label("device")?, s.label("mountpoint")?), s.value)))
+        .collect();
+
+    let mut filesystems: Vec<(&str, &str, f64)> = metric(samples, "node_filesystem_size_bytes")
+        .filter(|s| s.label("fstype").is_none_or(|t| !VIRTUAL_FILESYSTEMS.contains(&t)))
+        .filter_map(|s| Some((s.label("device")?, s.label("mountpoint")?, s.value)))
+        .filter(|(device, _, size)| device.starts_with("/dev/") && *size > 0.0)
+        .collect();
+    // Bind mounts repeat the device; keep the shortest mount point
+    filesystems.sort_by_key(|(device, mountpoint, _)| (*device, mountpoint.len()));
+    filesystems.dedup_by_key(|(device, _, _)| *device);
+    filesystems.sort_by_key(|(_, mountpoint, _)| *mountpoint);
+
+    for (device, mountpoint, size) in filesystems {
+        let disk = device.trim_start_matches("/dev/");
+        let temp_c = nvme_temps
+            .iter()
+            // nvme0 → nvme0n1p2, but not nvme10n1
+            .find(|(chip, _)| disk.strip_prefix(nvme_device(chip)).is_some_and(|rest| rest.starts_with('n')))
+            .map(|(_, temp)| *temp);
+        drives.push(DriveData {
+            name: Some(device.to_string()),
+            drive_letter: Some(mountpoint.to_string()),
+            temp_c,
+            total_gb: Some(size / 1_073_741_824.0),
+            free_gb: avail.get(&(device, mountpoint)).map(|b| b / 1_073_741_824.0),
+            ..DriveData::default()
+        });
+    }
+}
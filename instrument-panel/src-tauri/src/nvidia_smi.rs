@@ -0,0 +1,89 @@
+//! `nvidia-smi --query-gpu` CSV output.
+//!
+//! One line per GPU in the order of `QUERY_FIELDS`, without header or units.
+//! Fields a card does not support come back as `[N/A]` or `[Not Supported]`.
+
+use crate::hwinfo::types::GpuData;
+use crate::subprocess;
+use std::process::Command;
+use std::time::Duration;
+
+/// Command run when no path is configured; found on PATH on Linux and Windows
+pub const DEFAULT_COMMAND: &str = "nvidia-smi";
+
+/// nvidia-smi hangs when the driver is wedged; give up on it after this
+pub const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Columns requested from nvidia-smi, in output order
+pub const QUERY_FIELDS: &[&str] = &[
+    "index",
+    "name",
+    "temperature.gpu",
+    "power.draw",
+    "clocks.gr",
+    "clocks.mem",
+    "utilization.gpu",
+    "memory.used",
+    "memory.total",
+    "fan.speed",
+];
+
+/// Run `command --query-gpu=... --format=csv,noheader,nounits` and return its stdout
+pub fn query(command: &str) -> Result<String, String> {
+    let mut cmd = Command::new(command);
+    cmd.arg(format!("--query-gpu={}", QUERY_FIELDS.join(",")))
+        .arg("--format=csv,noheader,nounits");
+
+    // Polled every second; don't flash a console window each time
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = subprocess::output(&mut cmd, TIMEOUT).map_err(|e| format!("Failed to run {}: {}", command, e))?;
+    if !output.status.success() {
+        // nvidia-smi reports driver problems on stdout
+        let message = String::from_utf8_lossy(if output.stderr.is_empty() { &output.stdout } else { &output.stderr });
+        return Err(format!("{} failed: {}", command, message.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// One `GpuData` per line, in nvidia-smi's index order
+pub fn parse(csv: &str) -> Result<Vec<GpuData>, String> {
+    csv.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(parse_line)
+        .collect()
+}
+
+fn parse_line(line: &str) -> Result<GpuData, String> {
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    if fields.len() != QUERY_FIELDS.len() {
+        return Err(format!(
+            "Expected {} nvidia-smi columns, got {}: {}",
+            QUERY_FIELDS.len(),
+            fields.len(),
+            line
+        ));
+    }
+
+    let number = |i: usize| -> Option<f64> { fields[i].parse().ok() };
+    Ok(GpuData {
+        name: Some(fields[1].to_string()).filter(|n| !n.is_empty()),
+        // nvidia-smi has no hot spot sensor; temperature.gpu is the core
+        core_temp_c: number(2),
+        hotspot_temp_c: None,
+        memory_junction_temp_c: None,
+        power_w: number(3),
+        core_clock_mhz: number(4),
+        memory_clock_mhz: number(5),
+        usage_percent: number(6),
+        vram_used_mb: number(7),
+        vram_total_mb: number(8),
+        fan_speed_rpm: None,
+        fan_speed_percent: number(9),
+    })
+}
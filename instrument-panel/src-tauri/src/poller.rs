@@ -0,0 +1,290 @@
+//! Background polling of the selected source.
+//!
+//! One thread polls on its own schedule and keeps the latest reading, so
+//! `get_sensor_data` answers from the cache instead of polling per call, and
+//! each reading is pushed to every window as a `sensor-update` event, delta
+//! encoded (see delta.rs). The
+//! poller is handed the settings when they are saved rather than reading the
+//! settings file on every poll.
+//!
+//! The schedule follows who is watching: views subscribe while they show
+//! readings, optionally asking for a faster rate, and the window events and
+//! power state slow polling down while nobody is looking or on battery. With
+//! no subscribers, or only hidden windows, polling pauses. See `PollingPolicy`.
+
+use crate::commands::AppSettings;
+use crate::delta::{Encoder, Update};
+use crate::hwinfo::types::SensorData;
+use crate::power;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Event carrying a `delta::Update` for each new reading
+pub const UPDATE_EVENT: &str = "sensor-update";
+
+/// Polling intervals, from the `polling` setting
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PollingPolicy {
+    /// While a window has focus
+    pub focused_ms: u64,
+    /// While a window is visible but none has focus
+    pub unfocused_ms: u64,
+    /// While every window is minimized or hidden, if not paused then;
+    /// subscriber requests are ignored
+    pub hidden_ms: u64,
+    /// Fastest rate a subscriber may ask for
+    pub min_ms: u64,
+    /// Fastest rate on battery
+    pub battery_min_ms: u64,
+    /// Stop polling while nothing is subscribed or every window is hidden
+    pub pause_when_unused: bool,
+}
+
+impl Default for PollingPolicy {
+    fn default() -> Self {
+        Self {
+            focused_ms: 1000,
+            unfocused_ms: 2000,
+            hidden_ms: 5000,
+            min_ms: 250,
+            battery_min_ms: 2000,
+            pause_when_unused: true,
+        }
+    }
+}
+
+/// A window, as last reported by its window events
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WindowState {
+    /// Shown and not minimized
+    pub visible: bool,
+    pub focused: bool,
+}
+
+/// Who is watching, and on what power
+#[derive(Debug, Clone, Default)]
+pub struct Activity {
+    /// By window label. No windows reported yet counts as one focused window.
+    pub windows: BTreeMap<String, WindowState>,
+    /// Interval each subscriber asked for, `None` for no preference
+    pub subscriptions: BTreeMap<String, Option<Duration>>,
+    pub on_battery: bool,
+}
+
+impl PollingPolicy {
+    /// Time between polls, or `None` to pause
+    pub fn interval(&self, activity: &Activity) -> Option<Duration> {
+        let windows = &activity.windows;
+        // Subscribers in hidden windows have nobody to show readings to
+        let watched = windows.is_empty() || windows.values().any(|w| w.visible);
+        if self.pause_when_unused && (activity.subscriptions.is_empty() || !watched) {
+            return None;
+        }
+        let ms = if windows.is_empty() || windows.values().any(|w| w.visible && w.focused) {
+            self.requested_ms(activity).map_or(self.focused_ms, |ms| ms.min(self.focused_ms))
+        } else if windows.values().any(|w| w.visible) {
+            self.requested_ms(activity).map_or(self.unfocused_ms, |ms| ms.min(self.unfocused_ms))
+        } else {
+            self.hidden_ms
+        };
+        let floor = if activity.on_battery { self.battery_min_ms } else { 0 };
+        Some(Duration::from_millis(ms.max(floor).max(1)))
+    }
+
+    /// Fastest rate any subscriber asked for, no faster than `min_ms`
+    fn requested_ms(&self, activity: &Activity) -> Option<u64> {
+        activity
+            .subscriptions
+            .values()
+            .flatten()
+            .map(|d| (d.as_millis() as u64).max(self.min_ms))
+            .min()
+    }
+}
+
+struct State {
+    settings: AppSettings,
+    activity: Activity,
+    latest: Option<SensorData>,
+    encoder: Encoder,
+    /// Poll now instead of waiting out the interval
+    wake: bool,
+    stop: bool,
+}
+
+impl State {
+    fn interval(&self) -> Option<Duration> {
+        self.settings.polling.unwrap_or_default().interval(&self.activity)
+    }
+}
+
+struct Shared {
+    state: Mutex<State>,
+    changed: Condvar,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Apply `change`; the polling thread picks up the new interval, so a
+    /// faster rate can start the next poll early
+    fn update(&self, change: impl FnOnce(&mut State)) {
+        change(&mut self.lock());
+        self.changed.notify_all();
+    }
+
+    fn wake(&self, change: impl FnOnce(&mut State)) {
+        self.update(|state| {
+            change(state);
+            state.wake = true;
+        });
+    }
+}
+
+/// The polling thread and its latest reading, held in Tauri managed state.
+/// Dropping it stops the thread.
+pub struct Poller {
+    shared: Arc<Shared>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Poller {
+    /// Start polling with `poll` as `settings.polling` directs, passing the
+    /// update for each reading to `on_update` once it is cached
+    pub fn spawn<P, U>(settings: AppSettings, poll: P, on_update: U) -> Self
+    where
+        P: FnMut(&AppSettings) -> SensorData + Send + 'static,
+        U: FnMut(&Update) + Send + 'static,
+    {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                settings,
+                activity: Activity {
+                    on_battery: power::on_battery(),
+                    ..Activity::default()
+                },
+                latest: None,
+                encoder: Encoder::default(),
+                wake: false,
+                stop: false,
+            }),
+            changed: Condvar::new(),
+        });
+        let thread = {
+            let shared = shared.clone();
+            thread::spawn(move || run(&shared, poll, on_update))
+        };
+        Self {
+            shared,
+            thread: Some(thread),
+        }
+    }
+
+    /// The latest reading, or `None` until the first poll finishes
+    pub fn latest(&self) -> Option<SensorData> {
+        self.shared.lock().latest.clone()
+    }
+
+    /// The latest reading as a snapshot, for a client that missed an
+    /// update; `None` until the first poll finishes
+    pub fn resync(&self) -> Option<Update> {
+        self.shared.lock().encoder.snapshot()
+    }
+
+    pub fn settings(&self) -> AppSettings {
+        self.shared.lock().settings.clone()
+    }
+
+    /// Poll with `settings` from now on, starting right away
+    pub fn set_settings(&self, settings: AppSettings) {
+        self.shared.wake(|state| state.settings = settings);
+    }
+
+    /// Poll right away; the reading arrives as usual
+    pub fn refresh(&self) {
+        self.shared.wake(|_| {});
+    }
+
+    /// Current time between polls, `None` while paused
+    pub fn interval(&self) -> Option<Duration> {
+        self.shared.lock().interval()
+    }
+
+    pub fn set_window(&self, label: &str, window: WindowState) {
+        self.shared.update(|state| {
+            state.activity.windows.insert(label.to_string(), window);
+        });
+    }
+
+    pub fn close_window(&self, label: &str) {
+        self.shared.update(|state| {
+            state.activity.windows.remove(label);
+        });
+    }
+
+    /// Keep polling while `id` is subscribed, at `interval` or faster
+    pub fn subscribe(&self, id: &str, interval: Option<Duration>) {
+        self.shared.update(|state| {
+            state.activity.subscriptions.insert(id.to_string(), interval);
+        });
+    }
+
+    pub fn unsubscribe(&self, id: &str) {
+        self.shared.update(|state| {
+            state.activity.subscriptions.remove(id);
+        });
+    }
+}
+
+impl Drop for Poller {
+    fn drop(&mut self) {
+        self.shared.wake(|state| state.stop = true);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn run(shared: &Shared, mut poll: impl FnMut(&AppSettings) -> SensorData, mut on_update: impl FnMut(&Update)) {
+    let mut last_poll: Option<Instant> = None;
+    loop {
+        let mut state = shared.lock();
+        // Wait until the interval since the last poll has passed, as it
+        // stands now: subscriptions and window events change it meanwhile
+        while !state.stop && !state.wake {
+            let Some(interval) = state.interval() else {
+                state = shared.changed.wait(state).unwrap_or_else(|e| e.into_inner());
+                continue;
+            };
+            let next = last_poll.map_or_else(Instant::now, |t| t + interval);
+            let Some(remaining) = next.checked_duration_since(Instant::now()).filter(|d| !d.is_zero()) else {
+                break;
+            };
+            state = shared.changed.wait_timeout(state, remaining).unwrap_or_else(|e| e.into_inner()).0;
+        }
+        if state.stop {
+            return;
+        }
+        state.wake = false;
+        let settings = state.settings.clone();
+        drop(state);
+
+        last_poll = Some(Instant::now());
+        let data = poll(&settings);
+        let on_battery = power::on_battery();
+        let update = {
+            let mut state = shared.lock();
+            state.activity.on_battery = on_battery;
+            let update = state.encoder.push(&data);
+            state.latest = Some(data);
+            update
+        };
+        on_update(&update);
+    }
+}
@@ -0,0 +1,38 @@
+//! Whether the machine is running on battery, so the poller can slow down.
+
+/// `false` when it cannot be told, e.g. on desktops
+pub fn on_battery() -> bool {
+    platform::on_battery().unwrap_or(false)
+}
+
+#[cfg(windows)]
+mod platform {
+    use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    pub fn on_battery() -> Option<bool> {
+        let mut status = SYSTEM_POWER_STATUS::default();
+        unsafe { GetSystemPowerStatus(&mut status) }.ok()?;
+        // 0 offline, 1 online, 255 unknown
+        match status.ACLineStatus {
+            0 => Some(true),
+            1 => Some(false),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::path::Path;
+
+    pub fn on_battery() -> Option<bool> {
+        crate::linux::power_supply::on_battery(Path::new("/sys"))
+    }
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+mod platform {
+    pub fn on_battery() -> Option<bool> {
+        None
+    }
+}
@@ -0,0 +1,230 @@
+//! `smartctl --json` output from smartmontools.
+//!
+//! Devices come from `smartctl --scan-open --json`, then each one is read with
+//! `smartctl --json -a -d <type> <device>`. NVMe drives report a health log
+//! (`nvme_smart_health_information_log`); ATA drives report the vendor
+//! attribute table (`ata_smart_attributes.table`), whose meaning varies by
+//! vendor, so only the widely shared ids are used.
+
+use crate::hwinfo::types::DriveData;
+use crate::subprocess;
+use serde::Deserialize;
+use std::process::{Command, Output};
+use std::time::Duration;
+
+/// Command run when none is configured. Reading SMART data usually needs
+/// root, so e.g. `sudo -n smartctl` can be configured instead.
+pub const DEFAULT_COMMAND: &str = "smartctl";
+
+/// A sleeping or failing disk can keep smartctl waiting for many seconds;
+/// each run is killed after this
+pub const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// smartctl's exit status is a bit mask; bits 0 and 1 mean the command line
+/// or the device could not be used. The others describe the drive's health
+/// and come with a full JSON document.
+const FATAL_EXIT_BITS: i32 = 0b11;
+
+/// A device listed by `--scan-open`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Device {
+    /// e.g. "/dev/nvme0" or "/dev/sda"
+    pub name: String,
+    /// `-d` argument, e.g. "nvme" or "sat"
+    #[serde(rename = "type")]
+    pub device_type: String,
+}
+
+#[derive(Deserialize)]
+struct Scan {
+    #[serde(default)]
+    devices: Vec<Device>,
+}
+
+#[derive(Deserialize)]
+struct Report {
+    #[serde(default)]
+    device: Option<ReportDevice>,
+    #[serde(default)]
+    model_name: Option<String>,
+    #[serde(default)]
+    user_capacity: Option<Capacity>,
+    #[serde(default)]
+    nvme_total_capacity: Option<u64>,
+    #[serde(default)]
+    smart_status: Option<SmartStatus>,
+    #[serde(default)]
+    temperature: Option<Temperature>,
+    #[serde(default)]
+    nvme_smart_health_information_log: Option<NvmeHealth>,
+    #[serde(default)]
+    ata_smart_attributes: Option<AtaAttributes>,
+}
+
+#[derive(Deserialize)]
+struct ReportDevice {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Capacity {
+    bytes: u64,
+}
+
+#[derive(Deserialize)]
+struct SmartStatus {
+    passed: bool,
+}
+
+#[derive(Deserialize)]
+struct Temperature {
+    current: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct NvmeHealth {
+    #[serde(default)]
+    critical_warning: u64,
+    percentage_used: Option<f64>,
+    media_errors: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct AtaAttributes {
+    #[serde(default)]
+    table: Vec<AtaAttribute>,
+}
+
+#[derive(Deserialize)]
+struct AtaAttribute {
+    id: u32,
+    /// Normalized value, usually 100 (or 200/253) when healthy
+    value: u64,
+    raw: AtaRaw,
+}
+
+#[derive(Deserialize)]
+struct AtaRaw {
+    value: u64,
+}
+
+/// ATA ids whose normalized value is the remaining rated life in percent:
+/// SSD_Life_Left, Media_Wearout_Indicator and Samsung's Wear_Leveling_Count
+const ATA_REMAINING_LIFE: &[u32] = &[231, 233, 177];
+const ATA_REALLOCATED_SECTORS: u32 = 5;
+/// Reported_Uncorrect and Offline_Uncorrectable
+const ATA_UNCORRECTABLE: &[u32] = &[187, 198];
+
+/// Split `command` on whitespace (no shell), append `args` and run it
+fn run(command: &str, args: &[&str]) -> Result<Output, String> {
+    let mut parts = command.split_whitespace();
+    let program = parts.next().ok_or("smartctl command is empty")?;
+    let mut cmd = Command::new(program);
+    cmd.args(parts).args(args);
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    subprocess::output(&mut cmd, TIMEOUT).map_err(|e| format!("Failed to run {}: {}", program, e))
+}
+
+/// stdout of a smartctl run, unless the exit status says nothing was read
+fn json_output(command: &str, output: Output) -> Result<String, String> {
+    let code = output.status.code().unwrap_or(FATAL_EXIT_BITS);
+    if code & FATAL_EXIT_BITS != 0 {
+        // The reason is in the JSON's smartctl.messages, but stdout is readable enough
+        let message = String::from_utf8_lossy(if output.stderr.is_empty() { &output.stdout } else { &output.stderr });
+        return Err(format!("{} failed (exit {}): {}", command, code, message.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Devices smartctl can open, via `--scan-open --json`
+pub fn scan(command: &str) -> Result<Vec<Device>, String> {
+    let output = run(command, &["--scan-open", "--json"])?;
+    parse_scan(&json_output(command, output)?)
+}
+
+pub fn parse_scan(json: &str) -> Result<Vec<Device>, String> {
+    let scan: Scan =
+        serde_json::from_str(json).map_err(|e| format!("Invalid smartctl --scan-open output: {}", e))?;
+    Ok(scan.devices)
+}
+
+/// Full SMART report for one device, via `--json -a`
+pub fn read(command: &str, device: &Device) -> Result<String, String> {
+    let output = run(command, &["--json", "-a", "-d", &device.device_type, &device.name])?;
+    json_output(command, output)
+}
+
+/// One `--json -a` report as a drive
+pub fn parse(json: &str) -> Result<DriveData, String> {
+    let report: Report =
+        serde_json::from_str(json).map_err(|e| format!("Invalid smartctl output: {}", e))?;
+
+    let mut drive = DriveData {
+        name: report.model_name.map(|n| n.trim().to_string()),
+        drive_letter: report.device.map(|d| d.name),
+        temp_c: report.temperature.and_then(|t| t.current),
+        total_gb: report
+            .user_capacity
+            .map(|c| c.bytes)
+            .or(report.nvme_total_capacity)
+            .filter(|&bytes| bytes > 0)
+            .map(|bytes| bytes as f64 / 1_000_000_000.0),
+        smart_passed: report.smart_status.map(|s| s.passed),
+        ..DriveData::default()
+    };
+
+    let mut critical_warning = false;
+    if let Some(log) = report.nvme_smart_health_information_log {
+        drive.wear_percent = log.percentage_used;
+        drive.media_errors = log.media_errors;
+        critical_warning = log.critical_warning != 0;
+    }
+
+    if let Some(attributes) = report.ata_smart_attributes {
+        let table = &attributes.table;
+        drive.wear_percent = ATA_REMAINING_LIFE
+            .iter()
+            .find_map(|id| table.iter().find(|a| a.id == *id))
+            .map(|a| 100.0 - a.value.min(100) as f64);
+        drive.reallocated_sectors =
+            table.iter().find(|a| a.id == ATA_REALLOCATED_SECTORS).map(|a| a.raw.value);
+        let uncorrectable: Vec<u64> = table
+            .iter()
+            .filter(|a| ATA_UNCORRECTABLE.contains(&a.id))
+            .map(|a| a.raw.value)
+            .collect();
+        if !uncorrectable.is_empty() {
+            drive.media_errors = uncorrectable.into_iter().max();
+        }
+    }
+
+    drive.smart_health = health(&drive, critical_warning);
+    Ok(drive)
+}
+
+/// "good", "warning", "critical" or "unknown", as for the other sources
+fn health(drive: &DriveData, critical_warning: bool) -> String {
+    if drive.smart_passed == Some(false) {
+        return "critical".to_string();
+    }
+
+    let mut health = match drive.wear_percent {
+        Some(wear) => DriveData::health_for_remaining_life(100.0 - wear),
+        None if drive.smart_passed == Some(true) => "good".to_string(),
+        None => "unknown".to_string(),
+    };
+    let has_errors = drive.media_errors.is_some_and(|n| n > 0)
+        || drive.reallocated_sectors.is_some_and(|n| n > 0)
+        || critical_warning;
+    if has_errors && health != "critical" {
+        health = "warning".to_string();
+    }
+    health
+}
@@ -0,0 +1,75 @@
+use super::{SensorSource, SourceCapabilities, SourceDescriptor, SourceRegistry};
+use crate::aida64::{self, shared_memory};
+use crate::commands::AppSettings;
+use crate::hwinfo::types::{Diagnostics, SensorData};
+use sysinfo::{CpuRefreshKind, RefreshKind, System};
+
+pub const DESCRIPTOR: SourceDescriptor = SourceDescriptor {
+    id: "aida64",
+    label: "AIDA64 shared memory",
+    create,
+    settings_key: super::no_settings,
+    fallback: Some(super::system_info::DESCRIPTOR.id),
+};
+
+fn create(_settings: &AppSettings, _registry: &SourceRegistry) -> Box<dyn SensorSource> {
+    Box::new(Aida64Source::default())
+}
+
+/// Reads AIDA64's sensor values text. AIDA64 does not publish hardware names,
+/// so the CPU name comes from the OS.
+#[derive(Default)]
+pub struct Aida64Source {
+    cpu_name: Option<String>,
+    reading_count: usize,
+}
+
+impl SensorSource for Aida64Source {
+    fn id(&self) -> &'static str {
+        DESCRIPTOR.id
+    }
+
+    fn connect(&mut self) -> Result<(), String> {
+        let system = System::new_with_specifics(RefreshKind::new().with_cpu(CpuRefreshKind::new()));
+        self.cpu_name = system
+            .cpus()
+            .first()
+            .map(|c| c.brand().trim().to_string())
+            .filter(|b| !b.is_empty());
+        Ok(())
+    }
+
+    fn poll(&mut self) -> Result<SensorData, String> {
+        let readings = aida64::parse(&shared_memory::read()?);
+        if readings.is_empty() {
+            return Err("AIDA64 shared memory is empty. Select the values to publish under External Applications.".to_string());
+        }
+        self.reading_count = readings.len();
+
+        let mut data = SensorData::connected(self.diagnostics());
+        aida64::apply(&readings, &mut data);
+        data.cpu.name = self.cpu_name.clone();
+        data.system.name = System::host_name();
+        Ok(data)
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            cpu: true,
+            gpu: true,
+            storage: true,
+            fans: true,
+            uptime: true,
+        }
+    }
+
+    fn diagnostics(&self) -> Diagnostics {
+        Diagnostics {
+            hwinfo_process_detected: false,
+            shared_memory_detected: self.reading_count > 0,
+            message: Some(format!("{} AIDA64 readings", self.reading_count)),
+            source: Some(DESCRIPTOR.id.to_string()),
+            provenance: Default::default(),
+        }
+    }
+}
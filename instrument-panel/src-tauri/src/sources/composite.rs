@@ -0,0 +1,133 @@
+use super::{system_info, Connection, SensorSource, SourceCapabilities, SourceDescriptor, SourceRegistry, DEFAULT_SOURCE};
+use crate::commands::AppSettings;
+use crate::hwinfo::types::{Diagnostics, SensorData};
+use crate::merge::{self, Priorities};
+
+pub const DESCRIPTOR: SourceDescriptor = SourceDescriptor {
+    id: "composite",
+    label: "Combined sources",
+    create,
+    settings_key,
+    fallback: None,
+};
+
+/// Members when `composite_sources` is not set
+const DEFAULT_MEMBERS: &[&str] = &[DEFAULT_SOURCE, system_info::DESCRIPTOR.id];
+
+/// Configured member ids, in priority order
+fn member_ids(settings: &AppSettings) -> Vec<String> {
+    match &settings.composite_sources {
+        Some(ids) => ids.clone(),
+        None => DEFAULT_MEMBERS.iter().map(|id| id.to_string()).collect(),
+    }
+}
+
+/// Members come from `registry`, the one the composite itself is created
+/// from, so sources registered at runtime can be combined too
+fn create(settings: &AppSettings, registry: &SourceRegistry) -> Box<dyn SensorSource> {
+    let ids = member_ids(settings);
+
+    let mut members = Vec::new();
+    let mut unknown = Vec::new();
+    // A composite can't contain itself
+    for id in ids.iter().filter(|id| *id != DESCRIPTOR.id) {
+        match registry.create(id, settings) {
+            Ok(source) => members.push(Connection::new(source)),
+            Err(_) => unknown.push(id.clone()),
+        }
+    }
+
+    Box::new(CompositeSource {
+        members,
+        unknown,
+        priorities: Priorities::new(ids, settings.composite_priorities.clone().unwrap_or_default()),
+        errors: Vec::new(),
+    })
+}
+
+/// The composite's own settings and those of each member
+fn settings_key(settings: &AppSettings, registry: &SourceRegistry) -> String {
+    let members: Vec<String> = member_ids(settings)
+        .iter()
+        .filter(|id| *id != DESCRIPTOR.id)
+        .filter_map(|id| registry.get(id))
+        .map(|d| (d.settings_key)(settings, registry))
+        .collect();
+    format!("{:?}", (&settings.composite_sources, &settings.composite_priorities, members))
+}
+
+/// Polls several sources and merges their readings field by field. Members
+/// that fail are skipped for that poll; the source only fails when none of
+/// them can be read.
+pub struct CompositeSource {
+    members: Vec<Connection>,
+    /// Configured ids the registry doesn't know
+    unknown: Vec<String>,
+    priorities: Priorities,
+    /// "id: message" for each member that failed the last poll
+    errors: Vec<String>,
+}
+
+impl SensorSource for CompositeSource {
+    fn id(&self) -> &'static str {
+        DESCRIPTOR.id
+    }
+
+    fn connect(&mut self) -> Result<(), String> {
+        if !self.unknown.is_empty() {
+            return Err(format!("Unknown data source: {}", self.unknown.join(", ")));
+        }
+        if self.members.is_empty() {
+            return Err("No sources configured to combine".to_string());
+        }
+        // Members connect on their first poll
+        Ok(())
+    }
+
+    fn poll(&mut self) -> Result<SensorData, String> {
+        let mut readings = Vec::new();
+        self.errors.clear();
+        for member in &mut self.members {
+            match member.poll() {
+                Ok(data) => readings.push((member.source.id(), data)),
+                Err(e) => self.errors.push(format!("{}: {}", member.source.id(), e)),
+            }
+        }
+        if readings.is_empty() {
+            return Err(format!("No source could be read. {}", self.errors.join("; ")));
+        }
+
+        let mut diagnostics = self.diagnostics();
+        let ids: Vec<&str> = readings.iter().map(|(id, _)| *id).collect();
+        diagnostics.message = Some(if self.errors.is_empty() {
+            format!("Combined {}", ids.join(", "))
+        } else {
+            format!("Combined {} (skipped {})", ids.join(", "), self.errors.join("; "))
+        });
+        Ok(merge::merge(&readings, &self.priorities, diagnostics))
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        self.members
+            .iter()
+            .map(|m| m.source.capabilities())
+            .fold(SourceCapabilities::default(), |all, c| SourceCapabilities {
+                cpu: all.cpu || c.cpu,
+                gpu: all.gpu || c.gpu,
+                storage: all.storage || c.storage,
+                fans: all.fans || c.fans,
+                uptime: all.uptime || c.uptime,
+            })
+    }
+
+    fn diagnostics(&self) -> Diagnostics {
+        let members: Vec<Diagnostics> = self.members.iter().map(|m| m.source.diagnostics()).collect();
+        Diagnostics {
+            hwinfo_process_detected: members.iter().any(|d| d.hwinfo_process_detected),
+            shared_memory_detected: members.iter().any(|d| d.shared_memory_detected),
+            message: Some(self.errors.join("; ")).filter(|m| !m.is_empty()),
+            source: Some(DESCRIPTOR.id.to_string()),
+            provenance: Default::default(),
+        }
+    }
+}
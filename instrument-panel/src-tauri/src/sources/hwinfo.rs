@@ -0,0 +1,70 @@
+use super::{SensorSource, SourceCapabilities, SourceDescriptor, SourceRegistry};
+use crate::commands::AppSettings;
+use crate::hwinfo::types::{Diagnostics, SensorData};
+use crate::hwinfo::{process, shared_memory};
+
+pub const DESCRIPTOR: SourceDescriptor = SourceDescriptor {
+    id: "hwinfo",
+    label: "HWiNFO shared memory",
+    create,
+    settings_key: super::no_settings,
+    fallback: Some(super::system_info::DESCRIPTOR.id),
+};
+
+fn create(_settings: &AppSettings, _registry: &SourceRegistry) -> Box<dyn SensorSource> {
+    Box::new(HwinfoSource::default())
+}
+
+/// Reads HWiNFO's shared memory region. The region is opened on every poll,
+/// so there is no handle to hold between calls.
+#[derive(Default)]
+pub struct HwinfoSource {
+    process_detected: bool,
+    shared_memory_detected: bool,
+}
+
+impl SensorSource for HwinfoSource {
+    fn id(&self) -> &'static str {
+        DESCRIPTOR.id
+    }
+
+    fn connect(&mut self) -> Result<(), String> {
+        self.process_detected = process::is_running();
+        Ok(())
+    }
+
+    fn poll(&mut self) -> Result<SensorData, String> {
+        match shared_memory::read() {
+            Ok(data) => {
+                self.process_detected = true;
+                self.shared_memory_detected = true;
+                Ok(data)
+            }
+            Err(msg) => {
+                self.process_detected = process::is_running();
+                self.shared_memory_detected = false;
+                Err(msg)
+            }
+        }
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            cpu: true,
+            gpu: true,
+            storage: true,
+            fans: true,
+            uptime: true,
+        }
+    }
+
+    fn diagnostics(&self) -> Diagnostics {
+        Diagnostics {
+            hwinfo_process_detected: self.process_detected,
+            shared_memory_detected: self.shared_memory_detected,
+            message: None,
+            source: Some(DESCRIPTOR.id.to_string()),
+            provenance: Default::default(),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use super::{SensorSource, SourceCapabilities, SourceDescriptor, SourceRegistry};
+use crate::commands::AppSettings;
+use crate::hwinfo::types::{Diagnostics, SensorData};
+use crate::lhm;
+
+pub const DESCRIPTOR: SourceDescriptor = SourceDescriptor {
+    id: "lhm",
+    label: "LibreHardwareMonitor web server",
+    create,
+    settings_key,
+    fallback: Some(super::system_info::DESCRIPTOR.id),
+};
+
+fn create(settings: &AppSettings, _registry: &SourceRegistry) -> Box<dyn SensorSource> {
+    let url = settings.lhm_url.as_deref().unwrap_or(lhm::DEFAULT_URL);
+    Box::new(LhmSource::new(url.to_string()))
+}
+
+fn settings_key(settings: &AppSettings, _registry: &SourceRegistry) -> String {
+    format!("{:?}", settings.lhm_url)
+}
+
+/// Polls LibreHardwareMonitor's `data.json` over HTTP
+pub struct LhmSource {
+    url: String,
+    agent: ureq::Agent,
+    hardware_count: usize,
+}
+
+impl LhmSource {
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+            agent: lhm::agent(),
+            hardware_count: 0,
+        }
+    }
+}
+
+impl SensorSource for LhmSource {
+    fn id(&self) -> &'static str {
+        DESCRIPTOR.id
+    }
+
+    fn connect(&mut self) -> Result<(), String> {
+        // Only plain HTTP is built in; the web server has no TLS option anyway
+        if !self.url.starts_with("http://") {
+            return Err(format!("Unsupported LibreHardwareMonitor URL: {}", self.url));
+        }
+        Ok(())
+    }
+
+    fn poll(&mut self) -> Result<SensorData, String> {
+        let root = lhm::fetch(&self.agent, &self.url)?;
+        self.hardware_count = lhm::hardware(&root).len();
+
+        let mut data = SensorData::connected(self.diagnostics());
+        lhm::apply(&root, &mut data);
+        Ok(data)
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            cpu: true,
+            gpu: true,
+            storage: true,
+            fans: true,
+            uptime: false,
+        }
+    }
+
+    fn diagnostics(&self) -> Diagnostics {
+        Diagnostics {
+            hwinfo_process_detected: false,
+            shared_memory_detected: false,
+            message: Some(format!("{} hardware nodes from {}", self.hardware_count, self.url)),
+            source: Some(DESCRIPTOR.id.to_string()),
+            provenance: Default::default(),
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use super::{SensorSource, SourceCapabilities, SourceDescriptor, SourceRegistry};
+use crate::commands::AppSettings;
+use crate::hwinfo::types::{Diagnostics, SensorData};
+use crate::linux::{cpufreq, drm, hwmon, thermal};
+use crate::linux::procfs::{self, ProcReader};
+use crate::linux::rapl::{self, RaplReader};
+use std::path::PathBuf;
+
+pub const DESCRIPTOR: SourceDescriptor = SourceDescriptor {
+    id: "linux",
+    label: "Linux sysfs/procfs",
+    create,
+    settings_key,
+    fallback: None,
+};
+
+fn create(settings: &AppSettings, _registry: &SourceRegistry) -> Box<dyn SensorSource> {
+    let sysfs_root = settings.sysfs_root.as_deref().unwrap_or("/sys");
+    let procfs_root = settings.procfs_root.as_deref().unwrap_or("/proc");
+    Box::new(LinuxSource::new(PathBuf::from(sysfs_root), PathBuf::from(procfs_root)))
+}
+
+fn settings_key(settings: &AppSettings, _registry: &SourceRegistry) -> String {
+    format!("{:?}", (&settings.sysfs_root, &settings.procfs_root))
+}
+
+/// Native Linux readings from hwmon, amdgpu DRM, RAPL, cpufreq, thermal zones and procfs
+pub struct LinuxSource {
+    sysfs_root: PathBuf,
+    proc: ProcReader,
+    rapl: RaplReader,
+    chip_count: usize,
+    rapl_domain_count: usize,
+}
+
+impl LinuxSource {
+    pub fn new(sysfs_root: PathBuf, procfs_root: PathBuf) -> Self {
+        Self {
+            rapl: RaplReader::new(sysfs_root.clone()),
+            sysfs_root,
+            proc: ProcReader::new(procfs_root),
+            chip_count: 0,
+            rapl_domain_count: 0,
+        }
+    }
+}
+
+impl SensorSource for LinuxSource {
+    fn id(&self) -> &'static str {
+        DESCRIPTOR.id
+    }
+
+    fn connect(&mut self) -> Result<(), String> {
+        let hwmon_dir = self.sysfs_root.join("class/hwmon");
+        if !hwmon_dir.is_dir() {
+            return Err(format!("{} not found", hwmon_dir.display()));
+        }
+        // Prime the /proc/stat and RAPL baselines so the first poll already has rates
+        self.proc.read()?;
+        self.rapl.read();
+        Ok(())
+    }
+
+    fn poll(&mut self) -> Result<SensorData, String> {
+        let chips = hwmon::read_chips(&self.sysfs_root)?;
+        self.chip_count = chips.len();
+        let cards = drm::read_cards(&self.sysfs_root);
+        let proc_snapshot = self.proc.read()?;
+        let powers = self.rapl.read();
+        self.rapl_domain_count = powers.len();
+
+        let mut data = SensorData::connected(self.diagnostics());
+        // DRM first so the card it picks also supplies the hwmon GPU readings
+        drm::apply(&cards, &mut data.gpu);
+        hwmon::apply(&chips, &mut data);
+        procfs::apply(&proc_snapshot, &mut data);
+        rapl::apply(&powers, &mut data.cpu);
+        cpufreq::apply(&cpufreq::read_frequencies(&self.sysfs_root), &mut data.cpu);
+        // Thermal zones only matter when hwmon had no CPU chip
+        thermal::apply(&thermal::read_zones(&self.sysfs_root), &mut data.cpu);
+        data.system.name = sysinfo::System::host_name();
+        Ok(data)
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            cpu: true,
+            gpu: true,
+            storage: true,
+            fans: true,
+            uptime: true,
+        }
+    }
+
+    fn diagnostics(&self) -> Diagnostics {
+        Diagnostics {
+            hwinfo_process_detected: false,
+            shared_memory_detected: false,
+            message: Some(format!(
+                "{} hwmon chips, {} RAPL domains",
+                self.chip_count, self.rapl_domain_count
+            )),
+            source: Some(DESCRIPTOR.id.to_string()),
+            provenance: Default::default(),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use super::{SensorSource, SourceCapabilities, SourceDescriptor, SourceRegistry};
+use crate::commands::AppSettings;
+use crate::hwinfo::types::{Diagnostics, SensorData};
+use crate::linux::{hwmon, lm_sensors};
+use std::fs;
+use std::path::PathBuf;
+
+pub const DESCRIPTOR: SourceDescriptor = SourceDescriptor {
+    id: "lm-sensors",
+    label: "lm-sensors (sensors -j)",
+    create,
+    settings_key,
+    fallback: None,
+};
+
+fn create(settings: &AppSettings, _registry: &SourceRegistry) -> Box<dyn SensorSource> {
+    let input = match &settings.lm_sensors_file {
+        Some(path) => Input::File(PathBuf::from(path)),
+        None => Input::Command(
+            settings
+                .lm_sensors_command
+                .clone()
+                .unwrap_or_else(|| lm_sensors::DEFAULT_COMMAND.to_string()),
+        ),
+    };
+    Box::new(LmSensorsSource { input, chip_count: 0 })
+}
+
+fn settings_key(settings: &AppSettings, _registry: &SourceRegistry) -> String {
+    format!("{:?}", (&settings.lm_sensors_file, &settings.lm_sensors_command))
+}
+
+/// Where the `sensors -j` JSON comes from
+enum Input {
+    Command(String),
+    /// Written by something else, e.g. a cron job or a remote host
+    File(PathBuf),
+}
+
+/// Maps `sensors -j` output through the hwmon chip mapping
+pub struct LmSensorsSource {
+    input: Input,
+    chip_count: usize,
+}
+
+impl LmSensorsSource {
+    fn read(&self) -> Result<String, String> {
+        match &self.input {
+            Input::Command(command) => lm_sensors::run(command),
+            Input::File(path) => fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+}
+
+impl SensorSource for LmSensorsSource {
+    fn id(&self) -> &'static str {
+        DESCRIPTOR.id
+    }
+
+    fn connect(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn poll(&mut self) -> Result<SensorData, String> {
+        let chips = lm_sensors::parse(&self.read()?)?;
+        self.chip_count = chips.len();
+
+        let mut data = SensorData::connected(self.diagnostics());
+        hwmon::apply(&chips, &mut data);
+        data.raw_readings = lm_sensors::raw_readings(&chips);
+        data.system.name = sysinfo::System::host_name();
+        Ok(data)
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            cpu: true,
+            gpu: true,
+            storage: true,
+            fans: true,
+            uptime: false,
+        }
+    }
+
+    fn diagnostics(&self) -> Diagnostics {
+        let from = match &self.input {
+            Input::Command(command) => command.clone(),
+            Input::File(path) => path.display().to_string(),
+        };
+        Diagnostics {
+            hwinfo_process_detected: false,
+            shared_memory_detected: false,
+            message: Some(format!("{} chips from {}", self.chip_count, from)),
+            source: Some(DESCRIPTOR.id.to_string()),
+            provenance: Default::default(),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use super::{SensorSource, SourceCapabilities, SourceDescriptor, SourceRegistry};
+use crate::commands::AppSettings;
+use crate::hwinfo::image;
+use crate::hwinfo::mock::{MockGenerator, SystemClock};
+use crate::hwinfo::scenario;
+use crate::hwinfo::types::{Diagnostics, SensorData};
+
+pub const DESCRIPTOR: SourceDescriptor = SourceDescriptor {
+    id: "mock",
+    label: "Mock data",
+    create,
+    settings_key,
+    fallback: None,
+};
+
+fn create(settings: &AppSettings, _registry: &SourceRegistry) -> Box<dyn SensorSource> {
+    let generator = match settings.mock_scenario.as_deref().map(scenario::load) {
+        None => Ok(MockGenerator::new(Box::new(SystemClock), 0)),
+        Some(scenario) => scenario.map(|s| MockGenerator::with_scenario(s, Box::new(SystemClock))),
+    };
+    Box::new(MockSource {
+        generator,
+        through_decoder: settings.mock_shared_memory.unwrap_or(false),
+    })
+}
+
+fn settings_key(settings: &AppSettings, _registry: &SourceRegistry) -> String {
+    format!("{:?}", (&settings.mock_scenario, settings.mock_shared_memory))
+}
+
+/// Fake rig for development without HWiNFO, optionally following a scenario
+pub struct MockSource {
+    /// Or why the `mock_scenario` setting could not be loaded
+    generator: Result<MockGenerator, String>,
+    /// Pass each reading through a HWiNFO shared-memory image and the real decoder
+    through_decoder: bool,
+}
+
+impl MockSource {
+    /// Mock source reading from `generator`. The registered source runs on
+    /// the system clock; registering one whose generator has a
+    /// `SteppedClock` and a fixed seed in its place gives the same stream
+    /// through `ActiveSource` and the poller on every run.
+    pub fn new(generator: MockGenerator, through_decoder: bool) -> Self {
+        Self {
+            generator: Ok(generator),
+            through_decoder,
+        }
+    }
+}
+
+impl SensorSource for MockSource {
+    fn id(&self) -> &'static str {
+        DESCRIPTOR.id
+    }
+
+    fn connect(&mut self) -> Result<(), String> {
+        self.generator.as_ref().map(|_| ()).map_err(String::clone)
+    }
+
+    fn poll(&mut self) -> Result<SensorData, String> {
+        let data = self.generator.as_mut().map_err(|e| e.clone())?.next_reading()?;
+        if self.through_decoder {
+            image::round_trip(&data)
+        } else {
+            Ok(data)
+        }
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            cpu: true,
+            gpu: true,
+            storage: true,
+            fans: true,
+            uptime: true,
+        }
+    }
+
+    fn diagnostics(&self) -> Diagnostics {
+        // Mirror HWiNFO's detection flags, so a scripted crash reads like the real thing
+        let connected = self.generator.as_ref().is_ok_and(MockGenerator::is_connected);
+        let message = match self.generator.as_ref().ok().and_then(MockGenerator::scenario) {
+            Some(scenario) => format!("Mock scenario: {}", scenario.name),
+            None => "Mock mode active".to_string(),
+        };
+        Diagnostics {
+            hwinfo_process_detected: connected,
+            shared_memory_detected: connected,
+            message: Some(message),
+            source: Some(DESCRIPTOR.id.to_string()),
+            provenance: Default::default(),
+        }
+    }
+}
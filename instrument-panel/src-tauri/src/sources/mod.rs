@@ -0,0 +1,297 @@
+pub mod aida64;
+pub mod composite;
+pub mod hwinfo;
+pub mod lhm;
+pub mod mock;
+pub mod node_exporter;
+pub mod nvidia_smi;
+pub mod replay;
+pub mod smartctl;
+pub mod system_info;
+#[cfg(target_os = "linux")]
+pub mod linux;
+#[cfg(target_os = "linux")]
+pub mod lm_sensors;
+
+use crate::collector::{CollectorSet, CollectorStatus};
+use crate::commands::AppSettings;
+use crate::hwinfo::types::{Diagnostics, SensorData};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// Source used when the `data_source` setting is not set
+pub const DEFAULT_SOURCE: &str = "hwinfo";
+
+/// Which parts of `SensorData` a source is able to fill
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceCapabilities {
+    pub cpu: bool,
+    pub gpu: bool,
+    pub storage: bool,
+    pub fans: bool,
+    pub uptime: bool,
+}
+
+/// A back end that produces `SensorData`
+///
+/// Creating a source should be cheap; anything that touches the system
+/// (opening handles, spawning processes, first samples) belongs in `connect`.
+pub trait SensorSource: Send {
+    /// Stable identifier, matched against the `data_source` setting
+    fn id(&self) -> &'static str;
+
+    /// Prepare the source for polling. Called before the first poll and
+    /// again after any poll fails.
+    fn connect(&mut self) -> Result<(), String>;
+
+    /// Take a reading
+    fn poll(&mut self) -> Result<SensorData, String>;
+
+    /// Which parts of `SensorData` this source can fill
+    fn capabilities(&self) -> SourceCapabilities;
+
+    /// Connection state, reported to the frontend when `connect` or `poll` fails
+    fn diagnostics(&self) -> Diagnostics;
+}
+
+/// Registry entry describing how to build a source
+#[derive(Clone, Copy)]
+pub struct SourceDescriptor {
+    pub id: &'static str,
+    pub label: &'static str,
+    /// Given the registry the source is created from, for sources built
+    /// from other sources
+    pub create: fn(&AppSettings, &SourceRegistry) -> Box<dyn SensorSource>,
+    /// The settings `create` reads, in any stable form. Sources read their
+    /// settings once, so a running source is recreated when this changes.
+    pub settings_key: fn(&AppSettings, &SourceRegistry) -> String,
+    /// Source polled instead while this one cannot connect
+    pub fallback: Option<&'static str>,
+}
+
+/// `settings_key` for sources that read no settings
+pub fn no_settings(_settings: &AppSettings, _registry: &SourceRegistry) -> String {
+    String::new()
+}
+
+/// Source listing sent to the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceInfo {
+    pub id: String,
+    pub label: String,
+    pub capabilities: SourceCapabilities,
+}
+
+/// All sources the app knows how to build, in display order
+pub struct SourceRegistry {
+    descriptors: Vec<SourceDescriptor>,
+}
+
+impl SourceRegistry {
+    pub fn new() -> Self {
+        Self { descriptors: Vec::new() }
+    }
+
+    /// Registry with every source that ships with the app
+    pub fn with_builtin() -> Self {
+        let mut registry = Self::new();
+        registry.register(hwinfo::DESCRIPTOR);
+        registry.register(lhm::DESCRIPTOR);
+        registry.register(aida64::DESCRIPTOR);
+        #[cfg(target_os = "linux")]
+        registry.register(linux::DESCRIPTOR);
+        #[cfg(target_os = "linux")]
+        registry.register(lm_sensors::DESCRIPTOR);
+        registry.register(nvidia_smi::DESCRIPTOR);
+        registry.register(smartctl::DESCRIPTOR);
+        registry.register(node_exporter::DESCRIPTOR);
+        registry.register(system_info::DESCRIPTOR);
+        registry.register(composite::DESCRIPTOR);
+        registry.register(replay::DESCRIPTOR);
+        registry.register(mock::DESCRIPTOR);
+        registry
+    }
+
+    /// Add a source, replacing any existing entry with the same id
+    pub fn register(&mut self, descriptor: SourceDescriptor) {
+        match self.descriptors.iter_mut().find(|d| d.id == descriptor.id) {
+            Some(existing) => *existing = descriptor,
+            None => self.descriptors.push(descriptor),
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<&SourceDescriptor> {
+        self.descriptors.iter().find(|d| d.id == id)
+    }
+
+    pub fn create(&self, id: &str, settings: &AppSettings) -> Result<Box<dyn SensorSource>, String> {
+        self.get(id)
+            .map(|d| (d.create)(settings, self))
+            .ok_or_else(|| format!("Unknown data source: {}", id))
+    }
+
+    pub fn list(&self, settings: &AppSettings) -> Vec<SourceInfo> {
+        self.descriptors
+            .iter()
+            .map(|d| SourceInfo {
+                id: d.id.to_string(),
+                label: d.label.to_string(),
+                capabilities: (d.create)(settings, self).capabilities(),
+            })
+            .collect()
+    }
+}
+
+impl Default for SourceRegistry {
+    fn default() -> Self {
+        Self::with_builtin()
+    }
+}
+
+struct Connection {
+    source: Box<dyn SensorSource>,
+    connected: bool,
+    /// `settings_key` of the settings the source was created with
+    settings_key: String,
+}
+
+impl Connection {
+    fn new(source: Box<dyn SensorSource>) -> Self {
+        Self {
+            source,
+            connected: false,
+            settings_key: String::new(),
+        }
+    }
+
+    /// The connection in `slot`, replaced unless it holds `descriptor`'s
+    /// source created with the same settings
+    fn reuse<'a>(
+        slot: &'a mut Option<Connection>,
+        registry: &SourceRegistry,
+        descriptor: &SourceDescriptor,
+        settings: &AppSettings,
+    ) -> &'a mut Connection {
+        let settings_key = (descriptor.settings_key)(settings, registry);
+        if slot.as_ref().is_none_or(|c| c.source.id() != descriptor.id || c.settings_key != settings_key) {
+            *slot = Some(Connection {
+                settings_key,
+                ..Connection::new((descriptor.create)(settings, registry))
+            });
+        }
+        slot.as_mut().expect("connection was just created")
+    }
+
+    /// Poll, connecting first if needed. A failure marks the connection for
+    /// a reconnect on the next call.
+    fn poll(&mut self) -> Result<SensorData, String> {
+        if !self.connected {
+            self.source.connect()?;
+            self.connected = true;
+        }
+        let result = self.source.poll();
+        if result.is_err() {
+            self.connected = false;
+        }
+        result
+    }
+}
+
+#[derive(Default)]
+struct Connections {
+    primary: Option<Connection>,
+    fallback: Option<Connection>,
+}
+
+/// The source selected in settings, held in Tauri managed state
+pub struct ActiveSource {
+    registry: SourceRegistry,
+    current: Mutex<Connections>,
+    collectors: Mutex<CollectorSet>,
+}
+
+impl ActiveSource {
+    pub fn new(registry: SourceRegistry) -> Self {
+        Self {
+            registry,
+            current: Mutex::new(Connections::default()),
+            collectors: Mutex::new(CollectorSet::default()),
+        }
+    }
+
+    pub fn registry(&self) -> &SourceRegistry {
+        &self.registry
+    }
+
+    /// Poll the selected source and add the latest readings from the
+    /// configured external collectors to its raw readings
+    pub fn poll(&self, settings: &AppSettings) -> SensorData {
+        let mut data = self.poll_source(settings);
+
+        let mut collectors = self.collectors.lock().unwrap_or_else(|e| e.into_inner());
+        collectors.sync(settings.collectors.as_deref().unwrap_or_default());
+        data.raw_readings.extend(collectors.raw_readings());
+        collectors.request();
+        data
+    }
+
+    pub fn collector_statuses(&self) -> Vec<CollectorStatus> {
+        self.collectors.lock().unwrap_or_else(|e| e.into_inner()).statuses()
+    }
+
+    /// Poll the source selected by `settings`, recreating it if the
+    /// selection or the settings it reads changed since the last call. While
+    /// the selected source is unavailable its fallback source, if any, is
+    /// polled instead and the result is reported with status "fallback".
+    fn poll_source(&self, settings: &AppSettings) -> SensorData {
+        let id = settings.source_id();
+        let mut current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+        let Connections { primary, fallback } = &mut *current;
+
+        let Some(selected) = self.registry.get(id) else {
+            *primary = None;
+            *fallback = None;
+            return SensorData::not_connected(Diagnostics {
+                hwinfo_process_detected: false,
+                shared_memory_detected: false,
+                message: Some(format!("Unknown data source: {}", id)),
+                source: Some(id.to_string()),
+                provenance: Default::default(),
+            });
+        };
+
+        let primary = Connection::reuse(primary, &self.registry, selected, settings);
+        let msg = match primary.poll() {
+            Ok(mut data) => {
+                data.diagnostics.source = Some(primary.source.id().to_string());
+                return data;
+            }
+            Err(msg) => msg,
+        };
+
+        let mut diagnostics = primary.source.diagnostics();
+        diagnostics.message = Some(msg);
+        diagnostics.source = Some(primary.source.id().to_string());
+
+        let Some(descriptor) = selected.fallback.and_then(|id| self.registry.get(id)) else {
+            *fallback = None;
+            return SensorData::not_connected(diagnostics);
+        };
+
+        match Connection::reuse(fallback, &self.registry, descriptor, settings).poll() {
+            Ok(mut data) => {
+                // Keep the primary's detection flags so the UI can explain what is missing
+                data.status = "fallback".to_string();
+                diagnostics.message = diagnostics
+                    .message
+                    .map(|m| format!("{}. Showing {} data instead.", m.trim_end_matches('.'), descriptor.label));
+                diagnostics.source = Some(descriptor.id.to_string());
+                data.diagnostics = diagnostics;
+                data
+            }
+            Err(_) => SensorData::not_connected(diagnostics),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use super::{SensorSource, SourceCapabilities, SourceDescriptor, SourceRegistry};
+use crate::commands::AppSettings;
+use crate::hwinfo::types::{Diagnostics, SensorData};
+use crate::node_exporter::{self, Sample};
+
+pub const DESCRIPTOR: SourceDescriptor = SourceDescriptor {
+    id: "node-exporter",
+    label: "Prometheus node_exporter (remote)",
+    create,
+    settings_key,
+    // Local readings would be mistaken for the remote machine's
+    fallback: None,
+};
+
+fn create(settings: &AppSettings, _registry: &SourceRegistry) -> Box<dyn SensorSource> {
+    let url = settings.node_exporter_url.as_deref().unwrap_or(node_exporter::DEFAULT_URL);
+    Box::new(NodeExporterSource::new(url.to_string()))
+}
+
+fn settings_key(settings: &AppSettings, _registry: &SourceRegistry) -> String {
+    format!("{:?}", settings.node_exporter_url)
+}
+
+/// Scrapes a node_exporter `/metrics` endpoint, keeping the previous scrape
+/// for CPU utilization
+pub struct NodeExporterSource {
+    url: String,
+    agent: ureq::Agent,
+    previous: Vec<Sample>,
+}
+
+impl NodeExporterSource {
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+            agent: node_exporter::agent(),
+            previous: Vec::new(),
+        }
+    }
+}
+
+impl SensorSource for NodeExporterSource {
+    fn id(&self) -> &'static str {
+        DESCRIPTOR.id
+    }
+
+    fn connect(&mut self) -> Result<(), String> {
+        if !self.url.starts_with("http://") {
+            return Err(format!("Unsupported node_exporter URL: {}", self.url));
+        }
+        // Counters from before a dropped connection would skew the first delta
+        self.previous.clear();
+        Ok(())
+    }
+
+    fn poll(&mut self) -> Result<SensorData, String> {
+        let samples = node_exporter::fetch(&self.agent, &self.url)?;
+        let mut data = SensorData::connected(self.diagnostics());
+        node_exporter::apply(&samples, &self.previous, &mut data);
+        self.previous = samples;
+        Ok(data)
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            cpu: true,
+            gpu: true,
+            storage: true,
+            fans: true,
+            uptime: true,
+        }
+    }
+
+    fn diagnostics(&self) -> Diagnostics {
+        Diagnostics {
+            hwinfo_process_detected: false,
+            shared_memory_detected: false,
+            message: Some(format!("{} samples from {}", self.previous.len(), self.url)),
+            source: Some(DESCRIPTOR.id.to_string()),
+            provenance: Default::default(),
+        }
+    }
+}
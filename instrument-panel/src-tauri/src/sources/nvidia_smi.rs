@@ -0,0 +1,75 @@
+use super::{SensorSource, SourceCapabilities, SourceDescriptor, SourceRegistry};
+use crate::commands::AppSettings;
+use crate::hwinfo::types::{Diagnostics, SensorData};
+use crate::nvidia_smi;
+
+pub const DESCRIPTOR: SourceDescriptor = SourceDescriptor {
+    id: "nvidia-smi",
+    label: "NVIDIA (nvidia-smi)",
+    create,
+    settings_key,
+    fallback: None,
+};
+
+fn create(settings: &AppSettings, _registry: &SourceRegistry) -> Box<dyn SensorSource> {
+    let command = settings.nvidia_smi_path.as_deref().unwrap_or(nvidia_smi::DEFAULT_COMMAND);
+    Box::new(NvidiaSmiSource {
+        command: command.to_string(),
+        gpu_count: 0,
+    })
+}
+
+fn settings_key(settings: &AppSettings, _registry: &SourceRegistry) -> String {
+    format!("{:?}", settings.nvidia_smi_path)
+}
+
+/// GPU readings from `nvidia-smi`. The first GPU fills `gpu`; all of them are
+/// listed in `gpus`.
+pub struct NvidiaSmiSource {
+    command: String,
+    gpu_count: usize,
+}
+
+impl SensorSource for NvidiaSmiSource {
+    fn id(&self) -> &'static str {
+        DESCRIPTOR.id
+    }
+
+    fn connect(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn poll(&mut self) -> Result<SensorData, String> {
+        let gpus = nvidia_smi::parse(&nvidia_smi::query(&self.command)?)?;
+        if gpus.is_empty() {
+            return Err(format!("{} reported no GPUs", self.command));
+        }
+        self.gpu_count = gpus.len();
+
+        let mut data = SensorData::connected(self.diagnostics());
+        data.gpu = gpus[0].clone();
+        data.gpus = gpus;
+        data.system.name = sysinfo::System::host_name();
+        Ok(data)
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            cpu: false,
+            gpu: true,
+            storage: false,
+            fans: false,
+            uptime: false,
+        }
+    }
+
+    fn diagnostics(&self) -> Diagnostics {
+        Diagnostics {
+            hwinfo_process_detected: false,
+            shared_memory_detected: false,
+            message: Some(format!("{} GPUs from {}", self.gpu_count, self.command)),
+            source: Some(DESCRIPTOR.id.to_string()),
+            provenance: Default::default(),
+        }
+    }
+}
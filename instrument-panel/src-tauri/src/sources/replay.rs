@@ -0,0 +1,105 @@
+use super::{SensorSource, SourceCapabilities, SourceDescriptor, SourceRegistry};
+use crate::commands::AppSettings;
+use crate::hwinfo::snapshot::Snapshot;
+use crate::hwinfo::types::{Diagnostics, SensorData};
+use chrono::Utc;
+use std::path::PathBuf;
+use std::time::Instant;
+
+pub const DESCRIPTOR: SourceDescriptor = SourceDescriptor {
+    id: "replay",
+    label: "Recorded snapshot",
+    create,
+    settings_key,
+    fallback: None,
+};
+
+fn create(settings: &AppSettings, _registry: &SourceRegistry) -> Box<dyn SensorSource> {
+    Box::new(ReplaySource {
+        path: settings.replay_file.as_ref().map(PathBuf::from),
+        speed: settings.replay_speed.filter(|s| *s > 0.0).unwrap_or(1.0),
+        looping: settings.replay_loop.unwrap_or(true),
+        snapshot: None,
+        started: None,
+        frame: None,
+    })
+}
+
+fn settings_key(settings: &AppSettings, _registry: &SourceRegistry) -> String {
+    format!("{:?}", (&settings.replay_file, settings.replay_speed, settings.replay_loop))
+}
+
+/// Plays back a snapshot file captured with `capture_snapshot`
+pub struct ReplaySource {
+    path: Option<PathBuf>,
+    /// 2.0 plays twice as fast as recorded
+    speed: f64,
+    looping: bool,
+    snapshot: Option<Snapshot>,
+    /// First poll; playback counts from here
+    started: Option<Instant>,
+    /// Frame shown by the latest poll
+    frame: Option<usize>,
+}
+
+impl SensorSource for ReplaySource {
+    fn id(&self) -> &'static str {
+        DESCRIPTOR.id
+    }
+
+    fn connect(&mut self) -> Result<(), String> {
+        let path = self
+            .path
+            .as_ref()
+            .ok_or("Set replayFile in settings to a snapshot file")?;
+        let snapshot = Snapshot::load(path)?;
+        if snapshot.frames.is_empty() {
+            return Err(format!("Snapshot {} has no frames", path.display()));
+        }
+        self.snapshot = Some(snapshot);
+        Ok(())
+    }
+
+    fn poll(&mut self) -> Result<SensorData, String> {
+        let snapshot = self.snapshot.as_ref().ok_or("Snapshot not loaded")?;
+        let elapsed = self.started.get_or_insert_with(Instant::now).elapsed().as_secs_f64();
+        let elapsed_ms = (elapsed * self.speed * 1000.0) as u64;
+
+        self.frame = snapshot.frame_at(elapsed_ms, self.looping);
+        let index = self.frame.ok_or("Reached the end of the snapshot")?;
+        let mut data = snapshot.replay(index)?;
+        data.last_read_at = Some(Utc::now().to_rfc3339());
+        data.diagnostics = self.diagnostics();
+        Ok(data)
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            cpu: true,
+            gpu: true,
+            storage: true,
+            fans: true,
+            uptime: true,
+        }
+    }
+
+    fn diagnostics(&self) -> Diagnostics {
+        let message = match (&self.snapshot, self.frame) {
+            (Some(snapshot), Some(frame)) => format!(
+                "Frame {} of {} from {} ({})",
+                frame + 1,
+                snapshot.frames.len(),
+                snapshot.host.hostname.as_deref().unwrap_or("unknown host"),
+                snapshot.source
+            ),
+            _ => "No snapshot playing".to_string(),
+        };
+        Diagnostics {
+            hwinfo_process_detected: false,
+            shared_memory_detected: self.frame.is_some(),
+            message: Some(message),
+            source: Some(DESCRIPTOR.id.to_string()),
+            provenance: Default::default(),
+        }
+    }
+}
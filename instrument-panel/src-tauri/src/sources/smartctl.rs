@@ -0,0 +1,113 @@
+use super::{SensorSource, SourceCapabilities, SourceDescriptor, SourceRegistry};
+use crate::commands::AppSettings;
+use crate::hwinfo::types::{Diagnostics, DriveData, SensorData, StorageData};
+use crate::smartctl::{self, Device};
+use std::time::{Duration, Instant};
+
+pub const DESCRIPTOR: SourceDescriptor = SourceDescriptor {
+    id: "smartctl",
+    label: "smartctl (drive health)",
+    create,
+    settings_key,
+    fallback: None,
+};
+
+/// SMART data changes slowly and each read wakes the drive's controller
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+fn create(settings: &AppSettings, _registry: &SourceRegistry) -> Box<dyn SensorSource> {
+    let command = settings.smartctl_command.as_deref().unwrap_or(smartctl::DEFAULT_COMMAND);
+    Box::new(SmartctlSource {
+        command: command.to_string(),
+        devices: Vec::new(),
+        drives: Vec::new(),
+        read_at: None,
+    })
+}
+
+fn settings_key(settings: &AppSettings, _registry: &SourceRegistry) -> String {
+    format!("{:?}", settings.smartctl_command)
+}
+
+/// Drive temperature, wear and error counters from smartmontools. Devices are
+/// scanned on connect and re-read at most once per `REFRESH_INTERVAL`.
+pub struct SmartctlSource {
+    command: String,
+    devices: Vec<Device>,
+    drives: Vec<DriveData>,
+    read_at: Option<Instant>,
+}
+
+impl SmartctlSource {
+    fn refresh(&mut self) -> Result<(), String> {
+        let mut drives = Vec::new();
+        let mut last_error = None;
+        // One unreadable device (e.g. a USB bridge smartctl can't pass through)
+        // shouldn't hide the rest
+        for device in &self.devices {
+            match smartctl::read(&self.command, device).and_then(|json| smartctl::parse(&json)) {
+                Ok(drive) => drives.push(drive),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        if drives.is_empty() {
+            return Err(last_error.unwrap_or_else(|| format!("{} found no devices", self.command)));
+        }
+        self.drives = drives;
+        self.read_at = Some(Instant::now());
+        Ok(())
+    }
+}
+
+impl SensorSource for SmartctlSource {
+    fn id(&self) -> &'static str {
+        DESCRIPTOR.id
+    }
+
+    fn connect(&mut self) -> Result<(), String> {
+        self.devices = smartctl::scan(&self.command)?;
+        self.read_at = None;
+        if self.devices.is_empty() {
+            return Err(format!("{} found no devices", self.command));
+        }
+        Ok(())
+    }
+
+    fn poll(&mut self) -> Result<SensorData, String> {
+        if self.read_at.is_none_or(|at| at.elapsed() >= REFRESH_INTERVAL) {
+            self.refresh()?;
+        }
+
+        let mut data = SensorData::connected(self.diagnostics());
+        if let Some(first) = self.drives.first() {
+            data.storage = StorageData {
+                name: first.name.clone(),
+                nvme_temp_c: first.temp_c,
+                smart_health: first.smart_health.clone(),
+            };
+        }
+        data.drives = self.drives.clone();
+        data.system.name = sysinfo::System::host_name();
+        Ok(data)
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            cpu: false,
+            gpu: false,
+            storage: true,
+            fans: false,
+            uptime: false,
+        }
+    }
+
+    fn diagnostics(&self) -> Diagnostics {
+        Diagnostics {
+            hwinfo_process_detected: false,
+            shared_memory_detected: false,
+            message: Some(format!("{} of {} devices from {}", self.drives.len(), self.devices.len(), self.command)),
+            source: Some(DESCRIPTOR.id.to_string()),
+            provenance: Default::default(),
+        }
+    }
+}
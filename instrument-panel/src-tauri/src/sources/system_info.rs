@@ -0,0 +1,168 @@
+use super::{SensorSource, SourceCapabilities, SourceDescriptor, SourceRegistry};
+use crate::commands::AppSettings;
+use crate::hwinfo::types::*;
+use sysinfo::{Components, Disks, System};
+
+pub const DESCRIPTOR: SourceDescriptor = SourceDescriptor {
+    id: "sysinfo",
+    label: "Generic (sysinfo)",
+    create,
+    settings_key: super::no_settings,
+    fallback: None,
+};
+
+fn create(_settings: &AppSettings, _registry: &SourceRegistry) -> Box<dyn SensorSource> {
+    Box::new(SystemInfoSource::new())
+}
+
+const BYTES_PER_MB: f64 = 1024.0 * 1024.0;
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// Portable readings from the `sysinfo` crate. Works on any OS without a
+/// helper process, but has no power, fan or S.M.A.R.T. data.
+pub struct SystemInfoSource {
+    system: System,
+    disks: Disks,
+    components: Components,
+}
+
+impl Default for SystemInfoSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SensorSource for SystemInfoSource {
+    fn id(&self) -> &'static str {
+        DESCRIPTOR.id
+    }
+
+    fn connect(&mut self) -> Result<(), String> {
+        // CPU usage is a difference between refreshes, so take the baseline now
+        self.system.refresh_cpu_all();
+        self.disks.refresh_list();
+        self.components.refresh_list();
+        Ok(())
+    }
+
+    fn poll(&mut self) -> Result<SensorData, String> {
+        self.system.refresh_cpu_all();
+        self.system.refresh_memory();
+        self.disks.refresh();
+        self.components.refresh();
+
+        let mut data = SensorData::connected(self.diagnostics());
+        self.fill_cpu(&mut data.cpu);
+        self.fill_gpu(&mut data.gpu);
+        self.fill_drives(&mut data);
+        self.fill_system(&mut data.system);
+        Ok(data)
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            cpu: true,
+            // GPU temperatures, where the OS exposes them as components
+            gpu: true,
+            storage: true,
+            fans: false,
+            uptime: true,
+        }
+    }
+
+    fn diagnostics(&self) -> Diagnostics {
+        Diagnostics {
+            hwinfo_process_detected: false,
+            shared_memory_detected: false,
+            message: None,
+            source: Some(DESCRIPTOR.id.to_string()),
+            provenance: Default::default(),
+        }
+    }
+}
+
+impl SystemInfoSource {
+    pub fn new() -> Self {
+        Self {
+            system: System::new(),
+            disks: Disks::new(),
+            components: Components::new(),
+        }
+    }
+
+    fn fill_cpu(&self, cpu: &mut CpuData) {
+        let cpus = self.system.cpus();
+        cpu.name = cpus.first().map(|c| c.brand().trim().to_string()).filter(|b| !b.is_empty());
+        cpu.usage_percent = Some(self.system.global_cpu_usage() as f64);
+        cpu.core_usage_percent = cpus.iter().map(|c| c.cpu_usage() as f64).collect();
+        cpu.core_clocks_mhz = cpus.iter().map(|c| c.frequency() as f64).filter(|f| *f > 0.0).collect();
+        if !cpu.core_clocks_mhz.is_empty() {
+            cpu.core_clock_mhz = Some(cpu.core_clocks_mhz.iter().sum::<f64>() / cpu.core_clocks_mhz.len() as f64);
+        }
+        cpu.package_temp_c = self.temperature(|label| {
+            label.contains("tctl") || label.contains("tdie") || label.contains("package id") || label.starts_with("cpu")
+        });
+    }
+
+    /// Junction is the hot spot; any other GPU label (edge, core) is the core
+    fn fill_gpu(&self, gpu: &mut GpuData) {
+        gpu.hotspot_temp_c = self.temperature(|label| label.contains("junction") && !label.contains("mem"));
+        gpu.core_temp_c = self.temperature(|label| {
+            (label.contains("gpu") || label.contains("amdgpu")) && !label.contains("junction") && !label.contains("mem")
+        });
+    }
+
+    fn fill_drives(&self, data: &mut SensorData) {
+        for disk in self.disks.list() {
+            if disk.total_space() == 0 {
+                continue;
+            }
+            // "C:\" on Windows, the mount point elsewhere
+            let mount = disk.mount_point().to_string_lossy().to_string();
+            let mount = if mount.len() > 1 { mount.trim_end_matches(['\\', '/']).to_string() } else { mount };
+            let name = disk.name().to_string_lossy().to_string();
+
+            data.drives.push(DriveData {
+                name: Some(if name.is_empty() { mount.clone() } else { name }),
+                drive_letter: Some(mount),
+                total_gb: Some(disk.total_space() as f64 / BYTES_PER_GB),
+                free_gb: Some(disk.available_space() as f64 / BYTES_PER_GB),
+                ..DriveData::default()
+            });
+        }
+
+        if let Some(first) = data.drives.first() {
+            data.storage = StorageData {
+                name: first.name.clone(),
+                nvme_temp_c: self.temperature(|label| label.contains("nvme") || label.contains("composite")),
+                smart_health: "unknown".to_string(),
+            };
+        }
+    }
+
+    fn fill_system(&self, system: &mut SystemData) {
+        system.name = System::host_name();
+        system.uptime_seconds = Some(System::uptime());
+        system.memory_total_mb = Some(self.system.total_memory() as f64 / BYTES_PER_MB);
+        system.memory_used_mb = Some(self.system.used_memory() as f64 / BYTES_PER_MB);
+        // Windows has no run queue load average; sysinfo reports zeros there
+        if cfg!(not(windows)) {
+            let load = System::load_average();
+            system.load_average = Some(LoadAverage {
+                one: load.one,
+                five: load.five,
+                fifteen: load.fifteen,
+            });
+        }
+    }
+
+    /// First component temperature whose lowercase label matches
+    fn temperature(&self, matches: impl Fn(&str) -> bool) -> Option<f64> {
+        self.components
+            .list()
+            .iter()
+            .filter(|c| matches(&c.label().to_lowercase()))
+            .map(|c| c.temperature() as f64)
+            .find(|t| t.is_finite())
+    }
+}
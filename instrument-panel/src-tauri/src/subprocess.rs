@@ -0,0 +1,74 @@
+//! Helper commands run from the polling thread, with a deadline.
+//!
+//! nvidia-smi, sensors and smartctl can all hang: on a wedged driver, a stuck
+//! bus or a disk spinning up. `Command::output` would wait for them forever
+//! and stall every update, so the command is killed once its time is up.
+
+use std::io::{self, Read};
+use std::process::{Command, Output, Stdio};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often to check whether the command has exited
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+/// How long output may trail the exit when the deadline is nearly up
+const PIPE_GRACE: Duration = Duration::from_millis(100);
+
+/// Like `Command::output`, but kills the command and fails with
+/// `ErrorKind::TimedOut` if it has not exited within `timeout`
+pub fn output(cmd: &mut Command, timeout: Duration) -> io::Result<Output> {
+    let mut child = cmd.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+    // Read both pipes while waiting, so a chatty command can't fill one and block
+    let stdout = read_all(child.stdout.take().expect("stdout is piped"));
+    let stderr = read_all(child.stderr.take().expect("stderr is piped"));
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("no answer within {} s", timeout.as_secs_f64()),
+            ));
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    // Whatever it started may still hold the pipes open; take what has arrived
+    let collect = |(buffer, closed): Pipe| {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let _ = closed.recv_timeout(remaining.max(PIPE_GRACE));
+        std::mem::take(&mut *buffer.lock().unwrap_or_else(|e| e.into_inner()))
+    };
+    Ok(Output {
+        status,
+        stdout: collect(stdout),
+        stderr: collect(stderr),
+    })
+}
+
+/// What has been read from a pipe so far, and a channel that disconnects
+/// once it is closed
+type Pipe = (Arc<Mutex<Vec<u8>>>, mpsc::Receiver<()>);
+
+/// Read `pipe` to the end on its own thread
+fn read_all(mut pipe: impl Read + Send + 'static) -> Pipe {
+    let buffer = Arc::new(Mutex::new(Vec::new()));
+    let (closed, rx) = mpsc::channel::<()>();
+    {
+        let buffer = buffer.clone();
+        thread::spawn(move || {
+            let _closed = closed;
+            let mut chunk = [0; 4096];
+            while let Ok(n @ 1..) = pipe.read(&mut chunk) {
+                buffer.lock().unwrap_or_else(|e| e.into_inner()).extend_from_slice(&chunk[..n]);
+            }
+        });
+    }
+    (buffer, rx)
+}
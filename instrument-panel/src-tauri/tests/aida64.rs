@@ -0,0 +1,109 @@
+mod common;
+
+use instrument_panel_lib::aida64;
+use instrument_panel_lib::hwinfo::types::SensorData;
+
+/// Copied out of `AIDA64_SensorValues` on an i9/Radeon machine
+const SENSOR_VALUES: &str = include_str!("fixtures/aida64/sensor_values.txt");
+
+fn mapped(text: &str) -> SensorData {
+    let mut data = common::reading("aida64");
+    aida64::apply(&aida64::parse(text), &mut data);
+    data
+}
+
+#[test]
+fn parses_every_category() {
+    let readings = aida64::parse(SENSOR_VALUES);
+    assert_eq!(readings.len(), 37);
+    assert_eq!(readings[0].category, "sys");
+    assert_eq!(readings[0].id, "SDATE");
+
+    let fan = readings.iter().find(|r| r.id == "FCHA1").unwrap();
+    assert_eq!(fan.category, "fan");
+    assert_eq!(fan.label, "Chassis #1");
+    assert_eq!(fan.number(), Some(842.0));
+
+    let date = readings.iter().find(|r| r.id == "SDATE").unwrap();
+    assert_eq!(date.number(), None);
+}
+
+#[test]
+fn maps_known_ids() {
+    let data = mapped(SENSOR_VALUES);
+
+    assert_eq!(data.cpu.package_temp_c, Some(67.0));
+    assert_eq!(data.cpu.core_temps, vec![64.0, 61.0]);
+    assert_eq!(data.cpu.package_power_w, Some(142.37));
+    assert_eq!(data.cpu.core_power_w, Some(128.90));
+    assert_eq!(data.cpu.uncore_power_w, Some(0.04));
+    assert_eq!(data.cpu.core_clock_mhz, Some(4788.0));
+    assert_eq!(data.cpu.core_clocks_mhz, vec![4900.0, 4675.0]);
+    assert_eq!(data.cpu.usage_percent, Some(18.0));
+    assert_eq!(data.cpu.core_usage_percent, vec![31.0, 5.0]);
+
+    assert_eq!(data.gpu.core_temp_c, Some(58.0));
+    assert_eq!(data.gpu.hotspot_temp_c, Some(71.0));
+    assert_eq!(data.gpu.memory_junction_temp_c, Some(64.0));
+    assert_eq!(data.gpu.power_w, Some(243.10));
+    assert_eq!(data.gpu.core_clock_mhz, Some(2520.0));
+    assert_eq!(data.gpu.memory_clock_mhz, Some(1250.0));
+    assert_eq!(data.gpu.usage_percent, Some(64.0));
+    // Only virtual memory is published here
+    assert_eq!(data.gpu.vram_used_mb, None);
+    assert_eq!(data.gpu.vram_total_mb, None);
+    assert_eq!(data.gpu.fan_speed_rpm, Some(1420.0));
+    assert_eq!(data.gpu.fan_speed_percent, Some(41.0));
+
+    let drives: Vec<(Option<&str>, Option<f64>)> =
+        data.drives.iter().map(|d| (d.name.as_deref(), d.temp_c)).collect();
+    assert_eq!(drives, [
+        (Some("Samsung SSD 990 PRO 2TB"), Some(44.0)),
+        (Some("WDC WD40EFRX-68N32N0"), Some(35.0)),
+    ]);
+    assert_eq!(data.storage.nvme_temp_c, Some(44.0));
+
+    assert_eq!(data.system.memory_used_mb, Some(12410.0));
+    assert_eq!(data.system.memory_total_mb, Some(32768.0));
+    assert_eq!(data.system.uptime_seconds, Some(2 * 86_400 + 3 * 3_600 + 4 * 60 + 5));
+
+    // Stopped and GPU fans are left out
+    let fans: Vec<&str> = data.system.fans.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(fans, ["CPU", "Chassis #1"]);
+    assert_eq!(data.system.fan_status, "ok");
+}
+
+#[test]
+fn without_package_sensor_falls_back_to_cpu_diode() {
+    let data = mapped("<temp><id>TCPU</id><label>CPU</label><value>49</value></temp>\
+                       <temp><id>TGPU1</id><label>GPU1</label><value>55</value></temp>");
+    assert_eq!(data.cpu.package_temp_c, Some(49.0));
+    assert_eq!(data.gpu.core_temp_c, Some(55.0));
+    assert_eq!(data.gpu.hotspot_temp_c, None);
+    assert_eq!(data.gpu.memory_junction_temp_c, None);
+}
+
+#[test]
+fn reads_only_the_first_gpu() {
+    let data = mapped("<sys><id>SGPU1USEDDEMEM</id><label>GPU1 Used Dedicated Memory</label><value>3012</value></sys>\
+                       <temp><id>TGPU10</id><label>GPU10</label><value>81</value></temp>\
+                       <temp><id>TGPU10HOT</id><label>GPU10 Hotspot</label><value>96</value></temp>\
+                       <temp><id>TGPU10MEM</id><label>GPU10 Memory</label><value>90</value></temp>");
+    assert_eq!(data.gpu.vram_used_mb, Some(3012.0));
+    assert_eq!(data.gpu.vram_total_mb, None);
+    assert_eq!(data.gpu.core_temp_c, None);
+    assert_eq!(data.gpu.hotspot_temp_c, None);
+    assert_eq!(data.gpu.memory_junction_temp_c, None);
+}
+
+#[test]
+fn tolerates_escapes_and_a_torn_tail() {
+    let readings = aida64::parse(
+        "<temp><id>THDD1</id><label>Drive &amp; Co &lt;NVMe&gt;</label><value>40</value></temp><temp><id>TCP",
+    );
+    assert_eq!(readings.len(), 1);
+    assert_eq!(readings[0].label, "Drive & Co <NVMe>");
+
+    assert_eq!(aida64::parse_uptime("00:12:30"), Some(750));
+    assert_eq!(aida64::parse_uptime("garbage"), None);
+}
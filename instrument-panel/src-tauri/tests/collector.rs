@@ -0,0 +1,206 @@
+use instrument_panel_lib::collector::{self, CollectorReading, Message};
+
+#[test]
+fn protocol_messages() {
+    let hello = collector::parse_message(
+        r#"{"type":"hello","protocol":1,"name":"rack-pdu","capabilities":["power","current"]}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        hello,
+        Message::Hello {
+            protocol: 1,
+            name: "rack-pdu".to_string(),
+            capabilities: vec!["power".to_string(), "current".to_string()],
+        }
+    );
+
+    let readings = collector::parse_message(
+        r#"{"type":"readings","readings":[{"id":"outlet1","label":"Outlet 1","type":"power","unit":"W","value":212.5}]}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        readings,
+        Message::Readings {
+            readings: vec![CollectorReading {
+                id: "outlet1".to_string(),
+                label: "Outlet 1".to_string(),
+                kind: "power".to_string(),
+                unit: "W".to_string(),
+                value: 212.5,
+            }],
+        }
+    );
+
+    assert!(collector::parse_message(r#"{"type":"goodbye"}"#).is_err());
+    assert!(collector::parse_message("Probe 1: 23.5C").is_err());
+}
+
+#[cfg(unix)]
+mod supervised {
+    use instrument_panel_lib::collector::{Collector, CollectorConfig, CollectorSet};
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::Path;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    /// Answers each poll with a reading counting up from 1
+    const THERMOCOUPLE: &str = r#"#!/bin/sh
+echo '{"type":"hello","protocol":1,"name":"thermocouple","capabilities":["temperature"]}'
+n=0
+while read line; do
+  n=$((n + 1))
+  echo "{\"type\":\"readings\",\"readings\":[{\"id\":\"tc1\",\"label\":\"Probe 1\",\"type\":\"temperature\",\"unit\":\"C\",\"value\":$n}]}"
+done
+"#;
+
+    fn script(dir: &Path, name: &str, body: &str) -> CollectorConfig {
+        let path = dir.join(name);
+        std::fs::write(&path, body).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        CollectorConfig {
+            command: path.to_string_lossy().into_owned(),
+            args: Vec::new(),
+        }
+    }
+
+    fn wait_for(mut done: impl FnMut() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !done() {
+            assert!(Instant::now() < deadline, "timed out");
+            thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    #[test]
+    fn readings_arrive_in_answer_to_polls() {
+        let dir = tempfile::tempdir().unwrap();
+        let collector = Collector::spawn(script(dir.path(), "thermocouple", THERMOCOUPLE));
+
+        wait_for(|| collector.status().running);
+        assert_eq!(collector.status().name.as_deref(), Some("thermocouple"));
+        assert_eq!(collector.status().capabilities, vec!["temperature"]);
+        assert!(collector.raw_readings().is_empty());
+
+        collector.request();
+        wait_for(|| !collector.raw_readings().is_empty());
+        let readings = collector.raw_readings();
+        assert_eq!(readings[0].group, "thermocouple");
+        assert_eq!(readings[0].label, "Probe 1");
+        assert_eq!(readings[0].value, 1.0);
+
+        collector.request();
+        wait_for(|| collector.raw_readings()[0].value == 2.0);
+    }
+
+    #[test]
+    fn exited_and_misbehaving_collectors_are_restarted() {
+        let dir = tempfile::tempdir().unwrap();
+        let quitter = Collector::spawn(script(
+            dir.path(),
+            "quitter",
+            "#!/bin/sh\necho '{\"type\":\"hello\",\"protocol\":1,\"name\":\"quitter\"}'\n",
+        ));
+        let rude = Collector::spawn(script(
+            dir.path(),
+            "rude",
+            "#!/bin/sh\necho '{\"type\":\"readings\",\"readings\":[]}'\nsleep 5\n",
+        ));
+
+        wait_for(|| quitter.status().restarts >= 2);
+        assert_eq!(quitter.status().last_error.as_deref(), Some("Collector exited"));
+
+        wait_for(|| rude.status().restarts >= 1);
+        let status = rude.status();
+        assert!(!status.running);
+        assert_eq!(status.last_error.as_deref(), Some("Collector did not start with a hello message"));
+
+        let missing = Collector::spawn(CollectorConfig {
+            command: dir.path().join("missing").to_string_lossy().into_owned(),
+            args: Vec::new(),
+        });
+        wait_for(|| missing.status().restarts >= 1);
+        assert!(missing.status().last_error.unwrap().starts_with("Failed to start"));
+    }
+
+    #[test]
+    fn stderr_explains_an_exit() {
+        let dir = tempfile::tempdir().unwrap();
+        let failing = Collector::spawn(script(
+            dir.path(),
+            "failing",
+            "#!/bin/sh\necho 'opening /dev/ttyUSB0' >&2\necho 'no probe on /dev/ttyUSB0' >&2\nexit 1\n",
+        ));
+        wait_for(|| failing.status().restarts >= 1);
+        assert_eq!(failing.status().last_error.as_deref(), Some("Collector exited: no probe on /dev/ttyUSB0"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn dropping_kills_what_the_collector_started() {
+        // The background sleep holds stdout open after the script itself is gone
+        const WRAPPER: &str = r#"#!/bin/sh
+sleep 60 &
+echo $! > "$0.pid"
+echo '{"type":"hello","protocol":1,"name":"wrapper"}'
+wait
+"#;
+        let dir = tempfile::tempdir().unwrap();
+        let collector = Collector::spawn(script(dir.path(), "wrapper", WRAPPER));
+        wait_for(|| collector.status().running);
+        let pid = std::fs::read_to_string(dir.path().join("wrapper.pid")).unwrap();
+
+        let started = Instant::now();
+        drop(collector);
+        assert!(started.elapsed() < Duration::from_secs(1));
+        // Gone, or a zombie waiting for init
+        let alive = || {
+            std::fs::read_to_string(format!("/proc/{}/stat", pid.trim())).is_ok_and(|stat| !stat.contains(") Z "))
+        };
+        wait_for(|| !alive());
+    }
+
+    #[test]
+    fn a_collector_that_stops_reading_stdin_blocks_nobody() {
+        // Never reads its stdin, so the pipe fills after a few thousand polls
+        let dir = tempfile::tempdir().unwrap();
+        let deaf = Collector::spawn(script(
+            dir.path(),
+            "deaf",
+            "#!/bin/sh\necho '{\"type\":\"hello\",\"protocol\":1,\"name\":\"deaf\"}'\nexec sleep 60\n",
+        ));
+        wait_for(|| deaf.status().running);
+
+        let started = Instant::now();
+        for _ in 0..100_000 {
+            deaf.request();
+        }
+        drop(deaf);
+        assert!(started.elapsed() < Duration::from_secs(2), "took {:?}", started.elapsed());
+    }
+
+    #[test]
+    fn set_follows_configuration() {
+        let dir = tempfile::tempdir().unwrap();
+        let thermocouple = script(dir.path(), "thermocouple", THERMOCOUPLE);
+        let mut set = CollectorSet::default();
+
+        set.sync(std::slice::from_ref(&thermocouple));
+        wait_for(|| set.statuses()[0].running);
+        set.request();
+        wait_for(|| set.raw_readings().len() == 1);
+
+        // An unchanged collector keeps running through a configuration change
+        let second = CollectorConfig {
+            args: vec!["second".to_string()],
+            ..thermocouple.clone()
+        };
+        set.sync(&[second, thermocouple]);
+        assert_eq!(set.statuses().len(), 2);
+        assert!(set.statuses()[1].running);
+        assert_eq!(set.raw_readings().len(), 1);
+
+        set.sync(&[]);
+        assert!(set.statuses().is_empty());
+    }
+}
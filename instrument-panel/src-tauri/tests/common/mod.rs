@@ -0,0 +1,62 @@
+//! Helpers shared by the integration tests. Each test crate uses only some.
+#![allow(dead_code)]
+
+use instrument_panel_lib::hwinfo::types::{Diagnostics, SensorData};
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::path::Path;
+use std::thread;
+
+/// Diagnostics of a connected `source` with nothing to report
+pub fn diagnostics(source: &str) -> Diagnostics {
+    Diagnostics {
+        hwinfo_process_detected: false,
+        shared_memory_detected: false,
+        message: None,
+        source: Some(source.to_string()),
+        provenance: Default::default(),
+    }
+}
+
+/// An empty connected reading from `source`, for a mapping to fill
+pub fn reading(source: &str) -> SensorData {
+    SensorData::connected(diagnostics(source))
+}
+
+/// Write `(relative path, contents)` pairs under `root`, the way sysfs and
+/// procfs files end: with a newline
+pub fn write_tree(root: &Path, files: &[(&str, &str)]) {
+    for (path, contents) in files {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, format!("{}\n", contents)).unwrap();
+    }
+}
+
+/// Serve `body` as `content_type` with `status` to every request on a local
+/// port, standing in for a sensor web server. Returns the base URL.
+pub fn serve(content_type: &'static str, status: &'static str, body: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                content_type,
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+    });
+    format!("http://{}", addr)
+}
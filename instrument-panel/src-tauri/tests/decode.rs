@@ -0,0 +1,135 @@
+//! Hostile regions: the header comes from another process, so the decoder
+//! must reject bad offsets and counts rather than read past the bytes it has.
+//! The fuzz targets in `fuzz/` explore the same ground with arbitrary input.
+
+use instrument_panel_lib::hwinfo::decode;
+use instrument_panel_lib::hwinfo::image::ImageBuilder;
+use instrument_panel_lib::hwinfo::scenario::Rng;
+use instrument_panel_lib::hwinfo::snapshot;
+use instrument_panel_lib::hwinfo::types::ReadingType;
+use std::path::Path;
+use std::time::Instant;
+
+// Header field offsets
+const SENSOR_SECTION_OFFSET: usize = 20;
+const SENSOR_SECTION_SIZE: usize = 24;
+const SENSOR_COUNT: usize = 28;
+const READING_SECTION_SIZE: usize = 36;
+const READING_COUNT: usize = 40;
+
+fn image() -> Vec<u8> {
+    ImageBuilder::new()
+        .sensor("CPU [#0]: AMD Ryzen 7 7800X3D")
+        .reading(ReadingType::Temp, "CPU (Tctl/Tdie)", "°C", 61.5)
+        .reading(ReadingType::Temp, "Core0 (CCD1)", "°C", 58.0)
+        .sensor("GPU [#0]: NVIDIA GeForce RTX 4080")
+        .reading(ReadingType::Temp, "GPU Hot Spot Temperature", "°C", 71.0)
+        .sensor("S.M.A.R.T.: Samsung SSD 990 PRO 2TB [C:]")
+        .reading(ReadingType::Temp, "Drive Temperature", "°C", 41.0)
+        .reading(ReadingType::Other, "Drive Remaining Life", "%", 98.0)
+        .build()
+}
+
+fn patch(image: &mut [u8], offset: usize, value: u32) {
+    image[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+}
+
+/// Every path that takes a region, which must not panic whatever the bytes
+fn decode_all(bytes: &[u8]) -> Result<(), String> {
+    let data = decode::decode(bytes);
+    let sensors = decode::dump_sensors(bytes);
+    let readings = decode::dump_readings(bytes, Some("temp"));
+    assert_eq!(data.is_ok(), sensors.is_ok());
+    assert_eq!(data.is_ok(), readings.is_ok());
+    data.map(|_| ())
+}
+
+#[test]
+fn rejects_huge_counts_without_allocating() {
+    for (field, value) in [
+        (SENSOR_COUNT, u32::MAX),
+        (READING_COUNT, u32::MAX),
+        (SENSOR_SECTION_SIZE, u32::MAX),
+        (READING_SECTION_SIZE, u32::MAX),
+        (SENSOR_SECTION_OFFSET, u32::MAX),
+    ] {
+        let mut image = image();
+        patch(&mut image, field, value);
+        let started = Instant::now();
+        let error = decode_all(&image).unwrap_err();
+        assert!(error.contains("HWiNFO"), "{}", error);
+        // Rejected from the header alone, not by walking four billion entries
+        assert!(started.elapsed().as_secs() < 1);
+    }
+}
+
+#[test]
+fn rejects_entries_smaller_than_the_structs() {
+    let mut image = image();
+    patch(&mut image, READING_SECTION_SIZE, 8);
+    let error = decode_all(&image).unwrap_err();
+    assert!(error.contains("reading entries are 8 bytes"), "{}", error);
+}
+
+#[test]
+fn survives_every_truncation() {
+    let image = image();
+    for len in 0..image.len() {
+        assert!(decode_all(&image[..len]).is_err(), "accepted {} of {} bytes", len, image.len());
+    }
+    decode_all(&image).unwrap();
+}
+
+#[test]
+fn survives_random_corruption() {
+    let mut seeds = vec![image()];
+    let golden = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/golden");
+    for entry in std::fs::read_dir(golden).unwrap() {
+        let path = entry.unwrap().path();
+        if path.to_string_lossy().ends_with(".snapshot.json") {
+            let snapshot = snapshot::Snapshot::load(&path).unwrap();
+            seeds.extend(snapshot.frames.into_iter().filter_map(|f| f.region));
+        }
+    }
+
+    let mut rng = Rng::new(47);
+    for seed in &seeds {
+        for _ in 0..200 {
+            let mut bytes = seed.clone();
+            let flips = 1 + (rng.next_f64() * 16.0) as usize;
+            for _ in 0..flips {
+                let at = (rng.next_f64() * bytes.len() as f64) as usize;
+                bytes[at] = (rng.next_f64() * 256.0) as u8;
+            }
+            let _ = decode_all(&bytes);
+        }
+    }
+}
+
+#[test]
+fn survives_unbalanced_drive_letters() {
+    let image = ImageBuilder::new()
+        .sensor("S.M.A.R.T.: odd] name [C:")
+        .reading(ReadingType::Temp, "Drive Temperature", "°C", 40.0)
+        .sensor("S.M.A.R.T.: WD Blue ]x[")
+        .reading(ReadingType::Temp, "Drive Temperature", "°C", 35.0)
+        .build();
+    let data = decode::decode(&image).unwrap();
+    let letters: Vec<_> = data.drives.iter().map(|d| d.drive_letter.clone()).collect();
+    assert_eq!(letters, [None, None]);
+}
+
+#[test]
+fn dumps_sensors_and_filtered_readings() {
+    let image = image();
+    let dump = decode::dump_sensors(&image).unwrap();
+    assert_eq!(dump.header.signature, "0x53695748");
+    assert_eq!(dump.header.sensor_count, 3);
+    let names: Vec<_> = dump.sensors.iter().map(|s| s.name_original.as_str()).collect();
+    assert_eq!(names[1], "GPU [#0]: NVIDIA GeForce RTX 4080");
+
+    let readings = decode::dump_readings(&image, Some("TEMPERATURE")).unwrap();
+    let labels: Vec<_> = readings.iter().map(|r| (r.index, r.label_original.as_str())).collect();
+    assert_eq!(labels, [(2, "GPU Hot Spot Temperature"), (3, "Drive Temperature")]);
+    assert_eq!(decode::dump_readings(&image, None).unwrap().len(), 5);
+}
@@ -0,0 +1,160 @@
+use chrono::{DateTime, Duration, Utc};
+use instrument_panel_lib::delta::{self, Change, Encoder, Key, Update};
+use instrument_panel_lib::hwinfo::mock::{MockGenerator, SteppedClock};
+use instrument_panel_lib::hwinfo::scenario;
+use instrument_panel_lib::hwinfo::types::{Diagnostics, SensorData};
+use serde_json::{json, Value};
+
+fn clock() -> Box<SteppedClock> {
+    let start = DateTime::parse_from_rfc3339("2025-06-01T12:00:00Z").unwrap().with_timezone(&Utc);
+    Box::new(SteppedClock::new(start, Duration::seconds(1)))
+}
+
+fn field(name: &str) -> Key {
+    Key::Field(name.to_string())
+}
+
+/// What a client holds after applying every update, and the updates' size
+fn replay(updates: &[Update]) -> (Value, usize) {
+    let mut client = Value::Null;
+    let mut bytes = 0;
+    for update in updates {
+        bytes += serde_json::to_string(update).unwrap().len();
+        match update {
+            Update::Snapshot { data, .. } => client = data.clone(),
+            Update::Delta { changes, .. } => delta::apply(&mut client, changes),
+        }
+    }
+    (client, bytes)
+}
+
+#[test]
+fn deltas_rebuild_every_reading() {
+    for id in ["idle", "gaming", "dying-ssd", "hwinfo-crash"] {
+        let mut generator = MockGenerator::with_scenario(scenario::load(id).unwrap(), clock());
+        let mut encoder = Encoder::default();
+        let mut updates = Vec::new();
+        let mut full_bytes = 0;
+
+        for i in 0..200 {
+            let data = generator.next_reading().unwrap_or_else(|message| {
+                SensorData::not_connected(Diagnostics {
+                    hwinfo_process_detected: false,
+                    shared_memory_detected: false,
+                    message: Some(message),
+                    source: Some("mock".to_string()),
+                    provenance: Default::default(),
+                })
+            });
+            updates.push(encoder.push(&data));
+            assert_eq!(updates.last().unwrap().seq(), i + 1);
+            full_bytes += serde_json::to_string(&data).unwrap().len();
+
+            let (client, _) = replay(&updates);
+            assert_eq!(client, serde_json::to_value(&data).unwrap(), "{} at poll {}", id, i);
+        }
+
+        // The mock changes every value on every poll; what is saved is the static text
+        let (_, delta_bytes) = replay(&updates);
+        assert!(delta_bytes < full_bytes, "{}: {} bytes of deltas vs {} of readings", id, delta_bytes, full_bytes);
+    }
+}
+
+/// A mock reading as the frontend receives it
+fn reading() -> Value {
+    serde_json::to_value(MockGenerator::new(clock(), 50).next_reading().unwrap()).unwrap()
+}
+
+#[test]
+fn unchanged_fields_are_not_sent() {
+    let old = reading();
+    let mut new = old.clone();
+    new["cpu"]["packageTempC"] = json!(91.5);
+    new["drives"][1]["tempC"] = json!(48.0);
+    assert_eq!(
+        delta::diff(&old, &new),
+        [
+            Change { path: vec![field("cpu"), field("packageTempC")], value: json!(91.5), removed: false },
+            Change { path: vec![field("drives"), Key::Index(1), field("tempC")], value: json!(48.0), removed: false },
+        ]
+    );
+    assert!(delta::diff(&new, &new).is_empty());
+}
+
+#[test]
+fn mostly_changed_lists_are_sent_whole() {
+    let old = reading();
+    let mut new = old.clone();
+    let temps = new["cpu"]["coreTemps"].as_array_mut().unwrap();
+    assert!(temps.len() > 1);
+    for temp in temps.iter_mut() {
+        *temp = json!(temp.as_f64().unwrap() + 1.0);
+    }
+    assert_eq!(
+        delta::diff(&old, &new),
+        [Change { path: vec![field("cpu"), field("coreTemps")], value: new["cpu"]["coreTemps"].clone(), removed: false }]
+    );
+}
+
+#[test]
+fn resized_lists_and_removed_keys() {
+    let mut old = reading();
+    old["diagnostics"]["provenance"] = json!({
+        "cpu.packageTempC": "hwinfo",
+        "gpu.hotspotTempC": "hwinfo",
+        "gpu.memoryJunctionTempC": "hwinfo",
+        "system.uptimeSeconds": "system_info",
+    });
+    let mut new = old.clone();
+    new["drives"].as_array_mut().unwrap().pop();
+    new["diagnostics"]["provenance"].as_object_mut().unwrap().remove("system.uptimeSeconds");
+
+    let changes = delta::diff(&old, &new);
+    assert_eq!(
+        changes,
+        [
+            Change { path: vec![field("diagnostics"), field("provenance"), field("system.uptimeSeconds")], value: Value::Null, removed: true },
+            Change { path: vec![field("drives")], value: new["drives"].clone(), removed: false },
+        ]
+    );
+
+    let mut client = old.clone();
+    delta::apply(&mut client, &changes);
+    assert_eq!(client, new);
+}
+
+#[test]
+fn wire_format() {
+    let update = Update::Delta {
+        seq: 7,
+        changes: vec![
+            Change { path: vec![field("drives"), Key::Index(0), field("tempC")], value: json!(41.0), removed: false },
+            Change { path: vec![field("provenance"), field("cpu.name")], value: Value::Null, removed: true },
+        ],
+    };
+    let json = serde_json::to_string(&update).unwrap();
+    assert_eq!(
+        json,
+        r#"{"type":"delta","seq":7,"changes":[{"path":["drives",0,"tempC"],"value":41.0},{"path":["provenance","cpu.name"],"value":null,"removed":true}]}"#
+    );
+    assert_eq!(serde_json::from_str::<Update>(&json).unwrap(), update);
+}
+
+#[test]
+fn resync_snapshot_matches_the_latest_update() {
+    let mut generator = MockGenerator::new(clock(), 50);
+    let mut encoder = Encoder::default();
+    assert_eq!(encoder.snapshot(), None);
+
+    let mut last = None;
+    for _ in 0..5 {
+        last = Some(generator.next_reading().unwrap());
+        encoder.push(last.as_ref().unwrap());
+    }
+    let Some(Update::Snapshot { seq, data }) = encoder.snapshot() else { panic!("no snapshot") };
+    assert_eq!(seq, 5);
+    assert_eq!(data, serde_json::to_value(last.unwrap()).unwrap());
+    // Deltas carry on from the snapshot
+    let next = encoder.push(&generator.next_reading().unwrap());
+    assert!(matches!(next, Update::Delta { seq: 6, .. }));
+}
@@ -0,0 +1,100 @@
+//! Golden tests for the HWiNFO decoder.
+//!
+//! Every `<rig>.snapshot.json` in tests/fixtures/golden (a file in the format
+//! `capture_snapshot` writes) is decoded frame by frame and compared with
+//! `<rig>.expected.json`. A failure lists each rig whose output changed and
+//! the fields that differ. After an intended mapping change, regenerate the
+//! expected files with `UPDATE_GOLDEN=1 cargo test --test golden` and review
+//! the diff.
+//!
+//! The rigs checked in so far are synthetic, written in that format from
+//! typical sensor names and values rather than captured on the machines, so
+//! they catch changes to the mapping but not mistakes in it. Real captures
+//! are still outstanding; fixtures/golden/README.md lists which.
+
+use instrument_panel_lib::hwinfo::snapshot::Snapshot;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn fixtures() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/golden")
+}
+
+/// Each frame's reading, or its error, without the time it was decoded
+fn decode(snapshot: &Snapshot) -> Value {
+    (0..snapshot.frames.len())
+        .map(|i| match snapshot.replay(i) {
+            Ok(mut data) => {
+                data.last_read_at = None;
+                serde_json::to_value(data).unwrap()
+            }
+            Err(e) => json!({ "error": e }),
+        })
+        .collect()
+}
+
+/// "path: expected …, got …" for every leaf that differs
+fn diff(path: &str, expected: &Value, actual: &Value, out: &mut Vec<String>) {
+    match (expected, actual) {
+        (Value::Object(e), Value::Object(a)) => {
+            let mut keys: Vec<&String> = e.keys().chain(a.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let (e, a) = (e.get(key).unwrap_or(&Value::Null), a.get(key).unwrap_or(&Value::Null));
+                diff(&format!("{}.{}", path, key), e, a, out);
+            }
+        }
+        (Value::Array(e), Value::Array(a)) if e.len() == a.len() => {
+            for (i, (e, a)) in e.iter().zip(a).enumerate() {
+                diff(&format!("{}[{}]", path, i), e, a, out);
+            }
+        }
+        (e, a) if e != a => out.push(format!("{}: expected {}, got {}", path, e, a)),
+        _ => {}
+    }
+}
+
+#[test]
+fn rigs_decode_as_recorded() {
+    let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+    let mut rigs: Vec<(String, PathBuf)> = fs::read_dir(fixtures())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter_map(|path| {
+            let rig = path.file_name()?.to_str()?.strip_suffix(".snapshot.json")?.to_string();
+            Some((rig, path))
+        })
+        .collect();
+    rigs.sort();
+    assert!(rigs.len() >= 5, "golden corpus is missing: {:?}", rigs);
+
+    let mut changed = Vec::new();
+    for (rig, path) in &rigs {
+        let actual = decode(&Snapshot::load(path).unwrap());
+        let expected_path = fixtures().join(format!("{}.expected.json", rig));
+        if update {
+            fs::write(&expected_path, serde_json::to_string_pretty(&actual).unwrap() + "\n").unwrap();
+            continue;
+        }
+
+        let Ok(expected) = fs::read_to_string(&expected_path) else {
+            changed.push(format!("{}: no {}.expected.json; run with UPDATE_GOLDEN=1", rig, rig));
+            continue;
+        };
+        let expected: Value = serde_json::from_str(&expected).unwrap();
+        let mut fields = Vec::new();
+        diff("", &expected, &actual, &mut fields);
+        if !fields.is_empty() {
+            changed.push(format!("{}:\n    {}", rig, fields.join("\n    ")));
+        }
+    }
+    assert!(
+        changed.is_empty(),
+        "{} of {} rigs decode differently:\n  {}",
+        changed.len(),
+        rigs.len(),
+        changed.join("\n  ")
+    );
+}
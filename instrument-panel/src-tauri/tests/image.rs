@@ -0,0 +1,116 @@
+use instrument_panel_lib::hwinfo::decode::{self, Region};
+use instrument_panel_lib::hwinfo::image::{self, ImageBuilder};
+use instrument_panel_lib::hwinfo::mock::{MockGenerator, SystemClock};
+use instrument_panel_lib::hwinfo::scenario::{self, Rng};
+use instrument_panel_lib::hwinfo::types::ReadingType;
+
+#[test]
+fn builds_a_valid_region() {
+    let image = ImageBuilder::new()
+        .version(2, 3)
+        .poll_time(123_456)
+        .sensor("CPU [#0]: AMD Ryzen 7 7800X3D")
+        .reading(ReadingType::Temp, "CPU (Tctl/Tdie)", "°C", 61.5)
+        .stats(38.0, 88.1, 55.2)
+        .reading(ReadingType::Power, "CPU PPT", "W", 72.0)
+        .sensor("Nuvoton NCT6799D")
+        .reading(ReadingType::Fan, "CPU Fan", "RPM", 1150.0)
+        .build();
+
+    let region = Region::new(&image).unwrap();
+    let header = region.header();
+    let (version, revision, poll_time) = (header.version, header.revision, header.poll_time);
+    assert_eq!((version, revision, poll_time), (2, 3, 123_456));
+    let (sensor_count, reading_count) = (header.sensor_count, header.reading_count);
+    assert_eq!((sensor_count, reading_count), (2, 3));
+    assert_eq!(image.len(), 44 + 2 * 264 + 3 * 316);
+
+    let readings: Vec<_> = region.readings().collect();
+    let (sensor_index, value, min, max, avg) = (
+        readings[0].sensor_index,
+        readings[0].value,
+        readings[0].value_min,
+        readings[0].value_max,
+        readings[0].value_avg,
+    );
+    assert_eq!((sensor_index, value, min, max, avg), (0, 61.5, 38.0, 88.1, 55.2));
+    let (fan_sensor, fan_type) = (readings[2].sensor_index, readings[2].reading_type);
+    assert_eq!((fan_sensor, fan_type), (1, ReadingType::Fan as u32));
+
+    let data = decode::decode(&image).unwrap();
+    assert_eq!(data.cpu.package_temp_c, Some(61.5));
+    assert_eq!(data.system.fans[0].name, "CPU Fan");
+}
+
+#[test]
+fn honours_longer_entries() {
+    let image = ImageBuilder::new()
+        .padding(64, 128)
+        .sensor("CPU [#0]: Intel Core i9-14900K")
+        .reading(ReadingType::Temp, "CPU Package", "°C", 70.0)
+        .reading(ReadingType::Usage, "Total CPU Usage", "%", 12.0)
+        .build();
+    let header = Region::new(&image).unwrap().header();
+    let (sensor_size, reading_size) = (header.sensor_section_size, header.reading_section_size);
+    assert_eq!((sensor_size, reading_size), (264 + 64, 316 + 128));
+
+    let data = decode::decode(&image).unwrap();
+    assert_eq!(data.cpu.package_temp_c, Some(70.0));
+    assert_eq!(data.cpu.usage_percent, Some(12.0));
+}
+
+#[test]
+fn cuts_long_labels() {
+    let label = "x".repeat(200);
+    let image = ImageBuilder::new()
+        .sensor(&label)
+        .reading(ReadingType::Fan, &label, "RPM", 900.0)
+        .build();
+    let data = decode::decode(&image).unwrap();
+    assert_eq!(data.system.fans[0].name, "x".repeat(127));
+}
+
+#[test]
+fn mock_readings_survive_the_decoder() {
+    let data = MockGenerator::new(Box::new(SystemClock), 0).next_reading().unwrap();
+    let decoded = image::round_trip(&data).unwrap();
+
+    assert_eq!(decoded.cpu.name, data.cpu.name);
+    assert_eq!(decoded.cpu.package_temp_c, data.cpu.package_temp_c);
+    assert_eq!(decoded.cpu.package_power_w, data.cpu.package_power_w);
+    assert_eq!(decoded.cpu.core_clock_mhz, data.cpu.core_clock_mhz);
+    assert_eq!(decoded.cpu.usage_percent, data.cpu.usage_percent);
+    assert_eq!(decoded.cpu.core_temps, data.cpu.core_temps);
+    assert_eq!(decoded.gpu.name.as_deref(), Some("GPU [#0]: NVIDIA GeForce RTX 5070"));
+    assert_eq!(decoded.gpu.hotspot_temp_c, data.gpu.hotspot_temp_c);
+    assert_eq!(decoded.gpu.vram_total_mb, data.gpu.vram_total_mb);
+    assert_eq!(decoded.gpu.fan_speed_percent, data.gpu.fan_speed_percent);
+    assert_eq!(decoded.system.fans.len(), data.system.fans.len());
+    assert_eq!(decoded.system.uptime_seconds, data.system.uptime_seconds);
+    assert_eq!(decoded.diagnostics.source.as_deref(), Some("mock"));
+
+    assert_eq!(decoded.drives.len(), 2);
+    for (decoded, original) in decoded.drives.iter().zip(&data.drives) {
+        assert_eq!(decoded.name, original.name);
+        assert_eq!(decoded.drive_letter, original.drive_letter);
+        assert_eq!(decoded.temp_c, original.temp_c);
+        assert_eq!(decoded.smart_health, original.smart_health);
+        assert_eq!(decoded.free_gb, original.free_gb);
+    }
+    assert_eq!(decoded.storage.name, data.storage.name);
+}
+
+#[test]
+fn scenarios_survive_the_decoder() {
+    for (id, scenario) in scenario::bundled() {
+        let mut rng = Rng::new(scenario.seed);
+        for t in (0..300).step_by(10) {
+            let Ok(data) = scenario.sample(t as f64, &mut rng) else { continue };
+            let decoded = image::round_trip(&data).unwrap();
+            assert_eq!(decoded.cpu.package_temp_c, data.cpu.package_temp_c, "{} at {}s", id, t);
+            for (decoded, original) in decoded.drives.iter().zip(&data.drives) {
+                assert_eq!(decoded.smart_health, original.smart_health, "{} at {}s", id, t);
+            }
+        }
+    }
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use instrument_panel_lib::lhm::{self, HardwareKind};
+
+const DATA_JSON: &str = include_str!("fixtures/lhm/data.json");
+
+/// Stands in for LibreHardwareMonitor's web server. Returns the base URL.
+fn serve(status: &'static str, body: &'static str) -> String {
+    common::serve("application/json", status, body)
+}
+
+#[test]
+fn maps_recorded_tree_served_over_http() {
+    let url = format!("{}/data.json", serve("200 OK", DATA_JSON));
+    let root = lhm::fetch(&lhm::agent(), &url).unwrap();
+    let mut data = common::reading("lhm");
+    lhm::apply(&root, &mut data);
+
+    assert_eq!(data.system.name.as_deref(), Some("DESKTOP-RIG"));
+
+    let cpu = &data.cpu;
+    assert_eq!(cpu.name.as_deref(), Some("AMD Ryzen 9 5900X"));
+    assert_eq!(cpu.package_temp_c, Some(61.4));
+    assert_eq!(cpu.core_temps, vec![58.3, 55.0]);
+    assert_eq!(cpu.package_power_w, Some(88.6));
+    assert_eq!(cpu.core_clocks_mhz, vec![4550.0, 4350.0]);
+    assert_eq!(cpu.core_clock_mhz, Some(4450.0));
+    assert_eq!(cpu.usage_percent, Some(23.7));
+    assert_eq!(cpu.core_usage_percent, vec![41.2, 6.2]);
+
+    // The discrete card wins over the integrated one listed first
+    let gpu = &data.gpu;
+    assert_eq!(gpu.name.as_deref(), Some("NVIDIA GeForce RTX 3080"));
+    assert_eq!(gpu.core_temp_c, Some(66.0));
+    assert_eq!(gpu.hotspot_temp_c, Some(78.6));
+    assert_eq!(gpu.memory_junction_temp_c, Some(84.0));
+    assert_eq!(gpu.power_w, Some(318.2));
+    assert_eq!(gpu.core_clock_mhz, Some(1905.0));
+    assert_eq!(gpu.memory_clock_mhz, Some(9251.0));
+    assert_eq!(gpu.usage_percent, Some(97.0));
+    assert_eq!(gpu.vram_used_mb, Some(7144.0));
+    assert_eq!(gpu.vram_total_mb, Some(10240.0));
+    assert_eq!(gpu.fan_speed_rpm, Some(1650.0));
+    assert_eq!(gpu.fan_speed_percent, Some(58.0));
+
+    assert_eq!(data.drives.len(), 1);
+    assert_eq!(data.drives[0].name.as_deref(), Some("Samsung SSD 980 PRO 1TB"));
+    assert_eq!(data.drives[0].temp_c, Some(47.0));
+    assert_eq!(data.storage.smart_health, "good");
+
+    assert_eq!(data.system.memory_used_mb, Some(12.5 * 1024.0));
+    assert_eq!(data.system.memory_total_mb, Some(32.0 * 1024.0));
+
+    // The stopped header is not reported; the GPU fan belongs to the GPU
+    let fans: Vec<(&str, f64)> = data.system.fans.iter().map(|f| (f.name.as_str(), f.rpm)).collect();
+    assert_eq!(fans, [("Fan #2", 1187.0), ("Fan #3", 702.0)]);
+    assert_eq!(data.system.fan_status, "ok");
+}
+
+#[test]
+fn sub_hardware_is_listed_separately() {
+    let root = lhm::parse(DATA_JSON).unwrap();
+    let kinds: Vec<(String, HardwareKind)> = lhm::hardware(&root).into_iter().map(|h| (h.name, h.kind)).collect();
+    assert_eq!(kinds, [
+        ("Nuvoton NCT6798D".to_string(), HardwareKind::SuperIo),
+        ("AMD Ryzen 9 5900X".to_string(), HardwareKind::Cpu),
+        ("Generic Memory".to_string(), HardwareKind::Memory),
+        ("AMD Radeon(TM) Graphics".to_string(), HardwareKind::Gpu),
+        ("NVIDIA GeForce RTX 3080".to_string(), HardwareKind::Gpu),
+        ("Samsung SSD 980 PRO 1TB".to_string(), HardwareKind::Storage),
+    ]);
+}
+
+#[test]
+fn values_in_other_locales_and_older_versions() {
+    assert_eq!(lhm::parse_value("45,3 °C"), Some((45.3, "°C".to_string())));
+    assert_eq!(lhm::parse_value("4,450.0 MHz"), Some((4450.0, "MHz".to_string())));
+    assert_eq!(lhm::parse_value("1187 RPM"), Some((1187.0, "RPM".to_string())));
+    assert_eq!(lhm::parse_value("-"), None);
+
+    // 0.8 has no SensorId/Type: kinds come from the group name and the icon
+    let json = r#"{"Text":"Sensor","Children":[{"Text":"PC","Children":[
+        {"Text":"Intel Core i7-12700K","ImageURL":"images_icon/cpu.png","Children":[
+            {"Text":"Temperatures","Children":[
+                {"Text":"CPU Package","Value":"71,0 °C","Children":[]},
+                {"Text":"Core #1","Value":"68,0 °C","Children":[]}]},
+            {"Text":"Powers","Children":[
+                {"Text":"CPU Package","Value":"124,5 W","Children":[]},
+                {"Text":"CPU Cores","Value":"110,2 W","Children":[]}]}]}]}]}"#;
+    let mut data = common::reading("lhm");
+    lhm::apply(&lhm::parse(json).unwrap(), &mut data);
+    assert_eq!(data.cpu.package_temp_c, Some(71.0));
+    assert_eq!(data.cpu.core_temps, vec![68.0]);
+    assert_eq!(data.cpu.package_power_w, Some(124.5));
+    assert_eq!(data.cpu.core_power_w, Some(110.2));
+}
+
+#[test]
+fn http_errors_are_reported() {
+    let url = format!("{}/data.json", serve("404 Not Found", "not found"));
+    let err = lhm::fetch(&lhm::agent(), &url).unwrap_err();
+    assert!(err.contains("HTTP 404"), "{}", err);
+
+    let url = format!("{}/data.json", serve("200 OK", "<html></html>"));
+    let err = lhm::fetch(&lhm::agent(), &url).unwrap_err();
+    assert!(err.starts_with("Invalid LibreHardwareMonitor JSON"), "{}", err);
+}
@@ -0,0 +1,77 @@
+#![cfg(target_os = "linux")]
+
+mod common;
+
+use common::write_tree;
+use instrument_panel_lib::hwinfo::types::CpuData;
+use instrument_panel_lib::linux::{cpufreq, thermal};
+
+#[test]
+fn per_core_and_average_clocks() {
+    let dir = tempfile::tempdir().unwrap();
+    write_tree(dir.path(), &[
+        ("devices/system/cpu/cpu0/cpufreq/scaling_cur_freq", "4700000"),
+        ("devices/system/cpu/cpu0/cpufreq/cpuinfo_max_freq", "5400000"),
+        ("devices/system/cpu/cpu1/cpufreq/scaling_cur_freq", "3900000"),
+        ("devices/system/cpu/cpu1/cpufreq/cpuinfo_max_freq", "5400000"),
+        ("devices/system/cpu/cpu10/cpufreq/scaling_cur_freq", "800000"),
+        ("devices/system/cpu/cpu10/cpufreq/cpuinfo_max_freq", "4200000"),
+        // Not CPUs
+        ("devices/system/cpu/cpufreq/boost", "1"),
+        ("devices/system/cpu/cpuidle/current_driver", "intel_idle"),
+        // Offline CPU without a cpufreq policy
+        ("devices/system/cpu/cpu11/online", "0"),
+    ]);
+
+    let frequencies = cpufreq::read_frequencies(dir.path());
+    let cpus: Vec<u32> = frequencies.iter().map(|f| f.cpu).collect();
+    assert_eq!(cpus, [0, 1, 10]);
+
+    let mut cpu = CpuData::default();
+    cpufreq::apply(&frequencies, &mut cpu);
+    assert_eq!(cpu.core_clocks_mhz, vec![4700.0, 3900.0, 800.0]);
+    assert_eq!(cpu.core_clock_mhz, Some(3133.3333333333335));
+    assert_eq!(cpu.max_clock_mhz, Some(5400.0));
+}
+
+#[test]
+fn thermal_zone_is_only_a_fallback() {
+    let dir = tempfile::tempdir().unwrap();
+    write_tree(dir.path(), &[
+        ("class/thermal/thermal_zone0/type", "acpitz"),
+        ("class/thermal/thermal_zone0/temp", "27800"),
+        ("class/thermal/thermal_zone1/type", "iwlwifi_1"),
+        ("class/thermal/thermal_zone1/temp", "41000"),
+        ("class/thermal/thermal_zone2/type", "x86_pkg_temp"),
+        ("class/thermal/thermal_zone2/temp", "58000"),
+        ("class/thermal/cooling_device0/type", "Processor"),
+    ]);
+
+    let zones = thermal::read_zones(dir.path());
+    assert_eq!(zones.len(), 3);
+    assert_eq!(thermal::cpu_zone(&zones).map(|z| z.kind.as_str()), Some("x86_pkg_temp"));
+
+    let mut cpu = CpuData::default();
+    thermal::apply(&zones, &mut cpu);
+    assert_eq!(cpu.package_temp_c, Some(58.0));
+
+    let mut from_hwmon = CpuData {
+        package_temp_c: Some(61.0),
+        ..CpuData::default()
+    };
+    thermal::apply(&zones, &mut from_hwmon);
+    assert_eq!(from_hwmon.package_temp_c, Some(61.0));
+}
+
+#[test]
+fn arm_board_cpu_zone() {
+    let dir = tempfile::tempdir().unwrap();
+    write_tree(dir.path(), &[
+        ("class/thermal/thermal_zone0/type", "cpu-thermal"),
+        ("class/thermal/thermal_zone0/temp", "49173"),
+    ]);
+
+    let mut cpu = CpuData::default();
+    thermal::apply(&thermal::read_zones(dir.path()), &mut cpu);
+    assert_eq!(cpu.package_temp_c, Some(49.173));
+}
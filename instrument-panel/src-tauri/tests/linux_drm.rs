@@ -0,0 +1,93 @@
+#![cfg(target_os = "linux")]
+
+mod common;
+
+use common::write_tree;
+use instrument_panel_lib::hwinfo::types::GpuData;
+use instrument_panel_lib::linux::drm;
+use std::path::Path;
+
+/// Ryzen APU (card0) plus a discrete Radeon (card1) and an NVIDIA card (card2)
+fn apu_with_discrete_card(root: &Path) {
+    write_tree(root, &[
+        ("class/drm/card0/device/vendor", "0x1002"),
+        ("class/drm/card0/device/gpu_busy_percent", "3"),
+        ("class/drm/card0/device/mem_info_vram_used", "134217728"),
+        ("class/drm/card0/device/mem_info_vram_total", "536870912"),
+        ("class/drm/card0/device/hwmon/hwmon3/name", "amdgpu"),
+        ("class/drm/card0/device/hwmon/hwmon3/temp1_input", "45000"),
+        ("class/drm/card0/device/hwmon/hwmon3/temp1_label", "edge"),
+        ("class/drm/card1/device/vendor", "0x1002"),
+        ("class/drm/card1/device/product_name", "AMD Radeon RX 7900 XTX"),
+        ("class/drm/card1/device/gpu_busy_percent", "87"),
+        ("class/drm/card1/device/mem_info_vram_used", "8589934592"),
+        ("class/drm/card1/device/mem_info_vram_total", "25753026560"),
+        ("class/drm/card1/device/pp_dpm_sclk", "0: 500Mhz\n1: 2498Mhz *\n2: 2680Mhz"),
+        ("class/drm/card1/device/pp_dpm_mclk", "0: 96Mhz\n1: 456Mhz\n2: 1249Mhz *"),
+        ("class/drm/card1/device/hwmon/hwmon5/name", "amdgpu"),
+        ("class/drm/card1/device/hwmon/hwmon5/temp1_input", "62000"),
+        ("class/drm/card1/device/hwmon/hwmon5/temp1_label", "edge"),
+        ("class/drm/card1/device/hwmon/hwmon5/temp2_input", "81000"),
+        ("class/drm/card1/device/hwmon/hwmon5/temp2_label", "junction"),
+        ("class/drm/card1/device/hwmon/hwmon5/temp3_input", "76000"),
+        ("class/drm/card1/device/hwmon/hwmon5/temp3_label", "mem"),
+        ("class/drm/card1/device/hwmon/hwmon5/power1_average", "310000000"),
+        ("class/drm/card1/device/hwmon/hwmon5/fan1_input", "1650"),
+        ("class/drm/card1/device/hwmon/hwmon5/pwm1", "153"),
+        ("class/drm/card1-DP-1/status", "connected"),
+        ("class/drm/card2/device/vendor", "0x10de"),
+        ("class/drm/renderD128/dev", "226:128"),
+    ]);
+}
+
+#[test]
+fn reads_only_amd_cards() {
+    let dir = tempfile::tempdir().unwrap();
+    apu_with_discrete_card(dir.path());
+
+    let cards = drm::read_cards(dir.path());
+    let names: Vec<&str> = cards.iter().map(|c| c.card.as_str()).collect();
+    assert_eq!(names, ["card0", "card1"]);
+    assert_eq!(cards[1].core_clock_mhz, Some(2498.0));
+    assert_eq!(cards[1].memory_clock_mhz, Some(1249.0));
+    assert_eq!(cards[0].core_clock_mhz, None);
+}
+
+#[test]
+fn fills_gpu_data_from_discrete_card() {
+    let dir = tempfile::tempdir().unwrap();
+    apu_with_discrete_card(dir.path());
+
+    let mut gpu = GpuData::default();
+    drm::apply(&drm::read_cards(dir.path()), &mut gpu);
+
+    assert_eq!(gpu.name.as_deref(), Some("AMD Radeon RX 7900 XTX"));
+    assert_eq!(gpu.usage_percent, Some(87.0));
+    assert_eq!(gpu.vram_used_mb, Some(8192.0));
+    assert_eq!(gpu.vram_total_mb, Some(24560.0));
+    assert_eq!(gpu.core_clock_mhz, Some(2498.0));
+    assert_eq!(gpu.memory_clock_mhz, Some(1249.0));
+    assert_eq!(gpu.core_temp_c, Some(62.0));
+    assert_eq!(gpu.hotspot_temp_c, Some(81.0));
+    assert_eq!(gpu.memory_junction_temp_c, Some(76.0));
+    assert_eq!(gpu.power_w, Some(310.0));
+    assert_eq!(gpu.fan_speed_rpm, Some(1650.0));
+    assert_eq!(gpu.fan_speed_percent, Some(60.0));
+}
+
+#[test]
+fn unnamed_card_gets_a_placeholder_name() {
+    let dir = tempfile::tempdir().unwrap();
+    write_tree(dir.path(), &[("class/drm/card0/device/vendor", "0x1002")]);
+
+    let mut gpu = GpuData::default();
+    drm::apply(&drm::read_cards(dir.path()), &mut gpu);
+    assert_eq!(gpu.name.as_deref(), Some("AMD Radeon (card0)"));
+    assert_eq!(gpu.usage_percent, None);
+}
+
+#[test]
+fn parses_active_dpm_level() {
+    assert_eq!(drm::parse_dpm_active("0: 300Mhz *\n1: 1200Mhz\n"), Some(300.0));
+    assert_eq!(drm::parse_dpm_active("0: 300Mhz\n1: 1200Mhz\n"), None);
+}
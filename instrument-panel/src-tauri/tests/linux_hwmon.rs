@@ -0,0 +1,138 @@
+#![cfg(target_os = "linux")]
+
+mod common;
+
+use common::write_tree;
+use instrument_panel_lib::linux::hwmon::{self, ChannelKind};
+use std::path::Path;
+
+fn amd_desktop(root: &Path) {
+    write_tree(root, &[
+        ("class/hwmon/hwmon0/name", "nvme"),
+        ("class/hwmon/hwmon0/device/model", "Samsung SSD 980 PRO 1TB"),
+        ("class/hwmon/hwmon0/temp1_input", "41850"),
+        ("class/hwmon/hwmon0/temp1_label", "Composite"),
+        ("class/hwmon/hwmon0/temp2_input", "52850"),
+        ("class/hwmon/hwmon0/temp2_label", "Sensor 1"),
+        ("class/hwmon/hwmon1/name", "k10temp"),
+        ("class/hwmon/hwmon1/temp1_input", "61250"),
+        ("class/hwmon/hwmon1/temp1_label", "Tctl"),
+        ("class/hwmon/hwmon1/temp3_input", "55000"),
+        ("class/hwmon/hwmon1/temp3_label", "Tccd1"),
+        ("class/hwmon/hwmon1/temp4_input", "57500"),
+        ("class/hwmon/hwmon1/temp4_label", "Tccd2"),
+        ("class/hwmon/hwmon2/name", "amdgpu"),
+        ("class/hwmon/hwmon2/temp1_input", "48000"),
+        ("class/hwmon/hwmon2/temp1_label", "edge"),
+        ("class/hwmon/hwmon2/temp2_input", "63000"),
+        ("class/hwmon/hwmon2/temp2_label", "junction"),
+        ("class/hwmon/hwmon2/temp3_input", "70000"),
+        ("class/hwmon/hwmon2/temp3_label", "mem"),
+        ("class/hwmon/hwmon2/power1_average", "187000000"),
+        ("class/hwmon/hwmon2/power1_label", "PPT"),
+        ("class/hwmon/hwmon2/freq1_input", "2450000000"),
+        ("class/hwmon/hwmon2/freq1_label", "sclk"),
+        ("class/hwmon/hwmon2/freq2_input", "1000000000"),
+        ("class/hwmon/hwmon2/freq2_label", "mclk"),
+        ("class/hwmon/hwmon2/fan1_input", "1450"),
+        ("class/hwmon/hwmon2/pwm1", "102"),
+        ("class/hwmon/hwmon2/in0_input", "1050"),
+        ("class/hwmon/hwmon2/in0_label", "vddgfx"),
+        ("class/hwmon/hwmon3/name", "nct6798"),
+        ("class/hwmon/hwmon3/fan1_input", "0"),
+        ("class/hwmon/hwmon3/fan2_input", "1180"),
+        ("class/hwmon/hwmon3/fan2_label", "CPU Fan"),
+        ("class/hwmon/hwmon3/fan3_input", "640"),
+        ("class/hwmon/hwmon3/in0_input", "1344"),
+    ]);
+}
+
+#[test]
+fn reads_chips_in_hwmon_order_with_converted_units() {
+    let dir = tempfile::tempdir().unwrap();
+    amd_desktop(dir.path());
+
+    let chips = hwmon::read_chips(dir.path()).unwrap();
+    let names: Vec<&str> = chips.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, ["nvme", "k10temp", "amdgpu", "nct6798"]);
+
+    let gpu = &chips[2];
+    assert_eq!(gpu.find(ChannelKind::Temp, "junction"), Some(63.0));
+    assert_eq!(gpu.find(ChannelKind::Power, "PPT"), Some(187.0));
+    assert_eq!(gpu.find(ChannelKind::Frequency, "sclk"), Some(2450.0));
+    assert_eq!(gpu.find(ChannelKind::Voltage, "vddgfx"), Some(1.05));
+}
+
+#[test]
+fn maps_amd_desktop_into_sensor_data() {
+    let dir = tempfile::tempdir().unwrap();
+    amd_desktop(dir.path());
+
+    let chips = hwmon::read_chips(dir.path()).unwrap();
+    let mut data = common::reading("linux");
+    hwmon::apply(&chips, &mut data);
+
+    assert_eq!(data.cpu.package_temp_c, Some(61.25));
+    assert_eq!(data.cpu.core_temps, vec![55.0, 57.5]);
+
+    assert_eq!(data.gpu.core_temp_c, Some(48.0));
+    assert_eq!(data.gpu.hotspot_temp_c, Some(63.0));
+    assert_eq!(data.gpu.memory_junction_temp_c, Some(70.0));
+    assert_eq!(data.gpu.power_w, Some(187.0));
+    assert_eq!(data.gpu.core_clock_mhz, Some(2450.0));
+    assert_eq!(data.gpu.memory_clock_mhz, Some(1000.0));
+    assert_eq!(data.gpu.fan_speed_rpm, Some(1450.0));
+    assert_eq!(data.gpu.fan_speed_percent, Some(40.0));
+
+    assert_eq!(data.drives.len(), 1);
+    assert_eq!(data.drives[0].name.as_deref(), Some("Samsung SSD 980 PRO 1TB"));
+    assert_eq!(data.drives[0].temp_c, Some(41.85));
+    assert_eq!(data.storage.name.as_deref(), Some("Samsung SSD 980 PRO 1TB"));
+
+    let fans: Vec<(&str, f64)> = data.system.fans.iter().map(|f| (f.name.as_str(), f.rpm)).collect();
+    assert_eq!(fans, [("CPU Fan", 1180.0), ("fan3", 640.0)]);
+    assert_eq!(data.system.fan_status, "ok");
+}
+
+#[test]
+fn maps_intel_coretemp_and_prefers_power_input() {
+    let dir = tempfile::tempdir().unwrap();
+    write_tree(dir.path(), &[
+        ("class/hwmon/hwmon4/name", "coretemp"),
+        ("class/hwmon/hwmon4/temp1_input", "72000"),
+        ("class/hwmon/hwmon4/temp1_label", "Package id 0"),
+        ("class/hwmon/hwmon4/temp2_input", "68000"),
+        ("class/hwmon/hwmon4/temp2_label", "Core 0"),
+        ("class/hwmon/hwmon4/temp6_input", "70000"),
+        ("class/hwmon/hwmon4/temp6_label", "Core 4"),
+        ("class/hwmon/hwmon10/name", "amdgpu"),
+        ("class/hwmon/hwmon10/power1_input", "95000000"),
+        ("class/hwmon/hwmon10/power1_average", "90000000"),
+        ("class/hwmon/hwmon10/temp1_input", "51000"),
+        ("class/hwmon/hwmon10/temp1_label", "edge"),
+        ("class/hwmon/hwmon9/name", "acpitz"),
+        ("class/hwmon/hwmon9/temp1_input", "27800"),
+    ]);
+
+    let chips = hwmon::read_chips(dir.path()).unwrap();
+    let names: Vec<&str> = chips.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, ["coretemp", "acpitz", "amdgpu"]);
+
+    let mut data = common::reading("linux");
+    hwmon::apply(&chips, &mut data);
+
+    assert_eq!(data.cpu.package_temp_c, Some(72.0));
+    assert_eq!(data.cpu.core_temps, vec![68.0, 70.0]);
+    assert_eq!(data.gpu.power_w, Some(95.0));
+    // No junction sensor: only the edge temperature
+    assert_eq!(data.gpu.core_temp_c, Some(51.0));
+    assert_eq!(data.gpu.hotspot_temp_c, None);
+    assert!(data.drives.is_empty());
+    assert_eq!(data.system.fan_status, "unknown");
+}
+
+#[test]
+fn missing_hwmon_class_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    assert!(hwmon::read_chips(dir.path()).is_err());
+}
@@ -0,0 +1,92 @@
+#![cfg(target_os = "linux")]
+
+mod common;
+
+use instrument_panel_lib::hwinfo::types::SensorData;
+use instrument_panel_lib::linux::hwmon::{self, ChannelKind};
+use instrument_panel_lib::linux::lm_sensors;
+
+/// `sensors -j` from lm-sensors 3.6 on an AMD desktop
+const SENSORS_JSON: &str = include_str!("fixtures/lm_sensors/sensors.json");
+
+fn mapped(json: &str) -> SensorData {
+    let chips = lm_sensors::parse(json).unwrap();
+    let mut data = common::reading("lm-sensors");
+    hwmon::apply(&chips, &mut data);
+    data.raw_readings = lm_sensors::raw_readings(&chips);
+    data
+}
+
+#[test]
+fn chips_become_hwmon_chips() {
+    let chips = lm_sensors::parse(SENSORS_JSON).unwrap();
+    let names: Vec<&str> = chips.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, ["acpitz", "k10temp", "nct6798", "nvme"]);
+
+    // Features named after the channel have no label of their own
+    let nct = &chips[2];
+    assert_eq!(nct.channels(ChannelKind::Fan).next().unwrap().label, None);
+    assert_eq!(nct.find(ChannelKind::Temp, "SYSTIN"), Some(34.0));
+}
+
+#[test]
+fn channels_keep_driver_order() {
+    let json = r#"{"coretemp-isa-0000": {"Adapter": "ISA adapter",
+        "Package id 0": {"temp1_input": 48.0, "temp1_crit": 100.0},
+        "Core 0": {"temp2_input": 45.0},
+        "Core 1": {"temp3_input": 47.0},
+        "Core 10": {"temp12_input": 44.0}}}"#;
+
+    // Not the alphabetical order of the feature names
+    let data = mapped(json);
+    assert_eq!(data.cpu.package_temp_c, Some(48.0));
+    assert_eq!(data.cpu.core_temps, vec![45.0, 47.0, 44.0]);
+    assert!(data.raw_readings.is_empty());
+}
+
+#[test]
+fn maps_cpu_drives_and_fans() {
+    let data = mapped(SENSORS_JSON);
+
+    assert_eq!(data.cpu.package_temp_c, Some(62.875));
+    assert_eq!(data.cpu.core_temps, vec![55.25, 51.0]);
+
+    assert_eq!(data.drives.len(), 1);
+    assert_eq!(data.drives[0].name.as_deref(), Some("NVMe (nvme-pci-0100)"));
+    assert_eq!(data.drives[0].temp_c, Some(41.85));
+    assert_eq!(data.storage.nvme_temp_c, Some(41.85));
+
+    let fans: Vec<(&str, f64)> = data.system.fans.iter().map(|f| (f.name.as_str(), f.rpm)).collect();
+    assert_eq!(fans, [("fan2", 1187.0), ("fan7", 702.0)]);
+    assert_eq!(data.system.fan_status, "ok");
+}
+
+#[test]
+fn unmapped_features_are_kept_as_raw_readings() {
+    let data = mapped(SENSORS_JSON);
+    let raw: Vec<(&str, &str, f64, &str)> = data
+        .raw_readings
+        .iter()
+        .map(|r| (r.group.as_str(), r.label.as_str(), r.value, r.unit.as_str()))
+        .collect();
+    assert_eq!(raw, [
+        ("acpitz-acpi-0", "temp1", 16.8, "°C"),
+        ("nct6798-isa-0290", "in0", 1.384, "V"),
+        ("nct6798-isa-0290", "SYSTIN", 34.0, "°C"),
+        ("nvme-pci-0100", "Sensor 1", 41.85, "°C"),
+    ]);
+}
+
+#[test]
+fn runs_the_configured_command() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sensors.json");
+    std::fs::write(&path, SENSORS_JSON).unwrap();
+
+    let output = lm_sensors::run(&format!("cat {}", path.display())).unwrap();
+    assert_eq!(lm_sensors::parse(&output).unwrap().len(), 4);
+
+    let err = lm_sensors::run("sensors-binary-that-does-not-exist -j").unwrap_err();
+    assert!(err.starts_with("Failed to run"), "{}", err);
+    assert!(lm_sensors::parse("not json").is_err());
+}
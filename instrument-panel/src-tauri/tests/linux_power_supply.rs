@@ -0,0 +1,53 @@
+#![cfg(target_os = "linux")]
+
+mod common;
+
+use common::write_tree;
+use instrument_panel_lib::linux::power_supply;
+use std::fs;
+
+#[test]
+fn laptop_on_ac_and_on_battery() {
+    let root = tempfile::tempdir().unwrap();
+    write_tree(root.path(), &[
+        ("class/power_supply/AC/type", "Mains"),
+        ("class/power_supply/AC/online", "1"),
+        ("class/power_supply/BAT0/type", "Battery"),
+        ("class/power_supply/BAT0/status", "Charging"),
+        // Wireless mouse; says nothing about the machine
+        ("class/power_supply/hidpp_battery_0/type", "Battery"),
+        ("class/power_supply/hidpp_battery_0/scope", "Device"),
+        ("class/power_supply/hidpp_battery_0/status", "Discharging"),
+    ]);
+    assert_eq!(power_supply::on_battery(root.path()), Some(false));
+
+    write_tree(root.path(), &[
+        ("class/power_supply/AC/online", "0"),
+        ("class/power_supply/BAT0/status", "Discharging"),
+    ]);
+    assert_eq!(power_supply::on_battery(root.path()), Some(true));
+}
+
+#[test]
+fn battery_without_adapter_entry() {
+    let root = tempfile::tempdir().unwrap();
+    write_tree(root.path(), &[
+        ("class/power_supply/BAT1/type", "Battery"),
+        ("class/power_supply/BAT1/status", "Full"),
+        ("class/power_supply/hidpp_battery_0/type", "Battery"),
+        ("class/power_supply/hidpp_battery_0/scope", "Device"),
+        ("class/power_supply/hidpp_battery_0/status", "Discharging"),
+    ]);
+    assert_eq!(power_supply::on_battery(root.path()), Some(false));
+
+    write_tree(root.path(), &[("class/power_supply/BAT1/status", "Discharging")]);
+    assert_eq!(power_supply::on_battery(root.path()), Some(true));
+}
+
+#[test]
+fn desktop_has_no_power_supply() {
+    let root = tempfile::tempdir().unwrap();
+    assert_eq!(power_supply::on_battery(root.path()), None);
+    fs::create_dir_all(root.path().join("class/power_supply")).unwrap();
+    assert_eq!(power_supply::on_battery(root.path()), None);
+}
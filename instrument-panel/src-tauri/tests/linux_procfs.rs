@@ -0,0 +1,102 @@
+#![cfg(target_os = "linux")]
+
+mod common;
+
+use instrument_panel_lib::linux::procfs::{self, ProcReader};
+use std::fs;
+use std::path::Path;
+
+const STAT_1: &str = "\
+cpu  1000 0 500 8000 500 0 0 0 0 0
+cpu0 600 0 300 3900 200 0 0 0 0 0
+cpu1 400 0 200 4100 300 0 0 0 0 0
+intr 123456 0 0
+ctxt 987654
+";
+
+// cpu0 spends 75 of 100 jiffies busy, cpu1 25 of 100
+const STAT_2: &str = "\
+cpu  1080 0 520 8095 505 0 0 0 0 0
+cpu0 660 0 315 3920 205 0 0 0 0 0
+cpu1 420 0 205 4175 300 0 0 0 0 0
+intr 123999 0 0
+ctxt 987999
+";
+
+const MEMINFO: &str = "\
+MemTotal:       32768000 kB
+MemFree:         1024000 kB
+MemAvailable:   20480000 kB
+Buffers:          512000 kB
+Cached:          8192000 kB
+";
+
+fn write_proc(root: &Path, stat: &str) {
+    fs::write(root.join("stat"), stat).unwrap();
+    fs::write(root.join("meminfo"), MEMINFO).unwrap();
+    fs::write(root.join("uptime"), "183742.51 1402311.98\n").unwrap();
+    fs::write(root.join("loadavg"), "1.25 0.80 0.55 2/1234 56789\n").unwrap();
+    fs::write(
+        root.join("cpuinfo"),
+        "processor\t: 0\nvendor_id\t: AuthenticAMD\nmodel name\t: AMD Ryzen 9 7950X 16-Core Processor\n",
+    )
+    .unwrap();
+}
+
+#[test]
+fn utilization_needs_two_samples() {
+    let dir = tempfile::tempdir().unwrap();
+    write_proc(dir.path(), STAT_1);
+    let mut reader = ProcReader::new(dir.path().to_path_buf());
+
+    let first = reader.read().unwrap();
+    assert_eq!(first.usage_percent, None);
+    assert!(first.core_usage_percent.is_empty());
+
+    write_proc(dir.path(), STAT_2);
+    let second = reader.read().unwrap();
+    assert_eq!(second.usage_percent, Some(50.0));
+    assert_eq!(second.core_usage_percent, vec![75.0, 25.0]);
+}
+
+#[test]
+fn maps_memory_uptime_and_load() {
+    let dir = tempfile::tempdir().unwrap();
+    write_proc(dir.path(), STAT_1);
+    let mut reader = ProcReader::new(dir.path().to_path_buf());
+    reader.read().unwrap();
+    write_proc(dir.path(), STAT_2);
+    let snapshot = reader.read().unwrap();
+
+    let mut data = common::reading("linux");
+    procfs::apply(&snapshot, &mut data);
+
+    assert_eq!(data.cpu.name.as_deref(), Some("AMD Ryzen 9 7950X 16-Core Processor"));
+    assert_eq!(data.cpu.usage_percent, Some(50.0));
+    assert_eq!(data.system.memory_total_mb, Some(32000.0));
+    assert_eq!(data.system.memory_used_mb, Some(12000.0));
+    assert_eq!(data.system.uptime_seconds, Some(183742));
+    let load = data.system.load_average.unwrap();
+    assert_eq!((load.one, load.five, load.fifteen), (1.25, 0.80, 0.55));
+}
+
+#[test]
+fn meminfo_without_mem_available_is_approximated() {
+    let info = procfs::parse_meminfo("MemTotal: 4096 kB\nMemFree: 1024 kB\nBuffers: 512 kB\nCached: 512 kB\n").unwrap();
+    assert_eq!(info.total_mb, 4.0);
+    assert_eq!(info.used_mb(), 2.0);
+}
+
+#[test]
+fn offline_cpus_are_matched_by_number() {
+    let before = procfs::parse_stat("cpu 0 0 0 0\ncpu0 10 0 0 90\ncpu2 50 0 0 50\n");
+    let after = procfs::parse_stat("cpu 0 0 0 0\ncpu2 100 0 0 100\n");
+    assert_eq!(after.cores.len(), 1);
+    assert_eq!(after.cores[0].1.usage_since(&before.cores[1].1), Some(50.0));
+}
+
+#[test]
+fn missing_stat_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    assert!(ProcReader::new(dir.path().to_path_buf()).read().is_err());
+}
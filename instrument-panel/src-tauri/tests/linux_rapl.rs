@@ -0,0 +1,90 @@
+#![cfg(target_os = "linux")]
+
+use instrument_panel_lib::hwinfo::types::CpuData;
+use instrument_panel_lib::linux::rapl::{self, RaplReader};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const MAX_RANGE: u64 = 262_143_328_850;
+
+fn write_zone(root: &Path, id: &str, name: &str, energy_uj: u64) {
+    let dir = root.join("class/powercap").join(id);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("name"), format!("{}\n", name)).unwrap();
+    fs::write(dir.join("energy_uj"), format!("{}\n", energy_uj)).unwrap();
+    fs::write(dir.join("max_energy_range_uj"), format!("{}\n", MAX_RANGE)).unwrap();
+}
+
+fn write_counters(root: &Path, package: u64, core: u64, uncore: u64) {
+    // Control-type directory without counters, as on real systems
+    fs::create_dir_all(root.join("class/powercap/intel-rapl")).unwrap();
+    write_zone(root, "intel-rapl:0", "package-0", package);
+    write_zone(root, "intel-rapl:0:0", "core", core);
+    write_zone(root, "intel-rapl:0:1", "uncore", uncore);
+}
+
+#[test]
+fn computes_domain_power_from_energy_deltas() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut reader = RaplReader::new(dir.path().to_path_buf());
+    let start = Instant::now();
+
+    write_counters(dir.path(), 1_000_000_000, 500_000_000, 10_000_000);
+    assert!(reader.read_at(start).is_empty());
+
+    // 2 seconds later: 130 J package, 90 J core, 4 J uncore
+    write_counters(dir.path(), 1_130_000_000, 590_000_000, 14_000_000);
+    let powers = reader.read_at(start + Duration::from_secs(2));
+    let names: Vec<(&str, f64)> = powers.iter().map(|p| (p.name.as_str(), p.watts)).collect();
+    assert_eq!(names, [("package-0", 65.0), ("core", 45.0), ("uncore", 2.0)]);
+
+    let mut cpu = CpuData::default();
+    rapl::apply(&powers, &mut cpu);
+    assert_eq!(cpu.package_power_w, Some(65.0));
+    assert_eq!(cpu.core_power_w, Some(45.0));
+    assert_eq!(cpu.uncore_power_w, Some(2.0));
+}
+
+#[test]
+fn handles_counter_wraparound() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut reader = RaplReader::new(dir.path().to_path_buf());
+    let start = Instant::now();
+
+    write_counters(dir.path(), MAX_RANGE - 20_000_000, 0, 0);
+    reader.read_at(start);
+    write_counters(dir.path(), 30_000_000, 0, 0);
+    let powers = reader.read_at(start + Duration::from_secs(1));
+
+    assert_eq!(powers[0].name, "package-0");
+    assert_eq!(powers[0].watts, 50.0);
+    assert_eq!(rapl::energy_delta_uj(10, 5, None), None);
+}
+
+#[test]
+fn sums_packages_across_sockets() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut reader = RaplReader::new(dir.path().to_path_buf());
+    let start = Instant::now();
+
+    write_zone(dir.path(), "intel-rapl:0", "package-0", 0);
+    write_zone(dir.path(), "intel-rapl:1", "package-1", 0);
+    reader.read_at(start);
+    write_zone(dir.path(), "intel-rapl:0", "package-0", 100_000_000);
+    write_zone(dir.path(), "intel-rapl:1", "package-1", 80_000_000);
+    let powers = reader.read_at(start + Duration::from_secs(1));
+
+    let mut cpu = CpuData::default();
+    rapl::apply(&powers, &mut cpu);
+    assert_eq!(cpu.package_power_w, Some(180.0));
+    assert_eq!(cpu.core_power_w, None);
+}
+
+#[test]
+fn missing_powercap_yields_nothing() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut reader = RaplReader::new(dir.path().to_path_buf());
+    reader.read();
+    assert!(reader.read().is_empty());
+}
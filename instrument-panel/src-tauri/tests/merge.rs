@@ -0,0 +1,167 @@
+mod common;
+
+use instrument_panel_lib::hwinfo::types::{DriveData, FanReading, GpuData, SensorData};
+use instrument_panel_lib::merge::{self, Priorities};
+use std::collections::BTreeMap;
+
+/// HWiNFO-like reading: CPU and GPU sensors, drive temperatures, no disk space
+fn hwinfo() -> SensorData {
+    let mut data = common::reading("hwinfo");
+    data.cpu.package_temp_c = Some(71.0);
+    data.cpu.usage_percent = Some(35.0);
+    data.gpu.hotspot_temp_c = Some(82.0);
+    data.drives = vec![DriveData {
+        name: Some("Samsung SSD 990 PRO".to_string()),
+        drive_letter: Some("C:".to_string()),
+        temp_c: Some(44.0),
+        ..DriveData::default()
+    }];
+    data.system.fans = vec![FanReading { name: "CPU".to_string(), rpm: 1200.0 }];
+    data.system.fan_status = "ok".to_string();
+    data
+}
+
+/// sysinfo-like reading: usage, memory and disk space, no GPU
+fn sysinfo() -> SensorData {
+    let mut data = common::reading("sysinfo");
+    data.cpu.usage_percent = Some(33.0);
+    data.cpu.package_temp_c = Some(69.0);
+    data.system.memory_total_mb = Some(32768.0);
+    data.drives = vec![DriveData {
+        drive_letter: Some("C:".to_string()),
+        total_gb: Some(1863.0),
+        free_gb: Some(1245.0),
+        ..DriveData::default()
+    }];
+    data
+}
+
+#[test]
+fn earlier_sources_win_and_gaps_are_filled() {
+    let priorities = Priorities::new(vec!["hwinfo".to_string(), "sysinfo".to_string()], BTreeMap::new());
+    let readings = [("sysinfo", sysinfo()), ("hwinfo", hwinfo())];
+    let data = merge::merge(&readings, &priorities, common::diagnostics("composite"));
+
+    assert_eq!(data.status, "connected");
+    assert_eq!(data.diagnostics.source.as_deref(), Some("composite"));
+    assert_eq!(data.cpu.package_temp_c, Some(71.0));
+    assert_eq!(data.cpu.usage_percent, Some(35.0));
+    assert_eq!(data.gpu.hotspot_temp_c, Some(82.0));
+    assert_eq!(data.system.memory_total_mb, Some(32768.0));
+    // "unknown" counts as missing, so HWiNFO's fan status survives
+    assert_eq!(data.system.fan_status, "ok");
+    // Both sources know drive C:
+    assert_eq!(data.drives.len(), 1);
+    assert_eq!(data.drives[0].name.as_deref(), Some("Samsung SSD 990 PRO"));
+    assert_eq!(data.drives[0].temp_c, Some(44.0));
+    assert_eq!(data.drives[0].total_gb, Some(1863.0));
+
+    let provenance = &data.diagnostics.provenance;
+    assert_eq!(provenance["cpu.packageTempC"], "hwinfo");
+    assert_eq!(provenance["system.memoryTotalMb"], "sysinfo");
+    assert_eq!(provenance["drives[0].tempC"], "hwinfo");
+    assert_eq!(provenance["drives[0].freeGb"], "sysinfo");
+    assert!(!provenance.contains_key("gpu.powerW"));
+    assert!(!provenance.contains_key("drives[0].wearPercent"));
+}
+
+#[test]
+fn field_and_section_priorities_override_the_default() {
+    let fields = BTreeMap::from([
+        ("cpu".to_string(), vec!["sysinfo".to_string()]),
+        ("cpu.packageTempC".to_string(), vec!["hwinfo".to_string()]),
+        ("drives".to_string(), vec!["sysinfo".to_string()]),
+        ("drives.tempC".to_string(), vec!["hwinfo".to_string()]),
+    ]);
+    let priorities = Priorities::new(vec!["hwinfo".to_string(), "sysinfo".to_string()], fields);
+    let readings = [("hwinfo", hwinfo()), ("sysinfo", sysinfo())];
+    let data = merge::merge(&readings, &priorities, common::diagnostics("composite"));
+
+    // Field beats section beats default
+    assert_eq!(data.cpu.package_temp_c, Some(71.0));
+    assert_eq!(data.cpu.usage_percent, Some(33.0));
+    assert_eq!(data.drives[0].drive_letter.as_deref(), Some("C:"));
+    assert_eq!(data.diagnostics.provenance["drives[0].driveLetter"], "sysinfo");
+    assert_eq!(data.diagnostics.provenance["drives[0].tempC"], "hwinfo");
+    assert_eq!(data.diagnostics.provenance["cpu.usagePercent"], "sysinfo");
+    assert_eq!(data.diagnostics.provenance["gpu.hotspotTempC"], "hwinfo");
+
+    assert_eq!(priorities.order("cpu.coreTemps", &["hwinfo", "sysinfo", "mock"]), vec!["sysinfo", "hwinfo", "mock"]);
+}
+
+fn drive(name: &str, letter: Option<&str>) -> DriveData {
+    DriveData {
+        name: Some(name.to_string()),
+        drive_letter: letter.map(str::to_string),
+        ..DriveData::default()
+    }
+}
+
+#[test]
+fn drives_match_by_device_and_partition() {
+    // smartctl names the disk, sysinfo and node_exporter its partitions
+    let mut smartctl = common::reading("smartctl");
+    smartctl.drives = vec![
+        DriveData { temp_c: Some(41.0), wear_percent: Some(3.0), ..drive("Samsung SSD 990 PRO 2TB", Some("/dev/nvme0")) },
+        DriveData { temp_c: Some(35.0), ..drive("WDC WD40EFRX", Some("/dev/sda")) },
+        DriveData { temp_c: Some(38.0), ..drive("Crucial P3", Some("/dev/nvme1")) },
+    ];
+    let mut sysinfo = common::reading("sysinfo");
+    sysinfo.drives = vec![
+        DriveData { total_gb: Some(1800.0), free_gb: Some(900.0), ..drive("/dev/nvme0n1p2", Some("/")) },
+        DriveData { total_gb: Some(3600.0), free_gb: Some(100.0), ..drive("/dev/sda1", Some("/srv")) },
+        // Not nvme1
+        DriveData { total_gb: Some(500.0), free_gb: Some(400.0), ..drive("/dev/nvme10n1p1", Some("/scratch")) },
+    ];
+
+    let priorities = Priorities::new(vec!["smartctl".to_string(), "sysinfo".to_string()], BTreeMap::new());
+    let data = merge::merge(&[("sysinfo", sysinfo), ("smartctl", smartctl)], &priorities, common::diagnostics("composite"));
+
+    let summary: Vec<_> = data
+        .drives
+        .iter()
+        .map(|d| (d.name.as_deref().unwrap(), d.drive_letter.as_deref().unwrap(), d.temp_c, d.total_gb))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("Samsung SSD 990 PRO 2TB", "/dev/nvme0", Some(41.0), Some(1800.0)),
+            ("WDC WD40EFRX", "/dev/sda", Some(35.0), Some(3600.0)),
+            ("Crucial P3", "/dev/nvme1", Some(38.0), None),
+            ("/dev/nvme10n1p1", "/scratch", None, Some(500.0)),
+        ]
+    );
+    assert_eq!(data.drives[0].wear_percent, Some(3.0));
+    assert_eq!(data.diagnostics.provenance["drives[0].totalGb"], "sysinfo");
+    assert_eq!(data.diagnostics.provenance["drives[3].name"], "sysinfo");
+}
+
+#[test]
+fn gpus_match_by_name() {
+    let gpu = |name: &str, power: Option<f64>, vram: Option<f64>| GpuData {
+        name: Some(name.to_string()),
+        power_w: power,
+        vram_total_mb: vram,
+        ..GpuData::default()
+    };
+    let mut hwinfo = hwinfo();
+    hwinfo.gpus = vec![gpu("NVIDIA GeForce RTX 4090", Some(320.0), None), gpu("AMD Radeon Graphics", Some(5.0), None)];
+    let mut nvidia = common::reading("nvidia-smi");
+    // Two of the same card: the second pairs with nothing from HWiNFO
+    nvidia.gpus = vec![gpu("NVIDIA GeForce RTX 4090", Some(318.0), Some(24564.0)), gpu("NVIDIA GeForce RTX 4090", Some(15.0), Some(24564.0))];
+
+    let priorities = Priorities::new(vec!["hwinfo".to_string(), "nvidia-smi".to_string()], BTreeMap::new());
+    let data = merge::merge(&[("hwinfo", hwinfo), ("nvidia-smi", nvidia)], &priorities, common::diagnostics("composite"));
+
+    let summary: Vec<_> = data.gpus.iter().map(|g| (g.name.as_deref().unwrap(), g.power_w, g.vram_total_mb)).collect();
+    assert_eq!(
+        summary,
+        [
+            ("NVIDIA GeForce RTX 4090", Some(320.0), Some(24564.0)),
+            ("AMD Radeon Graphics", Some(5.0), None),
+            ("NVIDIA GeForce RTX 4090", Some(15.0), Some(24564.0)),
+        ]
+    );
+    assert_eq!(data.diagnostics.provenance["gpus[0].vramTotalMb"], "nvidia-smi");
+    assert_eq!(data.diagnostics.provenance["gpus[0].powerW"], "hwinfo");
+}
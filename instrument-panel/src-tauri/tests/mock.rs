@@ -0,0 +1,118 @@
+use chrono::{DateTime, Duration, Utc};
+use instrument_panel_lib::delta::Update;
+use instrument_panel_lib::hwinfo::mock::{MockGenerator, SteppedClock};
+use instrument_panel_lib::hwinfo::scenario;
+use instrument_panel_lib::poller::{Poller, PollingPolicy};
+use instrument_panel_lib::sources::mock::{self, MockSource};
+use instrument_panel_lib::sources::{ActiveSource, SensorSource, SourceDescriptor, SourceRegistry};
+use instrument_panel_lib::AppSettings;
+use std::sync::mpsc;
+
+fn start() -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339("2025-06-01T12:00:00Z").unwrap().with_timezone(&Utc)
+}
+
+fn clock(step_secs: i64) -> Box<SteppedClock> {
+    Box::new(SteppedClock::new(start(), Duration::seconds(step_secs)))
+}
+
+/// Serialized readings, errors included
+fn run(mut generator: MockGenerator, count: usize) -> Vec<String> {
+    (0..count)
+        .map(|_| match generator.next_reading() {
+            Ok(data) => serde_json::to_string(&data).unwrap(),
+            Err(e) => e,
+        })
+        .collect()
+}
+
+#[test]
+fn default_rig_is_reproducible() {
+    let first = run(MockGenerator::new(clock(1), 42), 30);
+    let second = run(MockGenerator::new(clock(1), 42), 30);
+    assert_eq!(first, second);
+
+    let other_seed = run(MockGenerator::new(clock(1), 43), 30);
+    assert_ne!(first, other_seed);
+}
+
+#[test]
+fn readings_follow_the_clock() {
+    let mut generator = MockGenerator::new(clock(5), 0);
+
+    let first = generator.next_reading().unwrap();
+    assert_eq!(first.last_read_at.as_deref(), Some("2025-06-01T12:00:00+00:00"));
+    assert_eq!(first.system.uptime_seconds, Some(172_800));
+
+    let second = generator.next_reading().unwrap();
+    assert_eq!(second.last_read_at.as_deref(), Some("2025-06-01T12:00:05+00:00"));
+    assert_eq!(second.system.uptime_seconds, Some(172_805));
+    assert_eq!(second.status, "connected");
+}
+
+#[test]
+fn scenarios_are_reproducible() {
+    for (id, _) in scenario::bundled() {
+        let generator = || MockGenerator::with_scenario(scenario::load(id).unwrap(), clock(3));
+        assert_eq!(run(generator(), 100), run(generator(), 100), "{}", id);
+    }
+}
+
+#[test]
+fn scenario_disconnects_on_schedule() {
+    let mut generator = MockGenerator::with_scenario(scenario::load("hwinfo-crash").unwrap(), clock(10));
+
+    let results: Vec<bool> = (0..10)
+        .map(|_| {
+            let ok = generator.next_reading().is_ok();
+            assert_eq!(ok, generator.is_connected());
+            ok
+        })
+        .collect();
+    assert_eq!(results, vec![true, true, true, false, false, false, true, true, true, true]);
+}
+
+fn stepped_mock(_settings: &AppSettings, _registry: &SourceRegistry) -> Box<dyn SensorSource> {
+    Box::new(MockSource::new(MockGenerator::new(clock(1), 42), false))
+}
+
+/// The first `count` updates the app pushes with the mock source selected
+fn stream(count: usize) -> Vec<Update> {
+    let mut registry = SourceRegistry::with_builtin();
+    registry.register(SourceDescriptor {
+        create: stepped_mock,
+        ..mock::DESCRIPTOR
+    });
+    let source = ActiveSource::new(registry);
+    let settings = AppSettings {
+        data_source: Some(mock::DESCRIPTOR.id.to_string()),
+        polling: Some(PollingPolicy {
+            focused_ms: 1,
+            min_ms: 1,
+            battery_min_ms: 0,
+            ..PollingPolicy::default()
+        }),
+        ..AppSettings::default()
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let poller = Poller::spawn(settings, move |settings| source.poll(settings), move |update| {
+        let _ = tx.send(update.clone());
+    });
+    poller.subscribe("test", None);
+    (0..count)
+        .map(|_| rx.recv_timeout(std::time::Duration::from_secs(5)).unwrap())
+        .collect()
+}
+
+#[test]
+fn update_stream_is_reproducible() {
+    let first = stream(20);
+    assert_eq!(first, stream(20));
+
+    let Update::Snapshot { data, .. } = &first[0] else { panic!("stream does not start with a snapshot") };
+    assert_eq!(data["lastReadAt"], "2025-06-01T12:00:00+00:00");
+    assert_eq!(data["diagnostics"]["source"], "mock");
+    let Update::Delta { changes, .. } = &first[19] else { panic!("expected a delta") };
+    assert!(changes.iter().any(|c| c.value == "2025-06-01T12:00:19+00:00"));
+}
@@ -0,0 +1,139 @@
+use instrument_panel_lib::hwinfo::scenario::{self, Rng};
+
+#[test]
+fn bundled_scenarios_sample_throughout() {
+    let bundled = scenario::bundled();
+    let ids: Vec<&str> = bundled.iter().map(|(id, _)| *id).collect();
+    assert_eq!(ids, vec!["idle", "gaming", "thermal-runaway", "dying-ssd", "hwinfo-crash"]);
+
+    for (id, scenario) in &bundled {
+        let mut rng = Rng::new(scenario.seed);
+        for t in (0..600).step_by(7) {
+            if let Ok(data) = scenario.sample(t as f64, &mut rng) {
+                assert_eq!(data.cpu.core_temps.len(), 8, "{} at {}s", id, t);
+                assert_eq!(data.drives.len(), 2, "{} at {}s", id, t);
+                assert!(data.cpu.package_temp_c.is_some_and(|c| c > 0.0), "{} at {}s", id, t);
+            }
+        }
+    }
+}
+
+#[test]
+fn thermal_runaway() {
+    let scenario = scenario::load("thermal-runaway").unwrap();
+    let mut rng = Rng::new(scenario.seed);
+
+    let start = scenario.sample(0.0, &mut rng).unwrap();
+    assert!((start.cpu.package_temp_c.unwrap() - 62.0).abs() <= 0.5);
+    assert_eq!(start.system.fan_status, "ok");
+
+    // CPU fan stopped
+    let stalled = scenario.sample(30.0, &mut rng).unwrap();
+    assert_eq!(stalled.system.fans[0].rpm, 0.0);
+    assert_eq!(stalled.system.fan_status, "warning");
+
+    // Hot and throttled
+    let hot = scenario.sample(160.0, &mut rng).unwrap();
+    assert!(hot.cpu.package_temp_c.unwrap() >= 98.0);
+    assert!((hot.cpu.core_clock_mhz.unwrap() - 2900.0).abs() <= 40.0);
+
+    // Loops every 4 minutes
+    let again = scenario.sample(240.0, &mut rng).unwrap();
+    assert!((again.cpu.package_temp_c.unwrap() - 62.0).abs() <= 0.5);
+}
+
+#[test]
+fn dying_ssd() {
+    let scenario = scenario::load("dying-ssd").unwrap();
+    let mut rng = Rng::new(scenario.seed);
+
+    let healthy = scenario.sample(0.0, &mut rng).unwrap();
+    assert_eq!(healthy.drives[0].smart_health, "good");
+    assert_eq!(healthy.drives[0].media_errors, Some(0));
+
+    let failing = scenario.sample(100.0, &mut rng).unwrap();
+    assert_eq!(failing.drives[0].smart_health, "warning");
+    assert_eq!(failing.storage.smart_health, "warning");
+    // 70 of 240 seconds into a ramp to 420
+    assert_eq!(failing.drives[0].media_errors, Some(123));
+
+    // Dropped off the bus
+    let missing = scenario.sample(125.0, &mut rng).unwrap();
+    assert_eq!(missing.drives[0].temp_c, None);
+    assert_eq!(missing.storage.nvme_temp_c, None);
+
+    let dead = scenario.sample(260.0, &mut rng).unwrap();
+    assert_eq!(dead.drives[0].smart_health, "critical");
+    assert_eq!(dead.drives[0].smart_passed, Some(false));
+    // The other drive is untouched
+    assert_eq!(dead.drives[1].smart_health, "good");
+}
+
+#[test]
+fn hwinfo_crash() {
+    let scenario = scenario::load("hwinfo-crash").unwrap();
+    let mut rng = Rng::new(scenario.seed);
+
+    assert!(scenario.sample(10.0, &mut rng).is_ok());
+    let err = scenario.sample(45.0, &mut rng).unwrap_err();
+    assert!(err.contains("Shared memory not available"), "{}", err);
+    assert!(!scenario.is_connected(45.0));
+    assert!(scenario.sample(75.0, &mut rng).is_ok());
+    assert!(scenario.sample(125.0, &mut rng).is_err());
+}
+
+#[test]
+fn timeline_events() {
+    let scenario = scenario::parse(
+        r#"{
+            "name": "Events",
+            "inventory": { "cpu": { "coreTemps": [0, 0] } },
+            "metrics": {
+                "cpu.packageTempC": { "value": 0, "events": [
+                    { "at": 0, "rampTo": 100, "over": 100 },
+                    { "at": 50, "rampTo": 0, "over": 50 },
+                    { "at": 50, "noise": 0 }
+                ]},
+                "cpu.usagePercent": { "value": 10, "events": [{ "at": 20, "set": 90 }] },
+                "cpu.coreTemps": { "value": 40, "events": [{ "at": 5, "dropout": 5 }] },
+                "system.uptimeSeconds": { "value": 100, "events": [{ "at": 0, "rampTo": 200, "over": 3 }] }
+            }
+        }"#,
+    )
+    .unwrap();
+    let mut rng = Rng::new(0);
+    let at = |t: f64, rng: &mut Rng| scenario.sample(t, rng).unwrap();
+
+    assert_eq!(at(25.0, &mut rng).cpu.package_temp_c, Some(25.0));
+    // The second ramp starts from where the first had got to
+    assert_eq!(at(75.0, &mut rng).cpu.package_temp_c, Some(25.0));
+    assert_eq!(at(19.0, &mut rng).cpu.usage_percent, Some(10.0));
+    assert_eq!(at(20.0, &mut rng).cpu.usage_percent, Some(90.0));
+    assert_eq!(at(0.0, &mut rng).cpu.core_temps, vec![40.0, 40.0]);
+    assert!(at(7.0, &mut rng).cpu.core_temps.is_empty());
+    assert_eq!(at(10.0, &mut rng).cpu.core_temps, vec![40.0, 40.0]);
+    // Integer fields stay integers
+    assert_eq!(at(1.0, &mut rng).system.uptime_seconds, Some(133));
+}
+
+#[test]
+fn noise_is_seeded() {
+    let scenario = scenario::load("idle").unwrap();
+    let run = |seed| {
+        let mut rng = Rng::new(seed);
+        (0..20).map(|t| scenario.sample(t as f64, &mut rng).unwrap().cpu.core_temps).collect::<Vec<_>>()
+    };
+    assert_eq!(run(7), run(7));
+    assert_ne!(run(7), run(8));
+}
+
+#[test]
+fn invalid_scenarios() {
+    let err = scenario::parse(r#"{"name": "Typo", "metrics": {"cpu.packageTemp": {"value": 50}}}"#).unwrap_err();
+    assert_eq!(err, "Scenario metric cpu.packageTemp is not in the inventory");
+
+    let err = scenario::parse(r#"{"name": "No drives", "metrics": {"drives.0.tempC": {"value": 40}}}"#).unwrap_err();
+    assert!(err.contains("drives.0.tempC"), "{}", err);
+
+    assert!(scenario::load("no-such-scenario").unwrap_err().starts_with("Failed to read scenario"));
+}
@@ -0,0 +1,116 @@
+mod common;
+
+use instrument_panel_lib::node_exporter;
+
+/// Scrape of a render box: Ryzen, Radeon, one NVMe and one SATA disk
+const METRICS: &str = include_str!("fixtures/node_exporter/metrics.txt");
+
+/// The CPU counters one scrape earlier
+const EARLIER_CPU: &str = r#"
+node_cpu_seconds_total{cpu="0",mode="idle"} 8900
+node_cpu_seconds_total{cpu="0",mode="iowait"} 100
+node_cpu_seconds_total{cpu="0",mode="softirq"} 20
+node_cpu_seconds_total{cpu="0",mode="system"} 170
+node_cpu_seconds_total{cpu="0",mode="user"} 610
+node_cpu_seconds_total{cpu="1",mode="idle"} 9400
+node_cpu_seconds_total{cpu="1",mode="iowait"} 50
+node_cpu_seconds_total{cpu="1",mode="softirq"} 10
+node_cpu_seconds_total{cpu="1",mode="system"} 90
+node_cpu_seconds_total{cpu="1",mode="user"} 350
+"#;
+
+/// Stands in for node_exporter. Returns the base URL.
+fn serve(status: &'static str, body: &'static str) -> String {
+    common::serve("text/plain; version=0.0.4", status, body)
+}
+
+#[test]
+fn maps_captured_scrape_served_over_http() {
+    let url = format!("{}/metrics", serve("200 OK", METRICS));
+    let samples = node_exporter::fetch(&node_exporter::agent(), &url).unwrap();
+    let earlier = node_exporter::parse(EARLIER_CPU).unwrap();
+    let mut data = common::reading("node-exporter");
+    node_exporter::apply(&samples, &earlier, &mut data);
+
+    assert_eq!(data.system.name.as_deref(), Some("render-01"));
+
+    let cpu = &data.cpu;
+    assert_eq!(cpu.name.as_deref(), Some("AMD Ryzen 9 5950X 16-Core Processor"));
+    assert_eq!(cpu.package_temp_c, Some(67.5));
+    assert_eq!(cpu.core_temps, vec![61.25, 58.75]);
+    assert_eq!(cpu.core_clocks_mhz, vec![4550.0, 3650.0]);
+    assert_eq!(cpu.core_clock_mhz, Some(4100.0));
+    assert_eq!(cpu.max_clock_mhz, Some(5080.0));
+    // cpu0 was busy 100 of 200 seconds, cpu1 idle (iowait included) for 100
+    assert_eq!(cpu.core_usage_percent, vec![50.0, 0.0]);
+    assert_eq!(cpu.usage_percent.map(|u| (u * 100.0).round() / 100.0), Some(33.33));
+
+    let gpu = &data.gpu;
+    assert_eq!(gpu.core_temp_c, Some(64.0));
+    assert_eq!(gpu.hotspot_temp_c, Some(79.0));
+    assert_eq!(gpu.memory_junction_temp_c, Some(86.0));
+    assert_eq!(gpu.power_w, Some(212.0));
+    assert_eq!(gpu.fan_speed_rpm, Some(1480.0));
+
+    // Stopped header and the GPU fan are left out of the board fans
+    let fans: Vec<_> = data.system.fans.iter().map(|f| (f.name.as_str(), f.rpm)).collect();
+    assert_eq!(fans, vec![("nct6798 fan2", 1123.0), ("nct6798 fan3", 864.0)]);
+    assert_eq!(data.system.fan_status, "ok");
+
+    // tmpfs is skipped and the bind-mounted cache volume folds into /srv/render
+    let drives: Vec<_> = data.drives.iter().map(|d| d.drive_letter.as_deref().unwrap()).collect();
+    assert_eq!(drives, vec!["/", "/boot/efi", "/srv/render"]);
+    assert_eq!(data.drives[0].name.as_deref(), Some("/dev/nvme0n1p2"));
+    assert_eq!(data.drives[0].temp_c, Some(43.85));
+    assert_eq!(data.drives[0].total_gb, Some(1000.0));
+    assert_eq!(data.drives[0].free_gb, Some(400.0));
+    assert_eq!(data.drives[2].temp_c, None);
+    assert_eq!(data.drives[2].total_gb, Some(4000.0));
+    assert_eq!(data.storage.name.as_deref(), Some("NVMe (nvme0)"));
+    assert_eq!(data.storage.nvme_temp_c, Some(43.85));
+
+    assert_eq!(data.system.memory_total_mb, Some(65536.0));
+    assert_eq!(data.system.memory_used_mb, Some(45056.0));
+    assert_eq!(data.system.uptime_seconds, Some(93_700));
+    assert_eq!(data.system.load_average.map(|l| l.one), Some(12.41));
+
+    // Temperatures with no field of their own stay visible
+    let raw: Vec<_> = data.raw_readings.iter().map(|r| (r.group.as_str(), r.label.as_str())).collect();
+    assert_eq!(raw, vec![("nvme", "Sensor 1"), ("nct6798", "SYSTIN")]);
+}
+
+#[test]
+fn first_scrape_has_no_utilization() {
+    let samples = node_exporter::parse(METRICS).unwrap();
+    let mut data = common::reading("node-exporter");
+    node_exporter::apply(&samples, &[], &mut data);
+    assert_eq!(data.cpu.usage_percent, None);
+    assert!(data.cpu.core_usage_percent.is_empty());
+}
+
+#[test]
+fn exposition_format_details() {
+    let samples = node_exporter::parse(concat!(
+        "# HELP weird Escapes, timestamps and special values\n",
+        "weird{path=\"C:\\\\data\",note=\"say \\\"hi\\\"\\n\",} 1.5 1700000000000\n",
+        "no_labels +Inf\n",
+        "  \n",
+    ))
+    .unwrap();
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[0].label("path"), Some("C:\\data"));
+    assert_eq!(samples[0].label("note"), Some("say \"hi\"\n"));
+    assert_eq!(samples[0].value, 1.5);
+    assert!(samples[1].labels.is_empty());
+    assert_eq!(samples[1].value, f64::INFINITY);
+
+    let err = node_exporter::parse("<html><body>Node Exporter</body></html>").unwrap_err();
+    assert!(err.starts_with("Invalid metrics line 1"), "{}", err);
+}
+
+#[test]
+fn http_errors_are_reported() {
+    let url = format!("{}/metrics", serve("503 Service Unavailable", "busy"));
+    let err = node_exporter::fetch(&node_exporter::agent(), &url).unwrap_err();
+    assert!(err.contains("HTTP 503"), "{}", err);
+}
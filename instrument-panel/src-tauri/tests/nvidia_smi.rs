@@ -0,0 +1,65 @@
+use instrument_panel_lib::nvidia_smi;
+
+/// `nvidia-smi --query-gpu=... --format=csv,noheader,nounits` on a three-GPU box
+const QUERY_CSV: &str = include_str!("fixtures/nvidia_smi/query.csv");
+
+#[test]
+fn one_gpu_per_line() {
+    let gpus = nvidia_smi::parse(QUERY_CSV).unwrap();
+    assert_eq!(gpus.len(), 3);
+
+    let gpu = &gpus[0];
+    assert_eq!(gpu.name.as_deref(), Some("NVIDIA GeForce RTX 4090"));
+    assert_eq!(gpu.core_temp_c, Some(64.0));
+    assert_eq!(gpu.hotspot_temp_c, None);
+    assert_eq!(gpu.power_w, Some(387.52));
+    assert_eq!(gpu.core_clock_mhz, Some(2745.0));
+    assert_eq!(gpu.memory_clock_mhz, Some(10501.0));
+    assert_eq!(gpu.usage_percent, Some(98.0));
+    assert_eq!(gpu.vram_used_mb, Some(18932.0));
+    assert_eq!(gpu.vram_total_mb, Some(24564.0));
+    assert_eq!(gpu.fan_speed_percent, Some(61.0));
+
+    // Passively cooled datacenter card has no fan
+    assert_eq!(gpus[2].name.as_deref(), Some("Tesla T4"));
+    assert_eq!(gpus[2].fan_speed_percent, None);
+}
+
+#[test]
+fn unsupported_fields_and_bad_lines() {
+    let gpus = nvidia_smi::parse("0, Quadro P400, 37, [Not Supported], 139, 405, 0, 58, 2048, 34\n\n").unwrap();
+    assert_eq!(gpus.len(), 1);
+    assert_eq!(gpus[0].power_w, None);
+    assert_eq!(gpus[0].vram_total_mb, Some(2048.0));
+
+    let err = nvidia_smi::parse("No devices were found").unwrap_err();
+    assert!(err.starts_with("Expected 10 nvidia-smi columns"), "{}", err);
+}
+
+#[cfg(unix)]
+#[test]
+fn runs_a_stub_command() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let fixture = dir.path().join("query.csv");
+    std::fs::write(&fixture, QUERY_CSV).unwrap();
+
+    // Stands in for nvidia-smi; checks it was asked for CSV without units
+    let stub = dir.path().join("nvidia-smi");
+    std::fs::write(&stub, format!(
+        "#!/bin/sh\ncase \"$2\" in --format=csv,noheader,nounits) cat '{}' ;; *) echo bad args >&2; exit 2 ;; esac\n",
+        fixture.display()
+    )).unwrap();
+    std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let output = nvidia_smi::query(stub.to_str().unwrap()).unwrap();
+    assert_eq!(nvidia_smi::parse(&output).unwrap().len(), 3);
+
+    // A failing driver is reported with nvidia-smi's own message
+    let broken = dir.path().join("broken-smi");
+    std::fs::write(&broken, "#!/bin/sh\necho 'NVIDIA-SMI has failed because it could not communicate with the NVIDIA driver.'\nexit 9\n").unwrap();
+    std::fs::set_permissions(&broken, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let err = nvidia_smi::query(broken.to_str().unwrap()).unwrap_err();
+    assert!(err.contains("could not communicate"), "{}", err);
+}
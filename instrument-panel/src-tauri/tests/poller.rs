@@ -0,0 +1,222 @@
+mod common;
+
+use instrument_panel_lib::delta::{Key, Update};
+use instrument_panel_lib::hwinfo::types::SensorData;
+use instrument_panel_lib::poller::{Activity, Poller, PollingPolicy, WindowState};
+use instrument_panel_lib::AppSettings;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const WAIT: Duration = Duration::from_secs(5);
+
+/// Settings polling every `ms`, however the machine is powered
+fn every(ms: u64) -> AppSettings {
+    AppSettings {
+        polling: Some(PollingPolicy {
+            focused_ms: ms,
+            unfocused_ms: ms,
+            min_ms: 1,
+            battery_min_ms: 0,
+            ..PollingPolicy::default()
+        }),
+        ..AppSettings::default()
+    }
+}
+
+/// Poller whose updates are also sent down the returned channel
+fn spawn(settings: AppSettings) -> (Poller, mpsc::Receiver<Update>, Arc<AtomicUsize>) {
+    let (tx, rx) = mpsc::channel();
+    let polls = Arc::new(AtomicUsize::new(0));
+    let poller = Poller::spawn(
+        settings,
+        {
+            let polls = polls.clone();
+            move |settings| {
+                polls.fetch_add(1, Ordering::SeqCst);
+                // Names the source the settings selected
+                common::reading(settings.source_id())
+            }
+        },
+        move |update| {
+            let _ = tx.send(update.clone());
+        },
+    );
+    (poller, rx, polls)
+}
+
+fn source(data: &SensorData) -> Option<&str> {
+    data.diagnostics.source.as_deref()
+}
+
+#[test]
+fn caches_and_pushes_each_reading() {
+    let (poller, updates, _) = spawn(every(10));
+    poller.subscribe("dashboard", None);
+
+    let Update::Snapshot { seq: 1, data } = updates.recv_timeout(WAIT).unwrap() else {
+        panic!("stream does not start with a snapshot");
+    };
+    assert_eq!(data["diagnostics"]["source"], "hwinfo");
+    for seq in 2..4 {
+        let Update::Delta { seq: delta_seq, changes } = updates.recv_timeout(WAIT).unwrap() else {
+            panic!("snapshot after the first update");
+        };
+        assert_eq!(delta_seq, seq);
+        // Only the timestamp moves
+        assert!(changes.iter().all(|c| c.path == [Key::Field("lastReadAt".to_string())]));
+    }
+
+    let latest = poller.latest().unwrap();
+    assert_eq!(source(&latest), Some("hwinfo"));
+    let resync = poller.resync().unwrap();
+    assert!(matches!(resync, Update::Snapshot { seq, .. } if seq >= 3));
+}
+
+#[test]
+fn polls_with_new_settings_right_away() {
+    let (poller, updates, _) = spawn(every(3_600_000));
+    poller.subscribe("dashboard", None);
+    updates.recv_timeout(WAIT).unwrap();
+
+    poller.set_settings(AppSettings {
+        data_source: Some("mock".to_string()),
+        ..every(3_600_000)
+    });
+    let Update::Delta { changes, .. } = updates.recv_timeout(WAIT).unwrap() else { panic!("expected a delta") };
+    assert!(changes.iter().any(|c| c.value == "mock"));
+    assert_eq!(source(&poller.latest().unwrap()), Some("mock"));
+    assert_eq!(poller.settings().source_id(), "mock");
+}
+
+#[test]
+fn refresh_polls_without_waiting_for_the_interval() {
+    let (poller, updates, polls) = spawn(every(3_600_000));
+    poller.subscribe("dashboard", None);
+    updates.recv_timeout(WAIT).unwrap();
+    poller.refresh();
+    updates.recv_timeout(WAIT).unwrap();
+    assert_eq!(polls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn pauses_without_subscribers() {
+    let (poller, updates, polls) = spawn(every(10));
+    assert!(updates.recv_timeout(Duration::from_millis(100)).is_err());
+    assert_eq!(poller.interval(), None);
+
+    poller.subscribe("dashboard", None);
+    updates.recv_timeout(WAIT).unwrap();
+    poller.unsubscribe("dashboard");
+    // At most the poll already under way when unsubscribing
+    while updates.recv_timeout(Duration::from_millis(100)).is_ok() {}
+    let paused_at = polls.load(Ordering::SeqCst);
+    std::thread::sleep(Duration::from_millis(100));
+    assert_eq!(polls.load(Ordering::SeqCst), paused_at);
+}
+
+#[test]
+fn pauses_while_every_window_is_hidden() {
+    let (poller, updates, polls) = spawn(every(10));
+    poller.subscribe("dashboard", None);
+    poller.set_window("main", WindowState { visible: true, focused: true });
+    updates.recv_timeout(WAIT).unwrap();
+
+    poller.set_window("main", WindowState { visible: false, focused: false });
+    assert_eq!(poller.interval(), None);
+    while updates.recv_timeout(Duration::from_millis(100)).is_ok() {}
+    let paused_at = polls.load(Ordering::SeqCst);
+    std::thread::sleep(Duration::from_millis(100));
+    assert_eq!(polls.load(Ordering::SeqCst), paused_at);
+
+    // A second window showing readings is enough to carry on
+    poller.set_window("detail", WindowState { visible: true, focused: false });
+    updates.recv_timeout(WAIT).unwrap();
+    poller.close_window("detail");
+    assert_eq!(poller.interval(), None);
+
+    poller.set_window("main", WindowState { visible: true, focused: false });
+    updates.recv_timeout(WAIT).unwrap();
+}
+
+#[test]
+fn faster_subscriber_starts_the_next_poll_early() {
+    let (poller, updates, _) = spawn(every(3_600_000));
+    poller.subscribe("dashboard", None);
+    updates.recv_timeout(WAIT).unwrap();
+
+    poller.subscribe("cpu-detail", Some(Duration::from_millis(10)));
+    updates.recv_timeout(WAIT).unwrap();
+    assert_eq!(poller.interval(), Some(Duration::from_millis(10)));
+}
+
+#[test]
+fn dropping_stops_the_thread() {
+    let (poller, updates, polls) = spawn(every(3_600_000));
+    poller.subscribe("dashboard", None);
+    updates.recv_timeout(WAIT).unwrap();
+
+    let started = Instant::now();
+    drop(poller);
+    assert!(started.elapsed() < WAIT);
+    assert_eq!(polls.load(Ordering::SeqCst), 1);
+}
+
+fn activity(windows: &[WindowState], subscriptions: &[Option<u64>], on_battery: bool) -> Activity {
+    Activity {
+        windows: windows.iter().enumerate().map(|(i, w)| (format!("window{}", i), *w)).collect(),
+        subscriptions: subscriptions
+            .iter()
+            .enumerate()
+            .map(|(i, ms)| (format!("view{}", i), ms.map(Duration::from_millis)))
+            .collect(),
+        on_battery,
+    }
+}
+
+#[test]
+fn policy_intervals() {
+    let policy = PollingPolicy::default();
+    let interval = |activity: Activity| policy.interval(&activity).map(|d| d.as_millis() as u64);
+    let focused = WindowState { visible: true, focused: true };
+    let unfocused = WindowState { visible: true, focused: false };
+    let hidden = WindowState { visible: false, focused: false };
+
+    assert_eq!(interval(activity(&[focused], &[], false)), None);
+    assert_eq!(interval(activity(&[], &[None], false)), Some(1000));
+    assert_eq!(interval(activity(&[focused], &[None], false)), Some(1000));
+    assert_eq!(interval(activity(&[unfocused], &[None], false)), Some(2000));
+    assert_eq!(interval(activity(&[hidden], &[None], false)), None);
+    assert_eq!(interval(activity(&[hidden, unfocused], &[None], false)), Some(2000));
+
+    // Detail views ask for faster polling, down to `min_ms`
+    assert_eq!(interval(activity(&[focused], &[None, Some(250)], false)), Some(250));
+    assert_eq!(interval(activity(&[focused], &[Some(50)], false)), Some(250));
+    assert_eq!(interval(activity(&[unfocused], &[Some(500)], false)), Some(500));
+    // Nobody sees them while hidden
+    assert_eq!(interval(activity(&[hidden], &[Some(250)], false)), None);
+    // A subscriber cannot slow polling down
+    assert_eq!(interval(activity(&[focused], &[Some(10_000)], false)), Some(1000));
+
+    assert_eq!(interval(activity(&[focused], &[Some(250)], true)), Some(2000));
+
+    let always = PollingPolicy { pause_when_unused: false, ..policy };
+    let interval = |activity: Activity| always.interval(&activity).map(|d| d.as_millis() as u64);
+    assert_eq!(interval(activity(&[focused], &[], false)), Some(1000));
+    assert_eq!(interval(activity(&[hidden], &[None], false)), Some(5000));
+    assert_eq!(interval(activity(&[hidden], &[Some(250)], true)), Some(5000));
+}
+
+#[test]
+fn partial_policy_settings_use_the_defaults() {
+    let policy: PollingPolicy = serde_json::from_str(r#"{"hiddenMs":30000,"pauseWhenUnused":false}"#).unwrap();
+    assert_eq!(
+        policy,
+        PollingPolicy {
+            hidden_ms: 30_000,
+            pause_when_unused: false,
+            ..PollingPolicy::default()
+        }
+    );
+}
@@ -0,0 +1,105 @@
+use instrument_panel_lib::hwinfo::decode;
+use instrument_panel_lib::hwinfo::image::ImageBuilder;
+use instrument_panel_lib::hwinfo::mock::{MockGenerator, SystemClock};
+use instrument_panel_lib::hwinfo::snapshot::{HostInfo, Snapshot};
+use instrument_panel_lib::hwinfo::types::ReadingType;
+use std::time::Duration;
+
+fn rig() -> Vec<u8> {
+    ImageBuilder::new()
+        .sensor("CPU [#0]: AMD Ryzen 7 7800X3D")
+        .reading(ReadingType::Temp, "CPU (Tctl/Tdie)", "°C", 61.5)
+        .reading(ReadingType::Power, "CPU PPT", "W", 72.0)
+        .reading(ReadingType::Fan, "CPU Fan", "RPM", 1150.0)
+        .sensor("S.M.A.R.T.: Lexar SSD NQ780 2TB [C:]")
+        .reading(ReadingType::Temp, "Drive Temperature", "°C", 41.0)
+        .reading(ReadingType::Other, "Drive Remaining Life", "%", 97.0)
+        .build()
+}
+
+fn host() -> HostInfo {
+    HostInfo {
+        hostname: Some("TEAMMATE-PC".to_string()),
+        ..HostInfo::default()
+    }
+}
+
+#[test]
+fn decodes_a_region() {
+    let data = decode::decode(&rig()).unwrap();
+    assert_eq!(data.cpu.name.as_deref(), Some("CPU [#0]: AMD Ryzen 7 7800X3D"));
+    assert_eq!(data.cpu.package_temp_c, Some(61.5));
+    assert_eq!(data.cpu.package_power_w, Some(72.0));
+    assert_eq!(data.system.fans.len(), 1);
+    assert_eq!(data.drives[0].drive_letter.as_deref(), Some("C:"));
+    assert_eq!(data.drives[0].temp_c, Some(41.0));
+    assert_eq!(data.storage.smart_health, "good");
+}
+
+#[test]
+fn rejects_broken_regions() {
+    let mut bad_signature = rig();
+    bad_signature[0] = 0;
+    assert_eq!(decode::decode(&bad_signature).unwrap_err(), "Invalid HWiNFO signature: 0x53695700");
+
+    let region = rig();
+    let err = decode::decode(&region[..region.len() - 1]).unwrap_err();
+    assert!(err.starts_with("HWiNFO reading section ends at byte"), "{}", err);
+
+    assert!(decode::decode(&region[..10]).is_err());
+}
+
+#[test]
+fn replays_regions_with_the_captured_host() {
+    let mut recorded = MockGenerator::new(Box::new(SystemClock), 0).next_reading().unwrap();
+    recorded.drives[0].name = Some("S.M.A.R.T.: Lexar SSD NQ780 2TB [C:]".to_string());
+    recorded.drives[0].free_gb = Some(1245.0);
+
+    let mut snapshot = Snapshot::new("hwinfo", host());
+    snapshot.push(Duration::ZERO, Ok(rig()), Some(recorded));
+
+    let data = snapshot.replay(0).unwrap();
+    assert_eq!(data.cpu.package_temp_c, Some(61.5));
+    assert_eq!(data.system.name.as_deref(), Some("TEAMMATE-PC"));
+    assert_eq!(data.system.uptime_seconds, Some(172_800));
+    assert_eq!(data.drives[0].free_gb, Some(1245.0));
+}
+
+#[test]
+fn replays_recorded_data_without_a_region() {
+    let recorded = MockGenerator::new(Box::new(SystemClock), 0).next_reading().unwrap();
+    let mut snapshot = Snapshot::new("linux", host());
+    snapshot.push(Duration::ZERO, Err("HWiNFO shared memory is only available on Windows".to_string()), Some(recorded));
+    snapshot.push(Duration::from_secs(1), Err("Shared memory not available".to_string()), None);
+
+    assert_eq!(snapshot.replay(0).unwrap().cpu.name.as_deref(), Some("AMD Ryzen 7 7800X3D"));
+    assert_eq!(snapshot.replay(1).unwrap_err(), "Shared memory not available");
+    assert!(snapshot.replay(2).is_err());
+}
+
+#[test]
+fn plays_frames_on_their_schedule() {
+    let mut snapshot = Snapshot::new("hwinfo", host());
+    for ms in [0, 1000, 2000] {
+        snapshot.push(Duration::from_millis(ms), Ok(rig()), None);
+    }
+    assert_eq!(snapshot.duration_ms(), 3000);
+
+    assert_eq!(snapshot.frame_at(0, false), Some(0));
+    assert_eq!(snapshot.frame_at(999, false), Some(0));
+    assert_eq!(snapshot.frame_at(2500, false), Some(2));
+    assert_eq!(snapshot.frame_at(3000, false), None);
+    assert_eq!(snapshot.frame_at(4200, true), Some(1));
+
+    let empty = Snapshot::new("hwinfo", host());
+    assert_eq!(empty.frame_at(0, true), None);
+
+    // Offsets come from the file
+    let mut late = Snapshot::new("hwinfo", host());
+    late.push(Duration::from_millis(u64::MAX - 10), Ok(rig()), None);
+    assert_eq!(late.duration_ms(), u64::MAX);
+    assert_eq!(late.frame_at(u64::MAX - 1, false), Some(0));
+    late.push(Duration::from_millis(u64::MAX), Ok(rig()), None);
+    assert_eq!(late.duration_ms(), u64::MAX);
+    assert_eq!(late.frame_at(u64::MAX - 1, true), Some(0));
+}
@@ -0,0 +1,106 @@
+use instrument_panel_lib::smartctl::{self, Device};
+
+/// `smartctl --scan-open --json` with two SATA drives and an NVMe drive
+const SCAN_JSON: &str = include_str!("fixtures/smartctl/scan.json");
+/// `smartctl --json -a` reports, one per drive
+const NVME_JSON: &str = include_str!("fixtures/smartctl/nvme.json");
+const ATA_SSD_JSON: &str = include_str!("fixtures/smartctl/ata_ssd.json");
+const FAILING_HDD_JSON: &str = include_str!("fixtures/smartctl/failing_hdd.json");
+
+#[test]
+fn nvme_health_log() {
+    let drive = smartctl::parse(NVME_JSON).unwrap();
+    assert_eq!(drive.name.as_deref(), Some("Samsung SSD 980 PRO 2TB"));
+    assert_eq!(drive.drive_letter.as_deref(), Some("/dev/nvme0"));
+    assert_eq!(drive.temp_c, Some(41.0));
+    assert_eq!(drive.total_gb.map(|gb| gb.round()), Some(2000.0));
+    assert_eq!(drive.wear_percent, Some(4.0));
+    assert_eq!(drive.media_errors, Some(0));
+    assert_eq!(drive.reallocated_sectors, None);
+    assert_eq!(drive.smart_passed, Some(true));
+    assert_eq!(drive.smart_health, "good");
+}
+
+#[test]
+fn ata_attribute_table() {
+    let drive = smartctl::parse(ATA_SSD_JSON).unwrap();
+    assert_eq!(drive.name.as_deref(), Some("Samsung SSD 870 EVO 1TB"));
+    assert_eq!(drive.temp_c, Some(34.0));
+    // Wear_Leveling_Count normalized 91 -> 9% of rated life used
+    assert_eq!(drive.wear_percent, Some(9.0));
+    assert_eq!(drive.reallocated_sectors, Some(0));
+    assert_eq!(drive.media_errors, Some(0));
+    assert_eq!(drive.smart_health, "good");
+}
+
+#[test]
+fn failing_drive_is_critical() {
+    let drive = smartctl::parse(FAILING_HDD_JSON).unwrap();
+    assert_eq!(drive.smart_passed, Some(false));
+    assert_eq!(drive.reallocated_sectors, Some(3912));
+    // Worst of Reported_Uncorrect and Offline_Uncorrectable
+    assert_eq!(drive.media_errors, Some(211));
+    // Spinning disks have no wear indicator
+    assert_eq!(drive.wear_percent, None);
+    assert_eq!(drive.smart_health, "critical");
+}
+
+#[test]
+fn errors_downgrade_a_passing_drive() {
+    let json = ATA_SSD_JSON.replace(
+        r#""raw": {"value": 0, "string": "0"}},
+      {"id": 9"#,
+        r#""raw": {"value": 16, "string": "16"}},
+      {"id": 9"#,
+    );
+    let drive = smartctl::parse(&json).unwrap();
+    assert_eq!(drive.reallocated_sectors, Some(16));
+    assert_eq!(drive.smart_health, "warning");
+
+    // Nothing but the overall verdict
+    let drive = smartctl::parse(r#"{"smart_status": {"passed": true}}"#).unwrap();
+    assert_eq!(drive.smart_health, "good");
+    let drive = smartctl::parse("{}").unwrap();
+    assert_eq!(drive.smart_health, "unknown");
+
+    assert!(smartctl::parse("smartctl: command not found").is_err());
+}
+
+#[test]
+fn scan_lists_devices() {
+    let devices = smartctl::parse_scan(SCAN_JSON).unwrap();
+    assert_eq!(devices.len(), 3);
+    assert_eq!(devices[2], Device { name: "/dev/nvme0".to_string(), device_type: "nvme".to_string() });
+}
+
+#[cfg(unix)]
+#[test]
+fn runs_a_stub_command() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    for (name, json) in [("scan.json", SCAN_JSON), ("sdb.json", FAILING_HDD_JSON)] {
+        std::fs::write(dir.path().join(name), json).unwrap();
+    }
+
+    // Stands in for smartctl; a failing drive still prints its report but sets
+    // the health bits (3 and 4) of the exit status
+    let stub = dir.path().join("smartctl");
+    std::fs::write(&stub, format!(
+        "#!/bin/sh\ncd '{}'\ncase \"$*\" in\n\
+         '--scan-open --json') cat scan.json ;;\n\
+         '--json -a -d sat /dev/sdb') cat sdb.json; exit 24 ;;\n\
+         *) echo 'Smartctl open device: failed' ; exit 2 ;;\nesac\n",
+        dir.path().display()
+    )).unwrap();
+    std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let command = stub.to_str().unwrap();
+
+    let devices = smartctl::scan(command).unwrap();
+    let report = smartctl::read(command, &devices[1]).unwrap();
+    assert_eq!(smartctl::parse(&report).unwrap().smart_health, "critical");
+
+    // Exit bit 1: the device could not be opened
+    let err = smartctl::read(command, &devices[0]).unwrap_err();
+    assert!(err.contains("exit 2") && err.contains("open device"), "{}", err);
+}
@@ -0,0 +1,71 @@
+use instrument_panel_lib::hwinfo::mock::{MockGenerator, SystemClock};
+use instrument_panel_lib::hwinfo::snapshot::{self, HostInfo, Snapshot, FORMAT_VERSION};
+use std::time::Duration;
+
+fn host() -> HostInfo {
+    HostInfo {
+        hostname: Some("DESKTOP-PC".to_string()),
+        os: Some("Windows".to_string()),
+        cpu: Some("AMD Ryzen 7 7800X3D".to_string()),
+        cpu_count: 16,
+        ..HostInfo::default()
+    }
+}
+
+#[test]
+fn round_trips_through_a_file() {
+    let mut generator = MockGenerator::new(Box::new(SystemClock), 0);
+    let mut snapshot = Snapshot::new("hwinfo", host());
+    snapshot.push(Duration::ZERO, Ok(vec![0x48, 0x57, 0x69, 0x53, 0x00, 0xff]), generator.next_reading().ok());
+    snapshot.push(
+        Duration::from_millis(1003),
+        Err("Shared memory not available".to_string()),
+        generator.next_reading().ok(),
+    );
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(snapshot.file_name());
+    snapshot.save(&path).unwrap();
+
+    let json = std::fs::read_to_string(&path).unwrap();
+    assert!(json.contains("\"region\": \"4857695300ff\""), "{}", json);
+
+    let loaded = Snapshot::load(&path).unwrap();
+    assert_eq!(loaded.version, FORMAT_VERSION);
+    assert_eq!(loaded.source, "hwinfo");
+    assert_eq!(loaded.host.cpu_count, 16);
+    assert_eq!(loaded.frames.len(), 2);
+    assert_eq!(loaded.frames[0].region.as_deref(), Some(&[0x48, 0x57, 0x69, 0x53, 0x00, 0xff][..]));
+    assert_eq!(loaded.frames[1].elapsed_ms, 1003);
+    assert_eq!(loaded.frames[1].region, None);
+    assert_eq!(loaded.frames[1].region_error.as_deref(), Some("Shared memory not available"));
+    assert_eq!(
+        loaded.frames[1].data.as_ref().unwrap().cpu.name.as_deref(),
+        Some("AMD Ryzen 7 7800X3D")
+    );
+}
+
+#[test]
+fn file_name_comes_from_capture_time() {
+    let mut snapshot = Snapshot::new("mock", host());
+    snapshot.captured_at = "2025-06-01T12:34:56.789+00:00".to_string();
+    assert_eq!(snapshot.file_name(), "snapshot-20250601-123456.json");
+}
+
+#[test]
+fn rejects_other_files() {
+    let newer = r#"{"format": "instrument-panel-snapshot", "version": 99, "frames": []}"#;
+    assert_eq!(
+        snapshot::parse(newer).unwrap_err(),
+        format!("Snapshot version 99 is newer than this app reads ({})", FORMAT_VERSION)
+    );
+
+    let settings = r#"{"format": "settings", "version": 1}"#;
+    assert!(snapshot::parse(settings).unwrap_err().starts_with("Not a snapshot file"));
+    assert!(snapshot::parse(r#"{"mockMode": true}"#).unwrap_err().starts_with("Not a snapshot file"));
+
+    let mut snapshot = serde_json::to_value(Snapshot::new("hwinfo", host())).unwrap();
+    snapshot["frames"] = serde_json::json!([{"elapsedMs": 0, "capturedAt": "", "region": "48zz", "data": null}]);
+    let err = snapshot::parse(&snapshot.to_string()).unwrap_err();
+    assert!(err.contains("region is not hex"), "{}", err);
+}
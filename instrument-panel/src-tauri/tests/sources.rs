@@ -0,0 +1,386 @@
+mod common;
+
+use instrument_panel_lib::hwinfo::mock::{MockGenerator, SystemClock};
+use instrument_panel_lib::hwinfo::snapshot::{HostInfo, Snapshot};
+use instrument_panel_lib::hwinfo::types::{Diagnostics, SensorData};
+use instrument_panel_lib::sources::system_info::SystemInfoSource;
+use instrument_panel_lib::sources::{
+    self, ActiveSource, SensorSource, SourceCapabilities, SourceDescriptor, SourceRegistry,
+};
+use instrument_panel_lib::AppSettings;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Source whose behavior is set through statics, since descriptors hold
+/// plain function pointers
+struct Fake {
+    id: &'static str,
+    /// Polls fail while set
+    down: Option<&'static AtomicBool>,
+    connects: Option<&'static AtomicUsize>,
+}
+
+impl SensorSource for Fake {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+
+    fn connect(&mut self) -> Result<(), String> {
+        if let Some(connects) = self.connects {
+            connects.fetch_add(1, Ordering::SeqCst);
+        }
+        Ok(())
+    }
+
+    fn poll(&mut self) -> Result<SensorData, String> {
+        if self.down.is_some_and(|down| down.load(Ordering::SeqCst)) {
+            return Err(format!("{} is down", self.id));
+        }
+        Ok(common::reading(self.id))
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities { cpu: true, ..SourceCapabilities::default() }
+    }
+
+    fn diagnostics(&self) -> Diagnostics {
+        common::diagnostics(self.id)
+    }
+}
+
+fn descriptor(id: &'static str, create: fn(&AppSettings, &SourceRegistry) -> Box<dyn SensorSource>) -> SourceDescriptor {
+    SourceDescriptor {
+        id,
+        label: id,
+        create,
+        settings_key: sources::no_settings,
+        fallback: None,
+    }
+}
+
+fn steady(_: &AppSettings, _: &SourceRegistry) -> Box<dyn SensorSource> {
+    Box::new(Fake { id: "steady", down: None, connects: None })
+}
+
+fn selecting(id: &str) -> AppSettings {
+    AppSettings {
+        data_source: Some(id.to_string()),
+        ..AppSettings::default()
+    }
+}
+
+fn message(data: &SensorData) -> &str {
+    data.diagnostics.message.as_deref().unwrap_or_default()
+}
+
+#[test]
+fn registry_lists_and_creates_sources() {
+    let builtin = SourceRegistry::with_builtin();
+    let ids: Vec<String> = builtin.list(&AppSettings::default()).into_iter().map(|s| s.id).collect();
+    assert_eq!(ids.first().map(String::as_str), Some(sources::DEFAULT_SOURCE));
+    for id in ["sysinfo", "composite", "replay", "mock"] {
+        assert!(ids.iter().any(|i| i == id), "{} missing from {:?}", id, ids);
+    }
+    assert_eq!(builtin.get("hwinfo").unwrap().fallback, Some("sysinfo"));
+
+    let mut registry = SourceRegistry::new();
+    registry.register(descriptor("steady", steady));
+    registry.register(SourceDescriptor {
+        label: "Steady again",
+        ..descriptor("steady", steady)
+    });
+    let listed = registry.list(&AppSettings::default());
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].label, "Steady again");
+    assert!(listed[0].capabilities.cpu);
+
+    assert_eq!(registry.create("steady", &AppSettings::default()).unwrap().id(), "steady");
+    assert_eq!(registry.create("nope", &AppSettings::default()).err().unwrap(), "Unknown data source: nope");
+}
+
+#[test]
+fn switches_to_the_selected_source() {
+    let source = ActiveSource::new(SourceRegistry::with_builtin());
+    let data = source.poll(&selecting("mock"));
+    assert_eq!(data.status, "connected");
+    assert_eq!(data.diagnostics.source.as_deref(), Some("mock"));
+
+    let data = source.poll(&selecting("sysinfo"));
+    assert_eq!(data.status, "connected");
+    assert_eq!(data.diagnostics.source.as_deref(), Some("sysinfo"));
+
+    // mock_mode predates data_source and still wins
+    let data = source.poll(&AppSettings { mock_mode: true, ..selecting("sysinfo") });
+    assert_eq!(data.diagnostics.source.as_deref(), Some("mock"));
+}
+
+#[test]
+fn unknown_source_is_not_connected() {
+    let source = ActiveSource::new(SourceRegistry::with_builtin());
+    let data = source.poll(&selecting("nope"));
+    assert_eq!(data.status, "not_connected");
+    assert_eq!(message(&data), "Unknown data source: nope");
+    assert_eq!(data.diagnostics.source.as_deref(), Some("nope"));
+}
+
+static FLAKY_DOWN: AtomicBool = AtomicBool::new(false);
+static FLAKY_CONNECTS: AtomicUsize = AtomicUsize::new(0);
+
+fn flaky(_: &AppSettings, _: &SourceRegistry) -> Box<dyn SensorSource> {
+    Box::new(Fake { id: "flaky", down: Some(&FLAKY_DOWN), connects: Some(&FLAKY_CONNECTS) })
+}
+
+#[test]
+fn reconnects_after_a_failed_poll() {
+    let mut registry = SourceRegistry::new();
+    registry.register(descriptor("flaky", flaky));
+    let source = ActiveSource::new(registry);
+
+    assert_eq!(source.poll(&selecting("flaky")).status, "connected");
+    assert_eq!(source.poll(&selecting("flaky")).status, "connected");
+    assert_eq!(FLAKY_CONNECTS.load(Ordering::SeqCst), 1);
+
+    FLAKY_DOWN.store(true, Ordering::SeqCst);
+    let data = source.poll(&selecting("flaky"));
+    assert_eq!(data.status, "not_connected");
+    assert_eq!(message(&data), "flaky is down");
+
+    FLAKY_DOWN.store(false, Ordering::SeqCst);
+    assert_eq!(source.poll(&selecting("flaky")).status, "connected");
+    assert_eq!(FLAKY_CONNECTS.load(Ordering::SeqCst), 2);
+}
+
+static PRIMARY_DOWN: AtomicBool = AtomicBool::new(true);
+
+fn primary(_: &AppSettings, _: &SourceRegistry) -> Box<dyn SensorSource> {
+    Box::new(Fake { id: "primary", down: Some(&PRIMARY_DOWN), connects: None })
+}
+
+#[test]
+fn falls_back_while_the_selected_source_fails() {
+    let mut registry = SourceRegistry::new();
+    registry.register(SourceDescriptor {
+        fallback: Some("steady"),
+        ..descriptor("primary", primary)
+    });
+    registry.register(SourceDescriptor {
+        label: "Steady",
+        ..descriptor("steady", steady)
+    });
+    let source = ActiveSource::new(registry);
+
+    let data = source.poll(&selecting("primary"));
+    assert_eq!(data.status, "fallback");
+    assert_eq!(data.diagnostics.source.as_deref(), Some("steady"));
+    assert_eq!(message(&data), "primary is down. Showing Steady data instead.");
+
+    PRIMARY_DOWN.store(false, Ordering::SeqCst);
+    let data = source.poll(&selecting("primary"));
+    assert_eq!(data.status, "connected");
+    assert_eq!(data.diagnostics.source.as_deref(), Some("primary"));
+    assert_eq!(data.diagnostics.message, None);
+
+    PRIMARY_DOWN.store(true, Ordering::SeqCst);
+    assert_eq!(source.poll(&selecting("primary")).status, "fallback");
+}
+
+#[cfg(not(windows))]
+#[test]
+fn hwinfo_falls_back_to_sysinfo_off_windows() {
+    let data = ActiveSource::new(SourceRegistry::with_builtin()).poll(&AppSettings::default());
+    assert_eq!(data.status, "fallback");
+    assert_eq!(data.diagnostics.source.as_deref(), Some("sysinfo"));
+    assert_eq!(
+        message(&data),
+        "HWiNFO shared memory is only available on Windows. Showing Generic (sysinfo) data instead."
+    );
+    assert!(data.system.memory_total_mb.is_some());
+}
+
+#[test]
+fn system_info_reads_this_machine() {
+    let mut source = SystemInfoSource::new();
+    assert!(source.capabilities().gpu);
+    source.connect().unwrap();
+    let data = source.poll().unwrap();
+
+    assert_eq!(data.status, "connected");
+    assert_eq!(data.diagnostics.source.as_deref(), Some("sysinfo"));
+    assert!(data.cpu.usage_percent.is_some_and(|u| (0.0..=100.0).contains(&u)));
+    assert!(!data.cpu.core_usage_percent.is_empty());
+    let total = data.system.memory_total_mb.unwrap();
+    assert!(total > 0.0);
+    assert!(data.system.memory_used_mb.is_some_and(|used| used <= total));
+    assert!(data.system.uptime_seconds.is_some());
+    for drive in &data.drives {
+        assert!(drive.free_gb.unwrap() <= drive.total_gb.unwrap(), "{:?}", drive);
+    }
+    assert_eq!(data.storage.name, data.drives.first().and_then(|d| d.name.clone()));
+}
+
+static KEYED_CREATED: AtomicUsize = AtomicUsize::new(0);
+
+fn keyed(_: &AppSettings, _: &SourceRegistry) -> Box<dyn SensorSource> {
+    KEYED_CREATED.fetch_add(1, Ordering::SeqCst);
+    Box::new(Fake { id: "keyed", down: None, connects: None })
+}
+
+#[test]
+fn recreates_a_source_only_when_its_settings_change() {
+    let mut registry = SourceRegistry::new();
+    registry.register(SourceDescriptor {
+        settings_key: |settings, _| format!("{:?}", settings.lhm_url),
+        ..descriptor("keyed", keyed)
+    });
+    let source = ActiveSource::new(registry);
+
+    let settings = selecting("keyed");
+    source.poll(&settings);
+    source.poll(&AppSettings { auto_launch_hwinfo: true, ..settings.clone() });
+    source.poll(&AppSettings { node_exporter_url: Some("http://nas:9100".to_string()), ..settings.clone() });
+    assert_eq!(KEYED_CREATED.load(Ordering::SeqCst), 1);
+
+    source.poll(&AppSettings { lhm_url: Some("http://other:8085".to_string()), ..settings });
+    assert_eq!(KEYED_CREATED.load(Ordering::SeqCst), 2);
+}
+
+fn composite(members: &[&str]) -> AppSettings {
+    AppSettings {
+        composite_sources: Some(members.iter().map(|id| id.to_string()).collect()),
+        // Nothing listens on the discard port
+        lhm_url: Some("http://127.0.0.1:9".to_string()),
+        ..selecting("composite")
+    }
+}
+
+#[test]
+fn composite_merges_its_members() {
+    let source = ActiveSource::new(SourceRegistry::with_builtin());
+
+    let data = source.poll(&composite(&["lhm", "mock"]));
+    assert_eq!(data.status, "connected");
+    assert_eq!(data.diagnostics.source.as_deref(), Some("composite"));
+    assert!(message(&data).starts_with("Combined mock (skipped lhm: "), "{}", message(&data));
+    assert_eq!(data.diagnostics.provenance["cpu.packageTempC"], "mock");
+
+    let mut settings = composite(&["mock", "sysinfo"]);
+    settings.composite_priorities = Some(BTreeMap::from([("cpu.usagePercent".to_string(), vec!["sysinfo".to_string()])]));
+    let data = source.poll(&settings);
+    assert_eq!(message(&data), "Combined mock, sysinfo");
+    assert_eq!(data.diagnostics.provenance["cpu.usagePercent"], "sysinfo");
+    assert_eq!(data.diagnostics.provenance["cpu.packageTempC"], "mock");
+}
+
+#[test]
+fn composite_needs_known_readable_members() {
+    let source = ActiveSource::new(SourceRegistry::with_builtin());
+
+    let data = source.poll(&composite(&["mock", "nope"]));
+    assert_eq!(data.status, "not_connected");
+    assert_eq!(message(&data), "Unknown data source: nope");
+
+    let data = source.poll(&composite(&["lhm"]));
+    assert_eq!(data.status, "not_connected");
+    assert!(message(&data).starts_with("No source could be read. lhm: "), "{}", message(&data));
+
+    let data = source.poll(&composite(&["composite"]));
+    assert_eq!(message(&data), "No sources configured to combine");
+}
+
+static MEMBER_CREATED: AtomicUsize = AtomicUsize::new(0);
+
+fn member(_: &AppSettings, _: &SourceRegistry) -> Box<dyn SensorSource> {
+    MEMBER_CREATED.fetch_add(1, Ordering::SeqCst);
+    Box::new(Fake { id: "member", down: None, connects: None })
+}
+
+#[test]
+fn composite_combines_registered_sources() {
+    let mut registry = SourceRegistry::with_builtin();
+    registry.register(SourceDescriptor {
+        settings_key: |settings, _| format!("{:?}", settings.node_exporter_url),
+        ..descriptor("member", member)
+    });
+    let source = ActiveSource::new(registry);
+
+    let settings = composite(&["member", "mock"]);
+    let data = source.poll(&settings);
+    assert_eq!(message(&data), "Combined member, mock");
+    source.poll(&settings);
+    assert_eq!(MEMBER_CREATED.load(Ordering::SeqCst), 1);
+
+    // A member's settings are the composite's too
+    source.poll(&AppSettings { node_exporter_url: Some("http://nas:9100".to_string()), ..settings });
+    assert_eq!(MEMBER_CREATED.load(Ordering::SeqCst), 2);
+}
+
+/// A two-frame recording, three seconds apart
+fn write_recording(path: &Path) {
+    let mut generator = MockGenerator::new(Box::new(SystemClock), 0);
+    let mut snapshot = Snapshot::new(
+        "mock",
+        HostInfo {
+            hostname: Some("DESKTOP-PC".to_string()),
+            ..HostInfo::default()
+        },
+    );
+    for ms in [0, 3000] {
+        snapshot.push(
+            Duration::from_millis(ms),
+            Err("HWiNFO is not running".to_string()),
+            generator.next_reading().ok(),
+        );
+    }
+    snapshot.save(path).unwrap();
+}
+
+#[test]
+fn replay_plays_at_speed_and_stops_or_loops_at_the_end() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("recording.json");
+    write_recording(&path);
+    let replay = |looping| AppSettings {
+        replay_file: Some(path.display().to_string()),
+        // The six seconds of recording play in 300 ms
+        replay_speed: Some(20.0),
+        replay_loop: Some(looping),
+        ..selecting("replay")
+    };
+    let once = ActiveSource::new(SourceRegistry::with_builtin());
+    let looping = ActiveSource::new(SourceRegistry::with_builtin());
+
+    let started = Instant::now();
+    assert_eq!(message(&once.poll(&replay(false))), "Frame 1 of 2 from DESKTOP-PC (mock)");
+    assert_eq!(message(&looping.poll(&replay(true))), "Frame 1 of 2 from DESKTOP-PC (mock)");
+
+    thread::sleep(Duration::from_millis(200).saturating_sub(started.elapsed()));
+    let data = once.poll(&replay(false));
+    assert_eq!(data.status, "connected");
+    assert_eq!(message(&data), "Frame 2 of 2 from DESKTOP-PC (mock)");
+    assert_eq!(data.diagnostics.source.as_deref(), Some("replay"));
+
+    thread::sleep(Duration::from_millis(400).saturating_sub(started.elapsed()));
+    let data = once.poll(&replay(false));
+    assert_eq!(data.status, "not_connected");
+    assert_eq!(message(&data), "Reached the end of the snapshot");
+    assert_eq!(message(&looping.poll(&replay(true))), "Frame 1 of 2 from DESKTOP-PC (mock)");
+}
+
+#[test]
+fn replay_needs_a_file_with_frames() {
+    let source = ActiveSource::new(SourceRegistry::with_builtin());
+    assert_eq!(message(&source.poll(&selecting("replay"))), "Set replayFile in settings to a snapshot file");
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("empty.json");
+    Snapshot::new("mock", HostInfo::default()).save(&path).unwrap();
+    let data = source.poll(&AppSettings {
+        replay_file: Some(path.display().to_string()),
+        ..selecting("replay")
+    });
+    assert_eq!(data.status, "not_connected");
+    assert_eq!(message(&data), format!("Snapshot {} has no frames", path.display()));
+}
@@ -0,0 +1,38 @@
+#![cfg(unix)]
+
+use instrument_panel_lib::subprocess;
+use std::io::ErrorKind;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+fn sh(script: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.args(["-c", script]);
+    cmd
+}
+
+#[test]
+fn returns_output_and_status() {
+    let output = subprocess::output(&mut sh("echo out; echo err >&2; exit 3"), Duration::from_secs(5)).unwrap();
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(output.stdout, b"out\n");
+    assert_eq!(output.stderr, b"err\n");
+}
+
+#[test]
+fn kills_a_command_that_hangs() {
+    let started = Instant::now();
+    let err = subprocess::output(&mut sh("exec sleep 30"), Duration::from_millis(200)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::TimedOut);
+    assert!(started.elapsed() < Duration::from_secs(5), "took {:?}", started.elapsed());
+}
+
+#[test]
+fn does_not_wait_for_what_the_command_left_running() {
+    // The background sleep keeps stdout open after the shell exits
+    let started = Instant::now();
+    let output = subprocess::output(&mut sh("sleep 30 & echo started"), Duration::from_millis(500)).unwrap();
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"started\n");
+    assert!(started.elapsed() < Duration::from_secs(5), "took {:?}", started.elapsed());
+}
@@ -7,6 +7,7 @@ import { OverviewView } from "./components/views/OverviewView"
 import { CpuDetailView } from "./components/views/CpuDetailView"
 import { GpuDetailView } from "./components/views/GpuDetailView"
 import { StorageDetailView } from "./components/views/StorageDetailView"
+import { OtherSensorsView } from "./components/views/OtherSensorsView"
 import { SetupGuide } from "./components/SetupGuide"
 import "./App.css"
 
@@ -15,10 +16,11 @@ const VIEWS = [
   { id: "cpu", label: "CPU Details" },
   { id: "gpu", label: "GPU Details" },
   { id: "storage", label: "Storage" },
+  { id: "other", label: "Other Sensors" },
 ]
 
 function App() {
-  const { data, isLoading, refresh } = useSensorData(1000)
+  const { data, isLoading, refresh } = useSensorData()
   const { settings, updateSettings } = useSettings()
   const [showSetupGuide, setShowSetupGuide] = useState(false)
   const [activeView, setActiveView] = useState("overview")
@@ -32,7 +34,13 @@ function App() {
     )
   }
 
-  const isConnected = data?.status === "connected"
+  // "fallback" is connected, but to a stand-in for the selected source
+  const status = data?.status ?? "not_connected"
+  const indicator = {
+    connected: { className: "connected", symbol: "●", title: `Reading from ${data?.diagnostics.source ?? "the selected source"}` },
+    fallback: { className: "fallback", symbol: "◐", title: data?.diagnostics.message ?? "Showing fallback data" },
+    not_connected: { className: "disconnected", symbol: "○", title: "Not connected" },
+  }[status]
 
   const renderView = () => {
     switch (activeView) {
@@ -44,6 +52,8 @@ function App() {
         return <GpuDetailView data={data} />
       case "storage":
         return <StorageDetailView data={data} />
+      case "other":
+        return <OtherSensorsView data={data} />
       default:
         return <OverviewView data={data} />
     }
@@ -55,11 +65,11 @@ function App() {
         <div className="app-header-left">
           <h1>Instrument Panel</h1>
           <button
-            className={`status-indicator ${isConnected ? "connected" : "disconnected"}`}
+            className={`status-indicator ${indicator.className}`}
             onClick={() => setShowDataSource(!showDataSource)}
-            title={isConnected ? "Connected to HWiNFO" : "Not connected"}
+            title={indicator.title}
           >
-            {isConnected ? "●" : "○"}
+            {indicator.symbol}
           </button>
         </div>
         <ViewSelector
@@ -1,6 +1,12 @@
-import { useState } from "react"
+import { useState, useEffect } from "react"
 import { invoke } from "@tauri-apps/api/core"
-import { SensorData, AppSettings } from "../types/sensors"
+import {
+  SensorData,
+  AppSettings,
+  DataSourceInfo,
+  CollectorStatus,
+  ScenarioInfo,
+} from "../types/sensors"
 
 interface Props {
   data: SensorData | null
@@ -19,8 +25,59 @@ export function DataSourceCard({
 }: Props) {
   const [showDiagnostics, setShowDiagnostics] = useState(false)
   const [launching, setLaunching] = useState(false)
+  const [sources, setSources] = useState<DataSourceInfo[]>([])
+  const [scenarios, setScenarios] = useState<ScenarioInfo[]>([])
+  const [capturing, setCapturing] = useState(false)
 
-  const isConnected = data?.status === "connected"
+  // A fallback source is read like any other; the badge and message say why
+  const isFallback = data?.status === "fallback"
+  const isConnected = data?.status === "connected" || data?.status === "fallback"
+  const statusClass = isFallback ? "fallback" : isConnected ? "connected" : "disconnected"
+
+  useEffect(() => {
+    invoke<DataSourceInfo[]>("list_data_sources")
+      .then(setSources)
+      .catch(console.error)
+    invoke<ScenarioInfo[]>("list_mock_scenarios")
+      .then(setScenarios)
+      .catch(console.error)
+  }, [])
+
+  const sourcePicker = (
+    <label className="info-row source-select">
+      <span className="label">Source:</span>
+      <select
+        value={settings.dataSource ?? "hwinfo"}
+        disabled={settings.mockMode}
+        onChange={(e) => onSettingsChange({ dataSource: e.target.value })}
+      >
+        {sources.map((s) => (
+          <option key={s.id} value={s.id}>
+            {s.label}
+          </option>
+        ))}
+      </select>
+    </label>
+  )
+
+  const isMock = settings.mockMode || settings.dataSource === "mock"
+  const scenarioPicker = isMock && (
+    <label className="info-row source-select">
+      <span className="label">Scenario:</span>
+      <select
+        value={settings.mockScenario ?? ""}
+        title={scenarios.find((s) => s.id === settings.mockScenario)?.description}
+        onChange={(e) => onSettingsChange({ mockScenario: e.target.value || null })}
+      >
+        <option value="">Default rig</option>
+        {scenarios.map((s) => (
+          <option key={s.id} value={s.id}>
+            {s.name}
+          </option>
+        ))}
+      </select>
+    </label>
+  )
 
   const handleDumpSensors = async () => {
     try {
@@ -62,6 +119,43 @@ export function DataSourceCard({
     }
   }
 
+  const handleDumpRawReadings = () => {
+    console.log(`=== Unmapped readings from ${data?.diagnostics.source ?? "unknown"} ===`)
+    console.table(data?.rawReadings ?? [])
+  }
+
+  const handleDumpCollectors = async () => {
+    try {
+      const collectors = await invoke<CollectorStatus[]>("list_collectors")
+      console.log(`=== External collectors (${collectors.length}) ===`)
+      console.table(
+        collectors.map(({ readings, ...status }) => ({
+          ...status,
+          readings: readings.length,
+        })),
+      )
+    } catch (e) {
+      console.error("Failed to list collectors:", e)
+    }
+  }
+
+  const handleDumpProvenance = () => {
+    console.log("=== Field sources ===")
+    console.table(data?.diagnostics.provenance ?? {})
+  }
+
+  const handleCaptureSnapshot = async (polls: number) => {
+    setCapturing(true)
+    try {
+      const path = await invoke<string>("capture_snapshot", { polls, intervalMs: 1000 })
+      console.log(`=== Snapshot of ${polls} poll(s) written to ${path} ===`)
+    } catch (e) {
+      console.error("Failed to capture snapshot:", e)
+    } finally {
+      setCapturing(false)
+    }
+  }
+
   const handleLaunchHwinfo = async () => {
     setLaunching(true)
     try {
@@ -84,24 +178,31 @@ export function DataSourceCard({
 
   return (
     <div
-      className={`card data-source-card ${isConnected ? "connected" : "disconnected"}`}
+      className={`card data-source-card ${statusClass}`}
     >
       <div className="card-header">
         <h2>Data Source</h2>
         <span
-          className={`status-badge ${isConnected ? "connected" : "disconnected"}`}
+          className={`status-badge ${statusClass}`}
+          title={isFallback ? data?.diagnostics.message : undefined}
         >
-          {isConnected ? "✓ Connected" : "✗ Not Connected"}
+          {isFallback ? "⚠ Fallback" : isConnected ? "✓ Connected" : "✗ Not Connected"}
         </span>
       </div>
 
       {isConnected ? (
         <div className="card-body">
+          {isFallback && data.diagnostics.message && (
+            <p className="warning-message">{data.diagnostics.message}</p>
+          )}
+
           <div className="info-row">
             <span className="label">Last update:</span>
             <span className="value">{getTimeSinceUpdate()}</span>
           </div>
 
+          {sourcePicker}
+
           <label className="toggle-row">
             <input
               type="checkbox"
@@ -111,6 +212,8 @@ export function DataSourceCard({
             <span>Mock mode (fake data for testing)</span>
           </label>
 
+          {scenarioPicker}
+
           <button
             className="link-button"
             onClick={() => setShowDiagnostics(!showDiagnostics)}
@@ -155,6 +258,27 @@ export function DataSourceCard({
                 <button onClick={() => handleDumpReadings("power")}>
                   Dump Power Readings
                 </button>
+                {data.rawReadings.length > 0 && (
+                  <button onClick={handleDumpRawReadings}>
+                    Dump Unmapped Readings ({data.rawReadings.length})
+                  </button>
+                )}
+                {(settings.collectors?.length ?? 0) > 0 && (
+                  <button onClick={handleDumpCollectors}>
+                    Dump Collectors
+                  </button>
+                )}
+                {Object.keys(data.diagnostics.provenance ?? {}).length > 0 && (
+                  <button onClick={handleDumpProvenance}>
+                    Dump Field Sources
+                  </button>
+                )}
+                <button onClick={() => handleCaptureSnapshot(1)} disabled={capturing}>
+                  Capture Snapshot
+                </button>
+                <button onClick={() => handleCaptureSnapshot(10)} disabled={capturing}>
+                  {capturing ? "Capturing..." : "Capture 10s Snapshot"}
+                </button>
               </div>
             </div>
           )}
@@ -163,6 +287,8 @@ export function DataSourceCard({
         <div className="card-body not-connected">
           <p className="message">Sensor source not connected</p>
 
+          {sourcePicker}
+
           <div className="diagnostics">
             <div className="info-row">
               <span>HWiNFO process:</span>
@@ -212,6 +338,8 @@ export function DataSourceCard({
             />
             <span>Mock mode (fake data for testing)</span>
           </label>
+
+          {scenarioPicker}
         </div>
       )}
     </div>
@@ -10,12 +10,21 @@ export function GpuCard({ data }: Props) {
 
   return (
     <MetricCard title="GPU" subtitle={gpu?.name} icon="🎮">
-      <MetricCard.Row
-        label="Hotspot Temp"
-        value={gpu?.hotspotTempC ?? null}
-        unit="°C"
-        warningThreshold={95}
-      />
+      {gpu?.hotspotTempC == null && gpu?.coreTempC != null ? (
+        <MetricCard.Row
+          label="Core Temp"
+          value={gpu.coreTempC}
+          unit="°C"
+          warningThreshold={85}
+        />
+      ) : (
+        <MetricCard.Row
+          label="Hotspot Temp"
+          value={gpu?.hotspotTempC ?? null}
+          unit="°C"
+          warningThreshold={95}
+        />
+      )}
       <MetricCard.Row
         label="Memory Junction"
         value={gpu?.memoryJunctionTempC ?? null}
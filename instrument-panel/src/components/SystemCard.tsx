@@ -32,6 +32,25 @@ export function SystemCard({ data }: Props) {
           {formatUptime(system?.uptimeSeconds ?? null)}
         </span>
       </div>
+      {system?.memoryTotalMb != null && system.memoryUsedMb != null && (
+        <div className="metric-row">
+          <span className="label">Memory</span>
+          <span className="value">
+            {(system.memoryUsedMb / 1024).toFixed(1)} /{" "}
+            {(system.memoryTotalMb / 1024).toFixed(1)} GB
+          </span>
+        </div>
+      )}
+      {system?.loadAverage && (
+        <div className="metric-row">
+          <span className="label">Load</span>
+          <span className="value">
+            {system.loadAverage.one.toFixed(2)}{" "}
+            {system.loadAverage.five.toFixed(2)}{" "}
+            {system.loadAverage.fifteen.toFixed(2)}
+          </span>
+        </div>
+      )}
       <MetricCard.Status
         label="Fan Status"
         status={system?.fanStatus ?? "unknown"}
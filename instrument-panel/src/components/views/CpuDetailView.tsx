@@ -1,4 +1,5 @@
 import { SensorData } from "../../types/sensors"
+import { useSensorSubscription } from "../../hooks/useSensorData"
 
 interface Props {
   data: SensorData | null
@@ -26,6 +27,8 @@ function cleanCpuName(name: string | null | undefined): string {
 }
 
 export function CpuDetailView({ data }: Props) {
+  // Clocks and load move fast; poll quicker while this view is open
+  useSensorSubscription("cpu-detail", 250)
   const cpu = data?.cpu
   const tempWarning = (cpu?.packageTempC ?? 0) >= 85
   const cpuName = cleanCpuName(cpu?.name)
@@ -50,10 +53,29 @@ export function CpuDetailView({ data }: Props) {
             {formatValue(cpu?.packagePowerW, "W")}
           </span>
         </div>
+        {cpu?.corePowerW != null && (
+          <div className="detail-row">
+            <span className="detail-label">Core Power</span>
+            <span className="detail-value">
+              {formatValue(cpu.corePowerW, "W")}
+            </span>
+          </div>
+        )}
+        {cpu?.uncorePowerW != null && (
+          <div className="detail-row">
+            <span className="detail-label">Uncore Power</span>
+            <span className="detail-value">
+              {formatValue(cpu.uncorePowerW, "W")}
+            </span>
+          </div>
+        )}
         {cpu?.coreClockMhz && (
           <div className="detail-row">
             <span className="detail-label">Core Clock</span>
-            <span className="detail-value">{formatMhz(cpu.coreClockMhz)}</span>
+            <span className="detail-value">
+              {formatMhz(cpu.coreClockMhz)}
+              {cpu.maxClockMhz ? ` / ${formatMhz(cpu.maxClockMhz)}` : ""}
+            </span>
           </div>
         )}
         {cpu?.usagePercent !== null && cpu?.usagePercent !== undefined && (
@@ -72,6 +94,22 @@ export function CpuDetailView({ data }: Props) {
             </span>
           </div>
         )}
+        {cpu?.coreClocksMhz && cpu.coreClocksMhz.length > 0 && (
+          <div className="detail-row">
+            <span className="detail-label">Core Clocks</span>
+            <span className="detail-value detail-value-small">
+              {cpu.coreClocksMhz.map((c) => (c / 1000).toFixed(1)).join(" ")}
+            </span>
+          </div>
+        )}
+        {cpu?.coreUsagePercent && cpu.coreUsagePercent.length > 0 && (
+          <div className="detail-row">
+            <span className="detail-label">Core Usage</span>
+            <span className="detail-value detail-value-small">
+              {cpu.coreUsagePercent.map((u) => `${Math.round(u)}%`).join(" ")}
+            </span>
+          </div>
+        )}
       </div>
     </div>
   )
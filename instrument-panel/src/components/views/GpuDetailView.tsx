@@ -1,4 +1,6 @@
+import { useState } from "react"
 import { SensorData } from "../../types/sensors"
+import { useSensorSubscription } from "../../hooks/useSensorData"
 
 interface Props {
   data: SensorData | null
@@ -39,8 +41,14 @@ function cleanGpuName(name: string | null | undefined): string {
 }
 
 export function GpuDetailView({ data }: Props) {
-  const gpu = data?.gpu
+  // Clocks and load move fast; poll quicker while this view is open
+  useSensorSubscription("gpu-detail", 250)
+  const gpus = data?.gpus ?? []
+  const [selectedIndex, setSelectedIndex] = useState(0)
+  // Sources that report a single GPU only fill `gpu`
+  const gpu = gpus.length > 1 ? gpus[selectedIndex] ?? data?.gpu : data?.gpu
   const hotspotWarning = (gpu?.hotspotTempC ?? 0) >= 95
+  const coreWarning = (gpu?.coreTempC ?? 0) >= 85
   const memJunctionWarning = (gpu?.memoryJunctionTempC ?? 0) >= 100
   const gpuName = cleanGpuName(gpu?.name)
 
@@ -48,10 +56,32 @@ export function GpuDetailView({ data }: Props) {
     <div className="detail-view">
       <div className="detail-header">
         <span className="detail-title">GPU</span>
-        {gpuName && <span className="detail-subtitle">{gpuName}</span>}
+        {gpus.length > 1 ? (
+          <select
+            className="drive-selector"
+            value={selectedIndex}
+            onChange={(e) => setSelectedIndex(Number(e.target.value))}
+          >
+            {gpus.map((g, idx) => (
+              <option key={idx} value={idx}>
+                {cleanGpuName(g.name) || `GPU ${idx + 1}`}
+              </option>
+            ))}
+          </select>
+        ) : (
+          gpuName && <span className="detail-subtitle">{gpuName}</span>
+        )}
       </div>
 
       <div className="detail-rows">
+        {gpu?.coreTempC != null && (
+          <div className={`detail-row ${coreWarning ? "warning" : ""}`}>
+            <span className="detail-label">Core Temp</span>
+            <span className="detail-value">
+              {formatValue(gpu.coreTempC, "°C")}
+            </span>
+          </div>
+        )}
         <div className={`detail-row ${hotspotWarning ? "warning" : ""}`}>
           <span className="detail-label">Hotspot Temp</span>
           <span className="detail-value">
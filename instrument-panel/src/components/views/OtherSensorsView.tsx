@@ -0,0 +1,50 @@
+import { SensorData, RawReading } from "../../types/sensors"
+import { useSensorSubscription } from "../../hooks/useSensorData"
+
+interface Props {
+  data: SensorData | null
+}
+
+function formatReading(reading: RawReading): string {
+  const decimals = Number.isInteger(reading.value) ? 0 : 1
+  return `${reading.value.toFixed(decimals)} ${reading.unit}`.trim()
+}
+
+/** Readings without a field of their own: unmapped source readings and external collectors */
+export function OtherSensorsView({ data }: Props) {
+  useSensorSubscription("other-sensors")
+  const readings = data?.rawReadings ?? []
+  const groups = Array.from(new Set(readings.map((r) => r.group)))
+
+  return (
+    <div className="detail-view">
+      <div className="detail-header">
+        <span className="detail-title">Other Sensors</span>
+      </div>
+
+      {groups.length === 0 && (
+        <div className="detail-rows">
+          <div className="detail-row">
+            <span className="detail-label">No other sensors reported</span>
+          </div>
+        </div>
+      )}
+
+      {groups.map((group) => (
+        <div key={group}>
+          <div className="detail-subheader">{group}</div>
+          <div className="detail-rows">
+            {readings
+              .filter((r) => r.group === group)
+              .map((reading, idx) => (
+                <div className="detail-row" key={idx}>
+                  <span className="detail-label">{reading.label}</span>
+                  <span className="detail-value">{formatReading(reading)}</span>
+                </div>
+              ))}
+          </div>
+        </div>
+      ))}
+    </div>
+  )
+}
@@ -1,4 +1,5 @@
 import { SensorData } from "../../types/sensors"
+import { useSensorSubscription } from "../../hooks/useSensorData"
 
 interface Props {
   data: SensorData | null
@@ -45,6 +46,7 @@ function cleanCpuName(name: string | null | undefined): string {
 }
 
 export function OverviewView({ data }: Props) {
+  useSensorSubscription("overview")
   const cpu = data?.cpu
   const gpu = data?.gpu
   const storage = data?.storage
@@ -57,7 +59,9 @@ export function OverviewView({ data }: Props) {
   const driveLetter = primaryDrive?.driveLetter || storage?.name || "Storage"
 
   const cpuTempWarning = (cpu?.packageTempC ?? 0) >= 85
-  const gpuTempWarning = (gpu?.hotspotTempC ?? 0) >= 95
+  // Sources without a hot spot sensor report the cooler core temperature
+  const gpuTempWarning =
+    (gpu?.hotspotTempC ?? 0) >= 95 || (gpu?.coreTempC ?? 0) >= 85
   const storageTempWarning = (primaryDrive?.tempC ?? storage?.nvmeTempC ?? 0) >= 70
 
   return (
@@ -77,7 +81,7 @@ export function OverviewView({ data }: Props) {
         <div className="overview-card-header">GPU</div>
         <div className="overview-card-name">{shortenName(cleanGpuName(gpu?.name))}</div>
         <div className="overview-card-main">
-          {formatTemp(gpu?.hotspotTempC)}
+          {formatTemp(gpu?.hotspotTempC ?? gpu?.coreTempC)}
         </div>
         <div className="overview-card-secondary">
           {formatPower(gpu?.powerW)}
@@ -1,5 +1,6 @@
 import { useState } from "react"
 import { SensorData, DriveData } from "../../types/sensors"
+import { useSensorSubscription } from "../../hooks/useSensorData"
 
 interface Props {
   data: SensorData | null
@@ -35,6 +36,7 @@ function formatSpace(
 }
 
 export function StorageDetailView({ data }: Props) {
+  useSensorSubscription("storage-detail")
   const drives = data?.drives ?? []
   const [selectedIndex, setSelectedIndex] = useState(0)
 
@@ -116,6 +118,32 @@ export function StorageDetailView({ data }: Props) {
                 </span>
               </div>
             )}
+            {selectedDrive.wearPercent != null && (
+              <div className="detail-row">
+                <span className="detail-label">Wear</span>
+                <span className="detail-value">
+                  {formatValue(selectedDrive.wearPercent, "%", 0)}
+                </span>
+              </div>
+            )}
+            {selectedDrive.mediaErrors != null && (
+              <div
+                className={`detail-row ${selectedDrive.mediaErrors > 0 ? "warning" : ""}`}
+              >
+                <span className="detail-label">Media Errors</span>
+                <span className="detail-value">{selectedDrive.mediaErrors}</span>
+              </div>
+            )}
+            {selectedDrive.reallocatedSectors != null && (
+              <div
+                className={`detail-row ${selectedDrive.reallocatedSectors > 0 ? "warning" : ""}`}
+              >
+                <span className="detail-label">Reallocated Sectors</span>
+                <span className="detail-value">
+                  {selectedDrive.reallocatedSectors}
+                </span>
+              </div>
+            )}
           </div>
         </>
       )}
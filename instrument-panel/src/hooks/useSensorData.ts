@@ -1,6 +1,7 @@
-import { useState, useEffect, useCallback } from "react"
+import { useState, useEffect, useCallback, useRef } from "react"
 import { invoke } from "@tauri-apps/api/core"
-import { SensorData } from "../types/sensors"
+import { listen } from "@tauri-apps/api/event"
+import { SensorChange, SensorData, SensorUpdate } from "../types/sensors"
 
 interface UseSensorDataResult {
   data: SensorData | null
@@ -10,34 +11,128 @@ interface UseSensorDataResult {
   lastRefreshTime: Date | null
 }
 
-export function useSensorData(pollingInterval = 1000): UseSensorDataResult {
+let nextSubscriber = 0
+
+// Copy along each changed path, so components see new objects only where
+// values changed. Changes whose path does not exist are skipped.
+function applyChanges(data: SensorData, changes: SensorChange[]): SensorData {
+  return changes.reduce<any>(
+    (root, { path, value, removed }) => setIn(root, path, value, removed),
+    data,
+  )
+}
+
+function setIn(
+  target: any,
+  path: (string | number)[],
+  value: unknown,
+  removed?: boolean,
+): any {
+  if (path.length === 0) return value
+  if (target === null || typeof target !== "object") return target
+  const [key, ...rest] = path
+  const copy = Array.isArray(target) ? [...target] : { ...target }
+  if (rest.length > 0) {
+    if (!(key in copy)) return target
+    copy[key] = setIn(copy[key], rest, value, removed)
+  } else if (removed) {
+    delete copy[key]
+  } else {
+    copy[key] = value
+  }
+  return copy
+}
+
+// Keeps the backend polling while the calling component is mounted, at
+// `intervalMs` or faster when given. Views that show readings subscribe;
+// polling pauses with no subscribers or while every window is hidden.
+export function useSensorSubscription(name: string, intervalMs?: number) {
+  useEffect(() => {
+    const id = `${name}-${nextSubscriber++}`
+    invoke("subscribe_sensor_data", { id, intervalMs }).catch(console.error)
+    return () => {
+      invoke("unsubscribe_sensor_data", { id }).catch(console.error)
+    }
+  }, [name, intervalMs])
+}
+
+// The backend polls on its own schedule and pushes each reading as a
+// snapshot, then as deltas against the previous one. Listening does not
+// keep it polling; see useSensorSubscription.
+export function useSensorData(): UseSensorDataResult {
   const [data, setData] = useState<SensorData | null>(null)
   const [isLoading, setIsLoading] = useState(true)
   const [error, setError] = useState<string | null>(null)
   const [lastRefreshTime, setLastRefreshTime] = useState<Date | null>(null)
+  // Sequence number of the update `data` reflects
+  const seq = useRef<number | null>(null)
+  const resyncing = useRef(false)
 
+  const receive = useCallback((result: SensorData) => {
+    setData(result)
+    setError(null)
+    setLastRefreshTime(new Date())
+    setIsLoading(false)
+  }, [])
+
+  const fail = useCallback((e: unknown) => {
+    setError(e instanceof Error ? e.message : String(e))
+    setIsLoading(false)
+  }, [])
+
+  // The new reading arrives as a sensor-update event
   const refresh = useCallback(async () => {
     try {
-      const result = await invoke<SensorData>("get_sensor_data")
-      setData(result)
-      setError(null)
-      setLastRefreshTime(new Date())
+      await invoke("refresh_sensor_data")
     } catch (e) {
-      setError(e instanceof Error ? e.message : String(e))
+      fail(e)
+    }
+  }, [fail])
+
+  // Start over from the latest snapshot; null until the first poll has finished
+  const resync = useCallback(async () => {
+    if (resyncing.current) return
+    resyncing.current = true
+    try {
+      const snapshot = await invoke<SensorUpdate | null>("resync_sensor_data")
+      if (snapshot?.type === "snapshot") {
+        seq.current = snapshot.seq
+        receive(snapshot.data)
+      } else {
+        // Nothing polled yet, and no view is mounted to start polling while loading
+        await invoke("refresh_sensor_data")
+      }
+    } catch (e) {
+      fail(e)
     } finally {
-      setIsLoading(false)
+      resyncing.current = false
     }
-  }, [])
+  }, [receive, fail])
 
   useEffect(() => {
-    // Initial fetch
-    refresh()
+    const unlisten = listen<SensorUpdate>("sensor-update", ({ payload }) => {
+      if (payload.type === "snapshot") {
+        seq.current = payload.seq
+        receive(payload.data)
+        return
+      }
+      if (seq.current === null || payload.seq !== seq.current + 1) {
+        // Older deltas are already in the snapshot we resynced from
+        if (seq.current === null || payload.seq > seq.current) resync()
+        return
+      }
+      seq.current = payload.seq
+      setData((current) => current && applyChanges(current, payload.changes))
+      setError(null)
+      setLastRefreshTime(new Date())
+    })
 
-    // Set up polling
-    const interval = setInterval(refresh, pollingInterval)
+    resync()
 
-    return () => clearInterval(interval)
-  }, [refresh, pollingInterval])
+    return () => {
+      unlisten.then((stop) => stop())
+    }
+  }, [receive, resync])
 
   return { data, isLoading, error, refresh, lastRefreshTime }
 }
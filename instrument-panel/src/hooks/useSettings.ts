@@ -6,6 +6,24 @@ const DEFAULT_SETTINGS: AppSettings = {
   autoLaunchHwinfo: false,
   hwinfoPath: null,
   mockMode: false,
+  dataSource: null,
+  sysfsRoot: null,
+  procfsRoot: null,
+  lhmUrl: null,
+  lmSensorsCommand: null,
+  lmSensorsFile: null,
+  nvidiaSmiPath: null,
+  smartctlCommand: null,
+  nodeExporterUrl: null,
+  compositeSources: null,
+  compositePriorities: null,
+  collectors: null,
+  mockScenario: null,
+  mockSharedMemory: null,
+  replayFile: null,
+  replaySpeed: null,
+  replayLoop: null,
+  polling: null,
 }
 
 export function useSettings() {
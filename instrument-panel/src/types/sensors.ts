@@ -1,31 +1,42 @@
 // Matches the Rust SensorData struct
 
 export interface SensorData {
-  status: "connected" | "not_connected"
+  status: "connected" | "not_connected" | "fallback"
   lastReadAt: string | null
   diagnostics: {
     hwinfoProcessDetected: boolean
     sharedMemoryDetected: boolean
     message?: string
+    source?: string
+    // Field path (e.g. "gpu.hotspotTempC") → id of the source that supplied it
+    provenance: Record<string, string>
   }
   cpu: CpuData
   gpu: GpuData
+  gpus: GpuData[]
   storage: StorageData
   drives: DriveData[]
   system: SystemData
+  rawReadings: RawReading[]
 }
 
 export interface CpuData {
   name: string | null
   packageTempC: number | null
   packagePowerW: number | null
+  corePowerW: number | null
+  uncorePowerW: number | null
   coreClockMhz: number | null
+  maxClockMhz: number | null
   usagePercent: number | null
   coreTemps: number[]
+  coreClocksMhz: number[]
+  coreUsagePercent: number[]
 }
 
 export interface GpuData {
   name: string | null
+  coreTempC: number | null
   hotspotTempC: number | null
   memoryJunctionTempC: number | null
   powerW: number | null
@@ -51,6 +62,10 @@ export interface DriveData {
   smartHealth: "good" | "warning" | "critical" | "unknown"
   totalGb: number | null
   freeGb: number | null
+  wearPercent: number | null
+  mediaErrors: number | null
+  reallocatedSectors: number | null
+  smartPassed: boolean | null
 }
 
 export interface SystemData {
@@ -58,6 +73,34 @@ export interface SystemData {
   uptimeSeconds: number | null
   fanStatus: "ok" | "warning" | "unknown"
   fans: FanReading[]
+  memoryTotalMb: number | null
+  memoryUsedMb: number | null
+  loadAverage: LoadAverage | null
+}
+
+// Messages on the sensor-update event: a snapshot, then what changed
+export type SensorUpdate =
+  | { type: "snapshot"; seq: number; data: SensorData }
+  | { type: "delta"; seq: number; changes: SensorChange[] }
+
+export interface SensorChange {
+  // Object keys and array indexes
+  path: (string | number)[]
+  value: unknown
+  removed?: boolean
+}
+
+export interface RawReading {
+  group: string
+  label: string
+  value: number
+  unit: string
+}
+
+export interface LoadAverage {
+  one: number
+  five: number
+  fifteen: number
 }
 
 export interface FanReading {
@@ -69,4 +112,75 @@ export interface AppSettings {
   autoLaunchHwinfo: boolean
   hwinfoPath: string | null
   mockMode: boolean
+  dataSource: string | null
+  sysfsRoot: string | null
+  procfsRoot: string | null
+  lhmUrl: string | null
+  lmSensorsCommand: string | null
+  lmSensorsFile: string | null
+  nvidiaSmiPath: string | null
+  smartctlCommand: string | null
+  nodeExporterUrl: string | null
+  compositeSources: string[] | null
+  compositePriorities: Record<string, string[]> | null
+  collectors: CollectorConfig[] | null
+  mockScenario: string | null
+  mockSharedMemory: boolean | null
+  replayFile: string | null
+  replaySpeed: number | null
+  replayLoop: boolean | null
+  polling: PollingPolicy | null
+}
+
+// Every field is optional in settings.json; missing ones take the defaults
+export interface PollingPolicy {
+  focusedMs: number
+  unfocusedMs: number
+  hiddenMs: number
+  minMs: number
+  batteryMinMs: number
+  pauseWhenUnused: boolean
+}
+
+export interface SourceCapabilities {
+  cpu: boolean
+  gpu: boolean
+  storage: boolean
+  fans: boolean
+  uptime: boolean
+}
+
+export interface DataSourceInfo {
+  id: string
+  label: string
+  capabilities: SourceCapabilities
+}
+
+export interface CollectorConfig {
+  command: string
+  args?: string[]
+}
+
+export interface CollectorReading {
+  id: string
+  label: string
+  type: string
+  unit: string
+  value: number
+}
+
+export interface CollectorStatus {
+  command: string
+  name: string | null
+  capabilities: string[]
+  running: boolean
+  restarts: number
+  lastError: string | null
+  readings: CollectorReading[]
+}
+
+export interface ScenarioInfo {
+  id: string
+  name: string
+  description: string
 }